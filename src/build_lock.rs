@@ -0,0 +1,149 @@
+//! Detection of cargo locks a shader build would block on.
+//!
+//! Cargo serialises builds sharing a target directory, and every build sharing
+//! a cargo home, with `flock`ed lock files. A compile started while a host
+//! `cargo build` or rust-analyzer check holds one of them stalls silently, so
+//! the locks are probed before handing over to spirv-builder.
+
+use crate::status::{CompileState, LockHolder};
+use parking_lot::Mutex;
+use std::fs::{File, TryLockError};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+const MAX_BACKOFF: Duration = Duration::from_secs(4);
+/// Longest time to wait for another cargo process before giving up.
+const MAX_WAIT: Duration = Duration::from_secs(300);
+
+/// Waits until none of the cargo locks used by a build in `target_dir` are held.
+///
+/// While waiting, `state` reports the contended lock and its holder. Locks held
+/// by cargo are retried with exponential backoff; a lock held by any other
+/// process fails immediately, since waiting would not release it.
+///
+/// # Errors
+///
+/// Returns an error if a lock is held by a process other than cargo, or if
+/// cargo still holds it after the maximum wait.
+pub(crate) fn wait_for_build_locks(
+    target_dir: &Path,
+    target: &str,
+    profile: &str,
+    state: &Mutex<CompileState>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let started = Instant::now();
+    let mut backoff = INITIAL_BACKOFF;
+    let mut reported = None;
+
+    for path in lock_files(target_dir, target, profile) {
+        while is_locked(&path)? {
+            let holder = lock_holder(&path);
+            if let Some(holder) = &holder
+                && !holder.is_cargo()
+            {
+                return Err(format!(
+                    "{} is locked by {} (pid {})",
+                    path.display(),
+                    holder.name,
+                    holder.pid
+                )
+                .into());
+            }
+            if started.elapsed() >= MAX_WAIT {
+                return Err(format!(
+                    "timed out after {}s waiting for cargo lock {}",
+                    MAX_WAIT.as_secs(),
+                    path.display()
+                )
+                .into());
+            }
+
+            if reported.as_ref() != Some(&path) {
+                match &holder {
+                    Some(holder) => println!(
+                        "Waiting for cargo lock {} held by {} (pid {})",
+                        path.display(),
+                        holder.name,
+                        holder.pid
+                    ),
+                    None => println!("Waiting for cargo lock {}", path.display()),
+                }
+                reported = Some(path.clone());
+            }
+            *state.lock() = CompileState::WaitingForLock {
+                path: path.clone(),
+                holder,
+            };
+
+            std::thread::sleep(backoff);
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    if reported.is_some() {
+        *state.lock() = CompileState::Compiling;
+    }
+    Ok(())
+}
+
+/// Lock files cargo takes for a build of `target` into `target_dir`.
+fn lock_files(target_dir: &Path, target: &str, profile: &str) -> Vec<PathBuf> {
+    let mut files = vec![
+        target_dir.join(profile).join(".cargo-lock"),
+        target_dir.join(target).join(profile).join(".cargo-lock"),
+    ];
+    if let Some(cargo_home) = cargo_home() {
+        files.push(cargo_home.join(".package-cache"));
+    }
+    files
+}
+
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::home_dir().map(|home| home.join(".cargo")))
+}
+
+/// Returns true if another process holds the lock, releasing the probe at once.
+fn is_locked(path: &Path) -> io::Result<bool> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    match file.try_lock() {
+        Ok(()) => Ok(false),
+        Err(TryLockError::WouldBlock) => Ok(true),
+        Err(TryLockError::Error(e)) => Err(e),
+    }
+}
+
+/// Finds the process holding the lock on `path` from `/proc/locks`.
+#[cfg(target_os = "linux")]
+fn lock_holder(path: &Path) -> Option<LockHolder> {
+    use std::os::unix::fs::MetadataExt;
+
+    let inode = std::fs::metadata(path).ok()?.ino();
+    let locks = std::fs::read_to_string("/proc/locks").ok()?;
+    // e.g. "1: FLOCK  ADVISORY  WRITE 12345 fd:01:1234567 0 EOF"
+    locks.lines().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let pid = fields.get(4)?.parse().ok()?;
+        let file_inode: u64 = fields.get(5)?.rsplit(':').next()?.parse().ok()?;
+        if file_inode != inode {
+            return None;
+        }
+        let name = std::fs::read_to_string(format!("/proc/{pid}/comm")).ok()?;
+        Some(LockHolder {
+            pid,
+            name: name.trim().to_string(),
+        })
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn lock_holder(_path: &Path) -> Option<LockHolder> {
+    None
+}
@@ -0,0 +1,95 @@
+//! spirv-builder options forwarded on every compile.
+
+use crate::runner::protocol::{Metadata, PanicStrategy};
+use spirv_builder::{ShaderPanicStrategy, SpirvMetadata};
+
+/// Extra rustflags spirv-builder appends to the ones it passes to cargo.
+const RUSTFLAGS_VAR: &str = "RUSTGPU_RUSTFLAGS";
+
+/// Code generation, validation and cargo settings passed to spirv-builder.
+#[derive(Clone, Debug)]
+pub(crate) struct BuildOptions {
+    pub spirv_metadata: SpirvMetadata,
+    pub shader_panic_strategy: Option<ShaderPanicStrategy>,
+    pub relax_struct_store: bool,
+    pub relax_logical_pointer: bool,
+    pub relax_block_layout: bool,
+    pub uniform_buffer_standard_layout: bool,
+    pub scalar_block_layout: bool,
+    pub skip_block_layout: bool,
+    pub preserve_bindings: bool,
+    /// Arguments for the rust-gpu codegen backend.
+    pub extra_args: Vec<String>,
+    pub rustflags: Vec<String>,
+    /// Environment variables set for the build runner and its cargo invocation.
+    pub env: Vec<(String, String)>,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        Self {
+            // Keeps variable names for reflection and disassembly
+            spirv_metadata: SpirvMetadata::NameVariables,
+            shader_panic_strategy: None,
+            relax_struct_store: false,
+            relax_logical_pointer: false,
+            relax_block_layout: false,
+            uniform_buffer_standard_layout: false,
+            scalar_block_layout: false,
+            skip_block_layout: false,
+            preserve_bindings: false,
+            extra_args: Vec::new(),
+            rustflags: Vec::new(),
+            env: Vec::new(),
+        }
+    }
+}
+
+impl BuildOptions {
+    /// Metadata level in the build runner's format.
+    pub fn metadata(&self) -> Metadata {
+        match self.spirv_metadata {
+            SpirvMetadata::None => Metadata::None,
+            SpirvMetadata::NameVariables => Metadata::NameVariables,
+            SpirvMetadata::Full => Metadata::Full,
+        }
+    }
+
+    /// Panic strategy in the build runner's format.
+    pub fn panic_strategy(&self) -> Option<PanicStrategy> {
+        Some(match self.shader_panic_strategy? {
+            ShaderPanicStrategy::SilentExit => PanicStrategy::SilentExit,
+            ShaderPanicStrategy::DebugPrintfThenExit {
+                print_inputs,
+                print_backtrace,
+            } => PanicStrategy::DebugPrintfThenExit {
+                print_inputs,
+                print_backtrace,
+            },
+            ShaderPanicStrategy::UNSOUND_DO_NOT_USE_UndefinedBehaviorViaUnreachable => {
+                PanicStrategy::UndefinedBehaviorViaUnreachable
+            }
+        })
+    }
+
+    /// Environment variables for the build runner, which spirv-builder passes
+    /// on to cargo.
+    ///
+    /// Rustflags are appended to any `RUSTGPU_RUSTFLAGS` this process has, which
+    /// is passed on even without rustflags so the result covers everything the
+    /// build sees.
+    pub fn build_env(&self) -> Vec<(String, String)> {
+        let mut vars = self.env.clone();
+        let mut rustflags = std::env::var(RUSTFLAGS_VAR).unwrap_or_default();
+        for flag in &self.rustflags {
+            if !rustflags.is_empty() {
+                rustflags.push(' ');
+            }
+            rustflags.push_str(flag);
+        }
+        if !rustflags.is_empty() {
+            vars.push((RUSTFLAGS_VAR.to_string(), rustflags));
+        }
+        vars
+    }
+}
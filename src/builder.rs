@@ -1,5 +1,12 @@
-use crate::{DEFAULT_DEBOUNCE_MS, DEFAULT_TARGET, ShaderHotReloader};
-use spirv_builder::Capability;
+#[cfg(feature = "hot-reload")]
+use crate::ShaderHotReloader;
+use crate::compile::{CompileConfig, LinkStep, Permutation};
+use crate::manifest::Manifest;
+use crate::status::CompileState;
+use crate::targets;
+use crate::{DEFAULT_DEBOUNCE_MS, DeviceProfile, embed, handles};
+use parking_lot::Mutex;
+use spirv_builder::{Capability, ShaderPanicStrategy, SpirvMetadata};
 use std::path::{Path, PathBuf};
 
 /// Builder for configuring a ShaderHotReloader instance.
@@ -11,7 +18,7 @@ use std::path::{Path, PathBuf};
 ///
 /// ```rust,no_run
 /// use rust_gpu_hotreload::ShaderHotReloader;
-/// use spirv_builder::Capability;
+/// use spirv_builder::{Capability, ShaderPanicStrategy, SpirvMetadata};
 /// use std::path::PathBuf;
 ///
 /// let shader_crate_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -28,13 +35,12 @@ use std::path::{Path, PathBuf};
 ///     .expect("Failed to initialise shader hot reloader");
 /// ```
 pub struct ShaderHotReloaderBuilder {
-    shader_crate_path: PathBuf,
-    target: String,
-    capabilities: Vec<Capability>,
-    extensions: Vec<String>,
-    multimodule: bool,
+    /// Settings of the crate currently being configured.
+    config: CompileConfig,
+    /// Crates configured before the last `add_crate` call.
+    crates: Vec<CompileConfig>,
+    #[cfg_attr(not(feature = "hot-reload"), allow(dead_code))]
     debounce_ms: u64,
-    strip_capabilities: Vec<Capability>,
 }
 
 impl ShaderHotReloaderBuilder {
@@ -45,13 +51,9 @@ impl ShaderHotReloaderBuilder {
     /// * `shader_crate_path` - Path to the shader crate directory
     pub fn new(shader_crate_path: impl AsRef<Path>) -> Self {
         Self {
-            shader_crate_path: shader_crate_path.as_ref().to_path_buf(),
-            target: DEFAULT_TARGET.to_string(),
-            capabilities: Vec::new(),
-            extensions: Vec::new(),
-            multimodule: false,
+            config: CompileConfig::new(shader_crate_path.as_ref()),
+            crates: Vec::new(),
             debounce_ms: DEFAULT_DEBOUNCE_MS,
-            strip_capabilities: Vec::new(),
         }
     }
 
@@ -61,13 +63,327 @@ impl ShaderHotReloaderBuilder {
     ///
     /// * `target` - Target string (e.g., "spirv-unknown-vulkan1.3")
     pub fn target(mut self, target: impl Into<String>) -> Self {
-        self.target = target.into();
+        self.config.target = target.into();
+        self
+    }
+
+    /// Sets several SPIR-V targets to build on every compile.
+    ///
+    /// The first target is the main one. Each target builds in its own target
+    /// directory so the builds run in parallel, and tasks pick a target with
+    /// [`ShaderOutputDir::for_target`](crate::ShaderOutputDir::for_target) or
+    /// [`ShaderOutputDir::for_api_version`](crate::ShaderOutputDir::for_api_version).
+    ///
+    /// # Arguments
+    ///
+    /// * `targets` - Target strings (e.g., `["spirv-unknown-vulkan1.1", "spirv-unknown-vulkan1.3"]`)
+    pub fn targets<I, S>(mut self, targets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut targets = targets.into_iter().map(Into::into);
+        if let Some(target) = targets.next() {
+            self.config.target = target;
+            self.config.extra_targets = targets.collect();
+        }
+        self
+    }
+
+    /// Sets the shader crate features to build with, on top of its default features.
+    ///
+    /// Can be changed later with [`ShaderHotReloader::set_features`].
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - Shader crate features to enable (e.g., `["debug_normals"]`)
+    pub fn features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.features = features.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets how much debug metadata the compiled SPIR-V keeps.
+    ///
+    /// Defaults to `SpirvMetadata::NameVariables`, which reflection and
+    /// disassembly use for friendly names.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - Metadata level
+    pub fn spirv_metadata(mut self, metadata: SpirvMetadata) -> Self {
+        self.config.options.spirv_metadata = metadata;
+        self
+    }
+
+    /// Sets what a shader does when it panics.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - Panic strategy (defaults to spirv-builder's `SilentExit`)
+    pub fn shader_panic_strategy(mut self, strategy: ShaderPanicStrategy) -> Self {
+        self.config.options.shader_panic_strategy = Some(strategy);
+        self
+    }
+
+    /// Allows stores to structs whose layouts differ only in decorations.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to pass `--relax-struct-store` to the validator
+    pub fn relax_struct_store(mut self, enabled: bool) -> Self {
+        self.config.options.relax_struct_store = enabled;
+        self
+    }
+
+    /// Allows pointers the logical addressing model otherwise forbids.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to pass `--relax-logical-pointer` to the validator
+    pub fn relax_logical_pointer(mut self, enabled: bool) -> Self {
+        self.config.options.relax_logical_pointer = enabled;
+        self
+    }
+
+    /// Enables `VK_KHR_relaxed_block_layout` rules when validating block layouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to pass `--relax-block-layout` to the validator
+    pub fn relax_block_layout(mut self, enabled: bool) -> Self {
+        self.config.options.relax_block_layout = enabled;
+        self
+    }
+
+    /// Enables `VK_KHR_uniform_buffer_standard_layout` rules when validating block layouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to pass `--uniform-buffer-standard-layout` to the validator
+    pub fn uniform_buffer_standard_layout(mut self, enabled: bool) -> Self {
+        self.config.options.uniform_buffer_standard_layout = enabled;
+        self
+    }
+
+    /// Enables `VK_EXT_scalar_block_layout` rules when validating block layouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to pass `--scalar-block-layout` to the validator
+    pub fn scalar_block_layout(mut self, enabled: bool) -> Self {
+        self.config.options.scalar_block_layout = enabled;
+        self
+    }
+
+    /// Skips block layout validation entirely.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to pass `--skip-block-layout` to the validator
+    pub fn skip_block_layout(mut self, enabled: bool) -> Self {
+        self.config.options.skip_block_layout = enabled;
+        self
+    }
+
+    /// Keeps descriptor bindings the shader declares but never uses.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether unused bindings stay in the module
+    pub fn preserve_bindings(mut self, enabled: bool) -> Self {
+        self.config.options.preserve_bindings = enabled;
+        self
+    }
+
+    /// Adds an argument for the rust-gpu codegen backend.
+    ///
+    /// # Arguments
+    ///
+    /// * `arg` - Codegen argument (e.g., "--no-dce")
+    pub fn extra_arg(mut self, arg: impl Into<String>) -> Self {
+        self.config.options.extra_args.push(arg.into());
+        self
+    }
+
+    /// Adds a flag to the rustflags of the shader crate build.
+    ///
+    /// Passed to spirv-builder through the `RUSTGPU_RUSTFLAGS` environment
+    /// variable of the build runner, after any this process has.
+    ///
+    /// # Arguments
+    ///
+    /// * `flag` - Rustc flag (e.g., "-Zshare-generics=off")
+    pub fn rustflag(mut self, flag: impl Into<String>) -> Self {
+        self.config.options.rustflags.push(flag.into());
+        self
+    }
+
+    /// Sets an environment variable for the cargo invocation of every compile.
+    ///
+    /// Compiles run spirv-builder in a separate runner process, which gets the
+    /// variable and passes it on to cargo. The application's own environment
+    /// is left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Variable name
+    /// * `value` - Variable value
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.config.options.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Declares a named permutation of shader crate features.
+    ///
+    /// Every compile builds the permutation next to the default features, into
+    /// a separate target and output directory. Load its shaders through
+    /// [`ShaderOutputDir::permutation`](crate::ShaderOutputDir::permutation).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Permutation name of ASCII letters, digits, `_` and `-` (e.g., "shadows_hq")
+    /// * `features` - Shader crate features to enable (e.g., `["shadows", "pcf_16"]`)
+    pub fn permutation<I, S>(mut self, name: impl Into<String>, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.permutations.push(Permutation {
+            name: name.into(),
+            features: features.into_iter().map(Into::into).collect(),
+        });
         self
     }
 
     /// Specifies SPIR-V capabilities to remove after compilation
     pub fn strip_capability(mut self, capability: Capability) -> Self {
-        self.strip_capabilities.push(capability);
+        self.config.post_process.strip_capabilities.push(capability);
+        self
+    }
+
+    /// Specifies SPIR-V extensions to remove after compilation.
+    ///
+    /// Compilation fails if a remaining capability still needs the extension.
+    ///
+    /// # Arguments
+    ///
+    /// * `extension` - Extension name (e.g., "SPV_KHR_shader_clock")
+    pub fn strip_extension(mut self, extension: impl Into<String>) -> Self {
+        self.config
+            .post_process
+            .strip_extensions
+            .push(extension.into());
+        self
+    }
+
+    /// Renames a SPIR-V extension declaration after compilation.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Extension name emitted by the compiler
+    /// * `to` - Extension name to declare instead
+    pub fn rename_extension(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.config
+            .post_process
+            .rename_extensions
+            .push((from.into(), to.into()));
+        self
+    }
+
+    /// Specifies an extended instruction set import to remove after compilation.
+    ///
+    /// Every `OpExtInst` from the set is removed with it. Compilation fails if a
+    /// remaining instruction still uses one of their results.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Instruction set name (e.g., "NonSemantic.DebugPrintf")
+    pub fn strip_ext_inst_import(mut self, name: impl Into<String>) -> Self {
+        self.config
+            .post_process
+            .strip_ext_inst_imports
+            .push(name.into());
+        self
+    }
+
+    /// Removes all `NonSemantic.*` instruction sets after compilation.
+    ///
+    /// `SPV_KHR_non_semantic_info` is dropped as well once nothing imports a
+    /// non-semantic set.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to strip non-semantic instruction sets
+    pub fn strip_non_semantic(mut self, enabled: bool) -> Self {
+        self.config.post_process.strip_non_semantic = enabled;
+        self
+    }
+
+    /// Moves a resource to a different descriptor set and binding after compilation.
+    ///
+    /// Both the reflected interface and the loaded `ShaderModule` see the new location.
+    /// Remaps can swap bindings; compiling fails if two remaps share a target, or
+    /// a resource is moved onto a binding another resource keeps.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - `(set, binding)` the shader source declares
+    /// * `to` - `(set, binding)` to decorate the resource with instead
+    pub fn remap_binding(mut self, from: (u32, u32), to: (u32, u32)) -> Self {
+        self.config.post_process.binding_remaps.push((from, to));
+        self
+    }
+
+    /// Exposes an entry point under a different name after compilation.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Entry point name emitted by the compiler (e.g., "main_fs")
+    /// * `to` - Name to expose instead
+    pub fn rename_entry_point(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.config
+            .post_process
+            .entry_point_renames
+            .push((from.into(), to.into()));
+        self
+    }
+
+    /// Changes a specialization constant ID after compilation.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - `SpecId` the shader source declares
+    /// * `to` - `SpecId` to decorate the constant with instead
+    pub fn remap_spec_id(mut self, from: u32, to: u32) -> Self {
+        self.config.post_process.spec_id_remaps.push((from, to));
+        self
+    }
+
+    /// Configures the target, capabilities and extensions a device profile allows.
+    ///
+    /// Replaces the target, and adds the profile's capabilities and extensions
+    /// to any declared already.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - Profile of the device shaders will run on, e.g. from
+    ///   [`DeviceProfile::from_device`]
+    pub fn device_profile(mut self, profile: &DeviceProfile) -> Self {
+        self.config.target = profile.target.clone();
+        for &capability in &profile.capabilities {
+            if !self.config.capabilities.contains(&capability) {
+                self.config.capabilities.push(capability);
+            }
+        }
+        for extension in &profile.extensions {
+            if !self.config.extensions.contains(extension) {
+                self.config.extensions.push(extension.clone());
+            }
+        }
         self
     }
 
@@ -77,7 +393,7 @@ impl ShaderHotReloaderBuilder {
     ///
     /// * `capability` - SPIR-V capability to enable
     pub fn capability(mut self, capability: Capability) -> Self {
-        self.capabilities.push(capability);
+        self.config.capabilities.push(capability);
         self
     }
 
@@ -87,7 +403,22 @@ impl ShaderHotReloaderBuilder {
     ///
     /// * `extension` - Extension name (e.g., "SPV_KHR_ray_tracing")
     pub fn extension(mut self, extension: impl Into<String>) -> Self {
-        self.extensions.push(extension.into());
+        self.config.extensions.push(extension.into());
+        self
+    }
+
+    /// Retries builds that fail for lack of a capability or extension with it added.
+    ///
+    /// Additions last for the session and are reported as a warning, and in
+    /// [`ReloadStatus::session_requirements`](crate::ReloadStatus::session_requirements),
+    /// since the builder configuration is then out of date. Without it, failed
+    /// builds only suggest the missing declarations.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to add missing capabilities and extensions automatically
+    pub fn auto_capabilities(mut self, enabled: bool) -> Self {
+        self.config.auto_capabilities = enabled;
         self
     }
 
@@ -97,7 +428,118 @@ impl ShaderHotReloaderBuilder {
     ///
     /// * `enabled` - Whether to enable multimodule output
     pub fn multimodule(mut self, enabled: bool) -> Self {
-        self.multimodule = enabled;
+        self.config.multimodule = enabled;
+        self
+    }
+
+    /// Splits the single compiled module into one module per entry point.
+    ///
+    /// Each module keeps only what its entry point reaches. Modules are named
+    /// `{entry_point}.spv`, published with the rest of the compile into the next
+    /// generation directory, and listed with their entry point in its
+    /// `manifest.json`. Has no effect together with `multimodule(true)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to split the compiled module
+    pub fn split_entry_points(mut self, enabled: bool) -> Self {
+        self.config.split_entry_points = enabled;
+        self
+    }
+
+    /// Links compiled modules into one module after compilation.
+    ///
+    /// Pairs with `multimodule(true)` so a single `ShaderModule` can serve a
+    /// whole pipeline. The linked module is written to the shader output
+    /// directory next to its inputs.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - File name of the linked module (e.g., "pipeline.spv")
+    /// * `modules` - File names of the modules to link (e.g., `["main_vs.spv", "main_fs.spv"]`)
+    pub fn link_modules<I, S>(mut self, output: impl Into<String>, modules: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.links.push(LinkStep {
+            output: output.into(),
+            inputs: modules.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Sets how many successfully compiled shader generations are kept on disk.
+    ///
+    /// Older generations are available to [`ShaderHotReloader::rollback`] and
+    /// [`ShaderHotReloader::checkout`], and are used if the initial compile fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of generations to keep, including the current one (at least 1)
+    pub fn keep_generations(mut self, count: usize) -> Self {
+        self.config.kept_generations = count;
+        self
+    }
+
+    /// Enables the on-disk compile cache in the given directory.
+    ///
+    /// Outputs are stored under a hash of the shader crate's sources, `Cargo.lock`,
+    /// toolchain and all compile settings. A compile whose inputs were seen
+    /// before, e.g. after switching back to a git branch, restores its shaders
+    /// from the cache instead of invoking spirv-builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory to keep cached outputs in
+    pub fn compile_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.compile_cache = Some(dir.into());
+        self
+    }
+
+    /// Sets the size limit of the compile cache in bytes.
+    ///
+    /// The least recently used entries are removed when the cache grows past it.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Maximum total size of cached outputs (default 256 MiB)
+    pub fn compile_cache_limit(mut self, bytes: u64) -> Self {
+        self.config.compile_cache_limit = bytes;
+        self
+    }
+
+    /// Builds shaders in a dedicated cargo target directory.
+    ///
+    /// By default spirv-builder builds in `target/spirv-builder`, sharing cargo's
+    /// package cache and build script locks with the host workspace. A separate
+    /// directory keeps a concurrent `cargo build` or rust-analyzer check from
+    /// blocking reloads. Use [`ShaderHotReloader::output_dir`] or
+    /// [`ShaderOutputDir::in_target_dir`](crate::ShaderOutputDir::in_target_dir)
+    /// to load the shaders built there.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Target directory, relative to the workspace `target` directory unless absolute
+    pub fn target_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.config.target_dir = Some(dir.into());
+        self
+    }
+
+    /// Adds another shader crate to watch with the same reloader.
+    ///
+    /// Compile settings called after this apply to the new crate only, which
+    /// starts from the defaults. A change recompiles just the crate that owns
+    /// the changed file, and reloads are reported per crate through
+    /// [`ShaderHotReloader::take_reloads`].
+    ///
+    /// # Arguments
+    ///
+    /// * `shader_crate_path` - Path to the shader crate directory
+    pub fn add_crate(mut self, shader_crate_path: impl AsRef<Path>) -> Self {
+        let config = CompileConfig::new(shader_crate_path.as_ref());
+        self.crates
+            .push(std::mem::replace(&mut self.config, config));
         self
     }
 
@@ -119,17 +561,129 @@ impl ShaderHotReloaderBuilder {
     ///
     /// Returns an error if:
     /// - The shader crate path is invalid
+    /// - A target is not a known rust-gpu target, or a capability lacks the
+    ///   extension or SPIR-V version it needs on one of the targets
+    /// - Two crates, or two permutations of a crate, share a name
     /// - Initial compilation fails
     /// - File watcher cannot be initialized
+    #[cfg(feature = "hot-reload")]
     pub fn build(self) -> Result<ShaderHotReloader, Box<dyn std::error::Error>> {
-        ShaderHotReloader::new_with_config(
-            &self.shader_crate_path,
-            &self.target,
-            self.capabilities,
-            self.extensions,
-            self.multimodule,
-            self.debounce_ms,
-            self.strip_capabilities, 
-        )
+        let debounce_ms = self.debounce_ms;
+        ShaderHotReloader::new_with_config(self.finish()?, debounce_ms)
+    }
+
+    /// Compiles every configured crate for inclusion in the executable.
+    ///
+    /// Call from a build script, with the same configuration the application
+    /// gives the hot reloader. Every target and permutation is compiled into
+    /// `OUT_DIR`, and [`embed_shaders!`](crate::embed_shaders) then includes
+    /// the modules. Cargo reruns the build script when a shader crate changes.
+    ///
+    /// Returns the path of the generated file the macro includes. The typed
+    /// handles of [`Self::generate_handles`] are written as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid, `OUT_DIR` is not set,
+    /// or a compile fails.
+    pub fn embed(self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        embed::write_embedded(&self.finish()?)
+    }
+
+    /// Generates typed handles for every module and entry point of the configured crates.
+    ///
+    /// Call from a build script, like [`Self::embed`]. Each crate's main target
+    /// is compiled with its default features into `OUT_DIR`, reusing the
+    /// output of an earlier [`Self::embed`] in the same build script, and
+    /// [`include_shader_handles!`](crate::include_shader_handles) then includes a
+    /// constant per module and entry point, so a renamed entry point fails the
+    /// application build instead of a later load. See [`handles`](crate::handles).
+    ///
+    /// Returns the path of the generated file the macro includes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration is invalid, `OUT_DIR` is not set,
+    /// a compile fails, or two handles of a crate would share a name.
+    pub fn generate_handles(self) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let out_dir = embed::out_dir()?;
+        let state = Mutex::new(CompileState::Idle);
+        let mut manifests = Vec::new();
+        for config in self.finish()? {
+            println!(
+                "cargo:rerun-if-changed={}",
+                config.shader_crate_path.display()
+            );
+            // The main target with the default features comes first
+            let main = config.variants().swap_remove(0);
+            let (_, generation) = embed::compile_into(&out_dir, &main, &state)?;
+            manifests.push((
+                config.crate_name().to_string(),
+                Manifest::read(&generation)?,
+            ));
+        }
+        handles::write(&out_dir, &manifests)
+    }
+
+    /// Checks the configuration of every crate and returns them in builder order.
+    fn finish(mut self) -> Result<Vec<CompileConfig>, Box<dyn std::error::Error>> {
+        self.crates.push(self.config);
+        for (i, config) in self.crates.iter().enumerate() {
+            if self.crates[..i]
+                .iter()
+                .any(|other| other.crate_name() == config.crate_name())
+            {
+                return Err(format!("shader crate {} added twice", config.crate_name()).into());
+            }
+            targets::validate(config)?;
+            for (j, permutation) in config.permutations.iter().enumerate() {
+                // The name becomes a directory name in target and output paths
+                let plain = !permutation.name.is_empty()
+                    && permutation
+                        .name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+                if !plain {
+                    return Err(format!(
+                        "permutation name {:?} of shader crate {} may only contain ASCII letters, digits, '_' and '-'",
+                        permutation.name,
+                        config.crate_name()
+                    )
+                    .into());
+                }
+                if config.permutations[..j]
+                    .iter()
+                    .any(|other| other.name == permutation.name)
+                {
+                    return Err(format!(
+                        "permutation {} of shader crate {} declared twice",
+                        permutation.name,
+                        config.crate_name()
+                    )
+                    .into());
+                }
+            }
+        }
+        Ok(self.crates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_permutation_names_that_are_not_plain() {
+        for name in ["../escape", "/tmp/escape", "a/b", "", "."] {
+            let result = ShaderHotReloaderBuilder::new("shaders")
+                .permutation(name, ["feature"])
+                .finish();
+            assert!(result.is_err(), "{name:?}");
+        }
+        let configs = ShaderHotReloaderBuilder::new("shaders")
+            .permutation("shadows_hq-2", ["feature"])
+            .finish()
+            .unwrap();
+        assert_eq!(configs[0].permutations[0].name, "shadows_hq-2");
     }
 }
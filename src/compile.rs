@@ -1,18 +1,47 @@
 use crate::DEFAULT_TARGET;
+use crate::device_profile::DeviceProfile;
+use crate::embed::EmbeddedFiles;
+use crate::handles::{EntryPointHandle, ShaderHandle};
+use crate::manifest::{MANIFEST_FILE, Manifest};
+use crate::module_cache::ShaderModuleCache;
+use crate::pack::{self, Compression, PackHeader, ShaderPack};
+use crate::publish;
+use crate::spv::{self, Module};
+use crate::targets::target_spirv_version;
 use bevy::prelude::Resource;
-use spirv_builder::Capability;
-use spirv_builder::SpirvBuilder;
-use spirv_builder::{SpirvMetadata};
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use vulkano::Version;
 use vulkano::device::Device;
-use vulkano::shader::{ShaderModule, ShaderModuleCreateInfo};
+use vulkano::shader::{EntryPoint, ShaderModule, ShaderModuleCreateInfo};
+
+#[cfg(feature = "compiler")]
+use {
+    crate::build_lock::wait_for_build_locks,
+    crate::build_options::BuildOptions,
+    crate::compile_cache::{self, CompileCache},
+    crate::diagnostics::{MissingRequirements, recognise},
+    crate::postprocess::PostProcess,
+    crate::publish::{GenerationInfo, Publisher, Staging},
+    crate::runner,
+    crate::status::CompileState,
+    crate::{DEFAULT_COMPILE_CACHE_LIMIT, DEFAULT_KEPT_GENERATIONS},
+    parking_lot::Mutex,
+    spirv::Capability,
+    spirv_builder::ModuleResult,
+    std::sync::OnceLock,
+};
 
 /// Resource for locating and loading compiled SPIR-V shaders.
 ///
 /// Manages the output directory where spirv-builder places compiled shaders
 /// and provides utilities for loading them into Vulkan shader modules.
 ///
+/// Each compile is published as a complete generation, so a load never sees a
+/// half-written module. Use [`ShaderOutputDir::snapshot`] to load several
+/// modules from the same compile.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -41,6 +70,27 @@ use vulkano::shader::{ShaderModule, ShaderModuleCreateInfo};
 #[derive(Resource, Clone)]
 pub struct ShaderOutputDir {
     path: PathBuf,
+    published: PathBuf,
+    pinned: Option<PathBuf>,
+    modules: Arc<ShaderModuleCache>,
+    target: String,
+    /// Feature permutation the shaders were built with, if not the default features.
+    permutation: Option<String>,
+    /// Output directories of every target and permutation the crate is built for.
+    variants: Arc<[ShaderOutputDir]>,
+    /// Where shader files are read from.
+    source: Source,
+}
+
+/// Where a [`ShaderOutputDir`] reads shader files from.
+#[derive(Clone)]
+enum Source {
+    /// Published generations on disk.
+    Disk,
+    /// Files included in the executable. `path` only names them.
+    Embedded(EmbeddedFiles),
+    /// A shader pack. `path` is the pack file.
+    Pack(Arc<ShaderPack>),
 }
 
 impl ShaderOutputDir {
@@ -56,12 +106,127 @@ impl ShaderOutputDir {
         target: Option<&str>,
         profile: Option<&str>,
     ) -> Self {
-        let path = calculate_shader_output_dir(
+        Self::locate(shader_crate_name.as_ref(), target, profile, None)
+    }
+
+    /// Creates a new ShaderOutputDir for shaders built in a dedicated target directory.
+    ///
+    /// Use this with a reloader configured through
+    /// [`ShaderHotReloaderBuilder::target_dir`](crate::ShaderHotReloaderBuilder::target_dir).
+    ///
+    /// # Arguments
+    ///
+    /// * `shader_crate_name` - Name of the shader crate
+    /// * `target` - Optional SPIR-V target (defaults to spirv-unknown-vulkan1.2)
+    /// * `profile` - Optional build profile (defaults to release)
+    /// * `target_dir` - Target directory spirv-builder builds in, relative to the
+    ///   workspace `target` directory unless absolute
+    pub fn in_target_dir(
+        shader_crate_name: impl AsRef<str>,
+        target: Option<&str>,
+        profile: Option<&str>,
+        target_dir: impl AsRef<Path>,
+    ) -> Self {
+        Self::locate(
             shader_crate_name.as_ref(),
-            target.unwrap_or(DEFAULT_TARGET),
-            profile.unwrap_or("release"),
-        );
-        Self { path }
+            target,
+            profile,
+            Some(target_dir.as_ref()),
+        )
+    }
+
+    pub(crate) fn locate(
+        shader_crate_name: &str,
+        target: Option<&str>,
+        profile: Option<&str>,
+        target_dir: Option<&Path>,
+    ) -> Self {
+        let target = target.unwrap_or(DEFAULT_TARGET);
+        let profile = profile.unwrap_or("release");
+        let crate_name = shader_crate_name.replace('-', "_");
+        let deps = spirv_target_dir(target_dir)
+            .join(target)
+            .join(profile)
+            .join("deps");
+        let published = deps.join(format!("{}.published", crate_name));
+        Self {
+            path: deps.join(format!("{}.spvs", crate_name)),
+            modules: ShaderModuleCache::for_root(&published),
+            published,
+            pinned: None,
+            target: target.to_string(),
+            permutation: None,
+            variants: Arc::new([]),
+            source: Source::Disk,
+        }
+    }
+
+    /// Creates a directory serving files included in the executable.
+    ///
+    /// `path` only identifies the directory; nothing is read from it.
+    pub(crate) fn embedded(
+        path: PathBuf,
+        target: &str,
+        permutation: Option<&str>,
+        files: EmbeddedFiles,
+    ) -> Self {
+        Self {
+            published: path.clone(),
+            path,
+            pinned: None,
+            modules: Arc::default(),
+            target: target.to_string(),
+            permutation: permutation.map(str::to_string),
+            variants: Arc::new([]),
+            source: Source::Embedded(files),
+        }
+    }
+
+    /// Creates a ShaderOutputDir serving the shaders in a pack file.
+    ///
+    /// Each file's hash is checked when it is read.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to a pack written by [`Self::export_pack`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid shader pack.
+    pub fn open_pack(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self::from_pack(ShaderPack::open(path)?))
+    }
+
+    /// Creates a ShaderOutputDir serving the shaders in a pack already in memory.
+    ///
+    /// # Arguments
+    ///
+    /// * `pack` - Shader pack, e.g. from [`ShaderPack::from_bytes`]
+    pub fn from_pack(pack: ShaderPack) -> Self {
+        let header = pack.header();
+        Self {
+            path: pack.path().to_path_buf(),
+            published: pack.path().to_path_buf(),
+            pinned: None,
+            modules: Arc::default(),
+            target: header.target.clone(),
+            permutation: header.permutation.clone(),
+            variants: Arc::new([]),
+            source: Source::Pack(Arc::new(pack)),
+        }
+    }
+
+    /// Combines the output directories of one crate built for several targets
+    /// and feature permutations.
+    ///
+    /// Returns the first directory, with the others reachable through
+    /// [`Self::for_target`], [`Self::for_api_version`] and [`Self::permutation`].
+    pub(crate) fn with_variants(dirs: Vec<ShaderOutputDir>) -> Self {
+        let variants: Arc<[ShaderOutputDir]> = dirs.into();
+        Self {
+            variants: variants.clone(),
+            ..variants[0].clone()
+        }
     }
 
     /// Creates a new ShaderOutputDir from shader crate path.
@@ -95,17 +260,292 @@ impl ShaderOutputDir {
     //     &self.path
     // }
 
+    /// Returns true if shaders are served from the executable rather than from disk.
+    pub fn is_embedded(&self) -> bool {
+        matches!(self.source, Source::Embedded(_))
+    }
+
+    /// Returns the SPIR-V target this directory holds shaders for.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// Returns every SPIR-V target the shader crate is built for.
+    pub fn targets(&self) -> Vec<&str> {
+        if self.variants.is_empty() {
+            return vec![self.target.as_str()];
+        }
+        self.variants
+            .iter()
+            .filter(|dir| dir.permutation == self.permutation)
+            .map(|dir| dir.target())
+            .collect()
+    }
+
+    /// Returns the feature permutation this directory holds shaders for.
+    ///
+    /// `None` for shaders built with the crate's default features.
+    pub fn permutation_name(&self) -> Option<&str> {
+        self.permutation.as_deref()
+    }
+
+    /// Returns the names of the feature permutations the shader crate is built with.
+    pub fn permutations(&self) -> Vec<&str> {
+        self.variants
+            .iter()
+            .filter(|dir| dir.target == self.target)
+            .filter_map(|dir| dir.permutation_name())
+            .collect()
+    }
+
+    /// Returns the output directory of the shaders built with a feature permutation.
+    ///
+    /// The target stays the same as this directory's.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Permutation name given to
+    ///   [`ShaderHotReloaderBuilder::permutation`](crate::ShaderHotReloaderBuilder::permutation)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no permutation has that name.
+    pub fn permutation(&self, name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        self.variant(&self.target, Some(name))
+            .ok_or_else(|| format!("no shader permutation named {}", name).into())
+    }
+
+    /// Returns the output directory of the shaders built with the default features.
+    pub fn default_permutation(&self) -> Self {
+        self.variant(&self.target, None)
+            .unwrap_or_else(|| self.clone())
+    }
+
+    /// Returns the output directory of the shaders built for another target.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - SPIR-V target (e.g., "spirv-unknown-vulkan1.1")
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crate is not built for the target.
+    pub fn for_target(&self, target: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        self.variant(target, self.permutation.as_deref())
+            .ok_or_else(|| format!("shaders are not built for target {}", target).into())
+    }
+
+    /// Finds the sibling directory built for `target` and `permutation`.
+    fn variant(&self, target: &str, permutation: Option<&str>) -> Option<Self> {
+        if self.target == target && self.permutation.as_deref() == permutation {
+            return Some(self.clone());
+        }
+        let dir = self
+            .variants
+            .iter()
+            .find(|dir| dir.target == target && dir.permutation.as_deref() == permutation)?;
+        Some(Self {
+            variants: self.variants.clone(),
+            ..dir.clone()
+        })
+    }
+
+    /// Returns the output directory of the newest target a Vulkan version can load.
+    ///
+    /// Pass the device's `api_version()` so a task loads modules it accepts.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_version` - Vulkan API version of the device
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if every target needs a newer SPIR-V version than the
+    /// API version supports.
+    pub fn for_api_version(
+        &self,
+        api_version: Version,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let limit = api_spirv_version(api_version, false);
+        let target = self
+            .targets()
+            .into_iter()
+            .filter_map(|target| Some((target_spirv_version(target)?, target)))
+            .filter(|(version, _)| *version <= limit)
+            .max()
+            .map(|(_, target)| target.to_string())
+            .ok_or_else(|| format!("no shader target loads on Vulkan {}", api_version))?;
+        self.for_target(&target)
+    }
+
+    /// Returns a copy pinned to the currently published generation.
+    ///
+    /// Every load through the snapshot comes from the same compile, even if a
+    /// recompile finishes in between. Take one at the start of a pipeline rebuild.
+    pub fn snapshot(&self) -> Self {
+        Self {
+            pinned: Some(self.generation_dir()),
+            ..self.clone()
+        }
+    }
+
+    /// Directory shaders are loaded from.
+    ///
+    /// This is the pinned generation for a snapshot, otherwise the currently
+    /// published generation. Falls back to the spirv-builder output directory
+    /// if nothing has been published yet. Embedded and packed shaders never
+    /// change, so their directory is always `path`.
+    fn generation_dir(&self) -> PathBuf {
+        if !matches!(self.source, Source::Disk) {
+            return self.path.clone();
+        }
+        self.pinned
+            .clone()
+            .or_else(|| publish::current_generation(&self.published))
+            .unwrap_or_else(|| self.path.clone())
+    }
+
+    /// Reads a shader file from `generation`, the embedded set or the pack.
+    fn read_file(
+        &self,
+        generation: &Path,
+        shader_name: &str,
+    ) -> Result<Cow<'static, [u8]>, Box<dyn std::error::Error>> {
+        match &self.source {
+            Source::Disk => Ok(Cow::Owned(std::fs::read(generation.join(shader_name))?)),
+            Source::Embedded(files) => files
+                .iter()
+                .find(|(name, _)| *name == shader_name)
+                .map(|(_, bytes)| Cow::Borrowed(*bytes))
+                .ok_or_else(|| format!("{} is not embedded", shader_name).into()),
+            Source::Pack(pack) => Ok(Cow::Owned(pack.read(shader_name)?)),
+        }
+    }
+
+    /// Returns the names of the files in the current generation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the generation directory cannot be listed.
+    pub fn files(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        self.files_in(&self.generation_dir())
+    }
+
+    fn files_in(&self, generation: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        match &self.source {
+            Source::Disk => {
+                if let Some(mut info) = publish::generation_info(generation) {
+                    if generation.join(MANIFEST_FILE).is_file() {
+                        info.files.push(MANIFEST_FILE.to_string());
+                    }
+                    return Ok(info.files);
+                }
+                // Unpublished spirv-builder output
+                let mut files = Vec::new();
+                for entry in std::fs::read_dir(generation)? {
+                    let entry = entry?;
+                    if entry.file_type()?.is_file()
+                        && let Some(name) = entry.file_name().to_str()
+                    {
+                        files.push(name.to_string());
+                    }
+                }
+                files.sort();
+                Ok(files)
+            }
+            Source::Embedded(files) => Ok(files.iter().map(|(name, _)| name.to_string()).collect()),
+            Source::Pack(pack) => Ok(pack
+                .header()
+                .files
+                .iter()
+                .map(|file| file.name.clone())
+                .collect()),
+        }
+    }
+
+    /// Returns the manifest of the current generation.
+    ///
+    /// It lists every module with its entry points, hash, size and capabilities.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the generation has no manifest because it is
+    /// unpublished spirv-builder output.
+    pub fn manifest(&self) -> Result<Manifest, Box<dyn std::error::Error>> {
+        let bytes = self.read_file(&self.generation_dir(), MANIFEST_FILE)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Writes the current generation to a single-file shader pack.
+    ///
+    /// The pack records every file with its hash, the reflection metadata of
+    /// each module, the target and the capabilities and extensions the modules
+    /// declare. Load it with [`Self::open_pack`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to write the pack to
+    /// * `compression` - How to store file data in the pack
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a file cannot be read, a module is not valid SPIR-V,
+    /// or the pack cannot be written.
+    pub fn export_pack(
+        &self,
+        path: impl AsRef<Path>,
+        compression: Compression,
+    ) -> Result<PackHeader, Box<dyn std::error::Error>> {
+        let generation = self.generation_dir();
+        let mut files = Vec::new();
+        for name in self.files_in(&generation)? {
+            let bytes = self.read_file(&generation, &name)?.into_owned();
+            files.push((name, bytes));
+        }
+        let generation_number = match &self.source {
+            Source::Disk => publish::generation_info(&generation).map(|info| info.generation),
+            Source::Embedded(_) => None,
+            Source::Pack(pack) => pack.header().generation,
+        };
+        pack::write(
+            path.as_ref(),
+            &self.target,
+            self.permutation.as_deref(),
+            generation_number,
+            &files,
+            compression,
+        )
+    }
+
+    /// Reads a shader file as SPIR-V words.
+    fn read_words(
+        &self,
+        generation: &Path,
+        shader_name: &str,
+    ) -> Result<Vec<u32>, Box<dyn std::error::Error>> {
+        let bytes = self.read_file(generation, shader_name)?;
+        Ok(spv::words_from_bytes(&bytes)?)
+    }
+
     /// Constructs the full path to a specific shader file.
     ///
+    /// Embedded shaders are not on disk, so the path only names them.
+    ///
     /// # Arguments
     ///
     /// * `shader_name` - Name of the shader file (e.g., "main.spv")
     pub fn shader_path(&self, shader_name: impl AsRef<str>) -> PathBuf {
-        self.path.join(shader_name.as_ref())
+        self.generation_dir().join(shader_name.as_ref())
     }
 
     /// Loads a compiled SPIR-V shader into a Vulkan shader module.
     ///
+    /// Modules are cached per device and SPIR-V content, and the cache is shared
+    /// by every `ShaderOutputDir` reading the same shaders. Loading unchanged
+    /// bytes returns the existing module. Modules no longer used outside the
+    /// cache are evicted once [`HotReloadableTask::reload`](crate::HotReloadableTask::reload)
+    /// has rebuilt a task's pipelines; otherwise call [`Self::evict_unused_shaders`].
+    ///
     /// # Arguments
     ///
     /// * `device` - Vulkan device to create the shader module on
@@ -122,8 +562,128 @@ impl ShaderOutputDir {
         device: Arc<Device>,
         shader_name: impl AsRef<str>,
     ) -> Result<Arc<ShaderModule>, Box<dyn std::error::Error>> {
-        let shader_path = self.shader_path(shader_name);
-        load_shader_from_file(device, &shader_path)
+        let generation = self.generation_dir();
+        let shader_path = generation.join(shader_name.as_ref());
+        let shader_bytes = self.read_file(&generation, shader_name.as_ref())?;
+        self.modules
+            .get_or_load(device, &shader_bytes)
+            .map_err(|e| format!("{}: {}", shader_path.display(), e).into())
+    }
+
+    /// Loads a compiled module through a generated [`ShaderHandle`].
+    ///
+    /// Same as [`Self::load_shader`], with the file name checked at build time.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - Vulkan device to create the shader module on
+    /// * `handle` - Module handle from [`include_shader_handles!`](crate::include_shader_handles)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`Self::load_shader`] fails for the module.
+    pub fn load(
+        &self,
+        device: Arc<Device>,
+        handle: ShaderHandle,
+    ) -> Result<Arc<ShaderModule>, Box<dyn std::error::Error>> {
+        self.load_shader(device, handle.file())
+    }
+
+    /// Loads the module of a generated [`EntryPointHandle`] and returns the entry point.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - Vulkan device to create the shader module on
+    /// * `handle` - Entry point handle from [`include_shader_handles!`](crate::include_shader_handles)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the module cannot be loaded, or no longer has the
+    /// entry point with its execution model, e.g. when loading a generation compiled after the handles
+    /// were generated.
+    pub fn load_entry_point(
+        &self,
+        device: Arc<Device>,
+        handle: EntryPointHandle,
+    ) -> Result<EntryPoint, Box<dyn std::error::Error>> {
+        let module = self.load(device, handle.module())?;
+        // A module may declare the same name for several execution models
+        module
+            .entry_point_with_execution(handle.name(), handle.vulkano_execution_model())
+            .ok_or_else(|| {
+                format!(
+                    "{}: no {:?} entry point named {}",
+                    handle.module().file(),
+                    handle.execution_model(),
+                    handle.name()
+                )
+                .into()
+            })
+    }
+
+    /// Checks that shaders only use what a device profile allows, without loading them.
+    ///
+    /// Call before rebuilding any pipeline, so a reload with an incompatible
+    /// module keeps every current pipeline instead of replacing some of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - Profile of the device, e.g. from [`DeviceProfile::from_device`]
+    /// * `shader_names` - Names of the shader files to check
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming every incompatible or unreadable shader and what
+    /// it declares beyond the profile.
+    pub fn check_compatible(
+        &self,
+        profile: &DeviceProfile,
+        shader_names: &[&str],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let generation = self.generation_dir();
+        let mut problems = Vec::new();
+        for name in shader_names {
+            let result = self
+                .read_words(&generation, name)
+                .and_then(|words| Ok(Module::from_words(&words)?))
+                .and_then(|module| Ok(profile.check(&module)?));
+            if let Err(e) = result {
+                problems.push(format!("{}: {}", name, e));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems.join("\n").into())
+        }
+    }
+
+    /// Drops cached shader modules that are no longer used outside the cache.
+    pub fn evict_unused_shaders(&self) {
+        self.modules.evict_unused();
+    }
+
+    /// Disassembles a compiled shader into SPIR-V assembly text.
+    ///
+    /// Ids are shown by their `OpName` where the compiler emitted one.
+    ///
+    /// # Arguments
+    ///
+    /// * `shader_name` - Name of the shader file to disassemble
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the shader file cannot be read or is not valid SPIR-V.
+    pub fn disassemble(
+        &self,
+        shader_name: impl AsRef<str>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let shader_name = shader_name.as_ref();
+        let words = self
+            .read_words(&self.generation_dir(), shader_name)
+            .map_err(|e| format!("{}: {}", shader_name, e))?;
+        Ok(spv::disassemble_words(&words)?)
     }
 }
 
@@ -146,6 +706,48 @@ pub fn calculate_shader_output_dir(
     target: &str,
     profile: &str,
 ) -> PathBuf {
+    ShaderOutputDir::locate(shader_crate_name, Some(target), Some(profile), None).path
+}
+
+/// Calculates the directory post-processed shaders are published to.
+///
+/// Each compile is published as a new generation directory inside it:
+/// `{workspace}/target/spirv-builder/{target}/{profile}/deps/{crate_name}.published`
+///
+/// # Arguments
+///
+/// * `shader_crate_name` - Name of the shader crate
+/// * `target` - SPIR-V target architecture
+/// * `profile` - Build profile (release or debug)
+///
+/// # Panics
+///
+/// Panics if workspace root cannot be determined from environment variables.
+pub fn calculate_published_dir(shader_crate_name: &str, target: &str, profile: &str) -> PathBuf {
+    ShaderOutputDir::locate(shader_crate_name, Some(target), Some(profile), None).published
+}
+
+/// Resolves the directory spirv-builder builds in.
+///
+/// Defaults to `{workspace}/target/spirv-builder`. A relative `target_dir` is
+/// resolved against the workspace `target` directory.
+///
+/// # Panics
+///
+/// Panics if `target_dir` is not absolute and the workspace root cannot be
+/// determined from environment variables.
+pub(crate) fn spirv_target_dir(target_dir: Option<&Path>) -> PathBuf {
+    try_spirv_target_dir(target_dir).expect("Could not determine workspace root")
+}
+
+/// Same as [`spirv_target_dir`], returning an error instead of panicking.
+pub(crate) fn try_spirv_target_dir(target_dir: Option<&Path>) -> Result<PathBuf, String> {
+    if let Some(dir) = target_dir
+        && dir.is_absolute()
+    {
+        return Ok(dir.to_path_buf());
+    }
+
     let workspace_root = std::env::var("CARGO_WORKSPACE_DIR")
         .ok()
         .or_else(|| {
@@ -155,103 +757,411 @@ pub fn calculate_shader_output_dir(
                     .map(|p| p.to_string_lossy().to_string())
             })
         })
-        .expect("Could not determine workspace root");
-
-    let shader_crate_name_normalized = shader_crate_name.replace('-', "_");
+        .ok_or(
+            "could not determine the workspace root: set CARGO_WORKSPACE_DIR, \
+             run through cargo, or configure an absolute target directory",
+        )?;
 
-    PathBuf::from(&workspace_root)
+    Ok(PathBuf::from(&workspace_root)
         .join("target")
-        .join("spirv-builder")
-        .join(target)
-        .join(profile)
-        .join("deps")
-        .join(format!("{}.spvs", shader_crate_name_normalized))
+        .join(target_dir.unwrap_or(Path::new("spirv-builder"))))
 }
 
-pub(crate) fn compile_shaders(
-    shader_crate_path: &Path,
-    target: &str,
-    capabilities: &[Capability],
-    extensions: &[String],
-    multimodule: bool,
-    strip_capabilities: &[Capability],
-) -> Result<(), Box<dyn std::error::Error>> {
-    use std::process::Command;
-    use std::fs;
+/// Settings applied to every (re)compile of the shader crate.
+#[cfg(feature = "compiler")]
+#[derive(Clone)]
+pub(crate) struct CompileConfig {
+    pub shader_crate_path: PathBuf,
+    pub target: String,
+    pub capabilities: Vec<Capability>,
+    pub extensions: Vec<String>,
+    pub multimodule: bool,
+    pub split_entry_points: bool,
+    pub post_process: PostProcess,
+    pub links: Vec<LinkStep>,
+    pub kept_generations: usize,
+    pub compile_cache: Option<PathBuf>,
+    pub compile_cache_limit: u64,
+    pub target_dir: Option<PathBuf>,
+    /// Targets built alongside `target` on every compile.
+    pub extra_targets: Vec<String>,
+    /// Shader crate features to build with, on top of its default features.
+    pub features: Vec<String>,
+    /// Name of the permutation `features` belong to, if any.
+    pub permutation: Option<String>,
+    /// Feature permutations built alongside the default features on every compile.
+    pub permutations: Vec<Permutation>,
+    pub options: BuildOptions,
+    /// Retry failed builds with capabilities and extensions the compiler reports missing.
+    pub auto_capabilities: bool,
+    /// `rustc --version` of the shader crate's toolchain, queried on first use
+    /// and shared by clones.
+    pub toolchain_version: Arc<OnceLock<Vec<u8>>>,
+}
 
-    // Compile with spirv-builder
-    let mut builder = SpirvBuilder::new(shader_crate_path, target);
-    for capability in capabilities {
-        builder = builder.capability(*capability);
-    }
-    for extension in extensions {
-        builder = builder.extension(extension.as_str());
-    }
-    if multimodule {
-        builder = builder.multimodule(true);
+/// A named set of shader crate features.
+#[cfg(feature = "compiler")]
+#[derive(Clone, Debug)]
+pub(crate) struct Permutation {
+    pub name: String,
+    pub features: Vec<String>,
+}
+
+/// Compiled modules to merge into one, by file name.
+#[cfg(feature = "compiler")]
+#[derive(Clone, Debug)]
+pub(crate) struct LinkStep {
+    pub output: String,
+    pub inputs: Vec<String>,
+}
+
+#[cfg(feature = "compiler")]
+impl CompileConfig {
+    pub fn new(shader_crate_path: &Path) -> Self {
+        Self {
+            shader_crate_path: shader_crate_path.to_path_buf(),
+            target: DEFAULT_TARGET.to_string(),
+            capabilities: Vec::new(),
+            extensions: Vec::new(),
+            multimodule: false,
+            split_entry_points: false,
+            post_process: PostProcess::default(),
+            links: Vec::new(),
+            kept_generations: DEFAULT_KEPT_GENERATIONS,
+            compile_cache: None,
+            compile_cache_limit: DEFAULT_COMPILE_CACHE_LIMIT,
+            target_dir: None,
+            extra_targets: Vec::new(),
+            features: Vec::new(),
+            permutation: None,
+            permutations: Vec::new(),
+            options: BuildOptions::default(),
+            auto_capabilities: false,
+            toolchain_version: Arc::new(OnceLock::new()),
+        }
     }
-    builder.spirv_metadata(SpirvMetadata::NameVariables).build()?;
 
-    // Locate output directory
-    let shader_out_dir = calculate_shader_output_dir(
-        shader_crate_path
+    /// Name of the shader crate, taken from its directory name.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the path does not have a valid final component.
+    pub fn crate_name(&self) -> &str {
+        self.shader_crate_path
             .file_name()
             .and_then(|s| s.to_str())
-            .expect("Invalid shader crate path"),
-        target,
-        "release",
-    );
-
-    // Strip unwanted capabilities
-    if shader_out_dir.exists() && !strip_capabilities.is_empty() {
-        for entry in fs::read_dir(&shader_out_dir)? {
-            let entry = entry?;
-            if entry.path().extension().and_then(|s| s.to_str()) == Some("spv") {
-                let spv_path = entry.path();
-                let asm_path = spv_path.with_extension("spvasm");
-
-                // Disassemble SPIR-V
-                Command::new("spirv-dis")
-                    .arg(&spv_path)
-                    .arg("-o")
-                    .arg(&asm_path)
-                    .status()?;
-
-                // Read and filter lines
-                let lines: Vec<String> = fs::read_to_string(&asm_path)?
-                    .lines()
-                    .map(|l| l.to_string())
-                    .filter(|l| {
-                        !strip_capabilities.iter().any(|cap| {
-                            l.contains(&format!("OpCapability {:?}", cap))
-                        })
-                    })
-                    .collect();
-
-                fs::write(&asm_path, lines.join("\n"))?;
-
-                // Reassemble
-                Command::new("spirv-as")
-                    .arg(&asm_path)
-                    .arg("-o")
-                    .arg(&spv_path)
-                    .status()?;
+            .expect("Invalid shader crate path")
+    }
+
+    /// Directory compiled shaders of this crate and target are published to.
+    pub fn published_dir(&self) -> PathBuf {
+        self.output_dir().published
+    }
+
+    /// Output directory following this configuration's target directory.
+    pub fn output_dir(&self) -> ShaderOutputDir {
+        ShaderOutputDir {
+            permutation: self.permutation.clone(),
+            ..ShaderOutputDir::locate(
+                self.crate_name(),
+                Some(&self.target),
+                Some("release"),
+                self.target_dir.as_deref(),
+            )
+        }
+    }
+
+    /// Splits a configuration into one configuration per target and feature permutation.
+    ///
+    /// The main target with the default features comes first. Every other
+    /// variant builds in its own target directory, so cargo does not serialise
+    /// the builds on a shared lock and they can run in parallel.
+    pub fn variants(&self) -> Vec<CompileConfig> {
+        let multi_target = !self.extra_targets.is_empty();
+        let base = self.spirv_target_dir();
+        let mut variants = Vec::new();
+        for target in std::iter::once(&self.target).chain(&self.extra_targets) {
+            let permutations = std::iter::once(None).chain(self.permutations.iter().map(Some));
+            for permutation in permutations {
+                let mut suffix = String::new();
+                if multi_target {
+                    suffix = format!("-{}", target);
+                }
+                if let Some(permutation) = permutation {
+                    suffix = format!("{}-{}", suffix, permutation.name);
+                }
+                let target_dir = if suffix.is_empty() {
+                    self.target_dir.clone()
+                } else {
+                    Some(PathBuf::from(format!("{}{}", base.display(), suffix)))
+                };
+                variants.push(CompileConfig {
+                    target: target.clone(),
+                    target_dir,
+                    extra_targets: Vec::new(),
+                    features: permutation.map_or_else(
+                        || self.features.clone(),
+                        |permutation| permutation.features.clone(),
+                    ),
+                    permutation: permutation.map(|permutation| permutation.name.clone()),
+                    permutations: Vec::new(),
+                    ..self.clone()
+                });
+            }
+        }
+        variants
+    }
+
+    /// Directory spirv-builder builds in.
+    pub fn spirv_target_dir(&self) -> PathBuf {
+        spirv_target_dir(self.target_dir.as_deref())
+    }
+}
+
+/// Compiles the shader crate and publishes the modules as one generation.
+#[cfg(feature = "compiler")]
+pub(crate) fn compile_shaders(
+    config: &CompileConfig,
+    publisher: &Publisher,
+    compile_id: u64,
+    state: &Mutex<CompileState>,
+) -> Result<GenerationInfo, Box<dyn std::error::Error>> {
+    let staging = stage_shaders(config, publisher, state)?;
+    Ok(publisher.publish(staging, &config.target, &config.features, compile_id)?)
+}
+
+/// Compiles the shader crate, or restores it from the compile cache, into a
+/// new staging directory of `publisher`.
+///
+/// Returns the staging directory, ready for [`Publisher::publish`].
+#[cfg(feature = "compiler")]
+pub(crate) fn stage_shaders(
+    config: &CompileConfig,
+    publisher: &Publisher,
+    state: &Mutex<CompileState>,
+) -> Result<Staging, Box<dyn std::error::Error>> {
+    let staging = publisher.begin()?;
+
+    let cache = match &config.compile_cache {
+        Some(dir) => Some((
+            CompileCache::new(dir, config.compile_cache_limit),
+            compile_cache::cache_key(config)?,
+        )),
+        None => None,
+    };
+
+    if let Some((cache, key)) = &cache
+        && cache.restore(key, staging.path())?
+    {
+        println!("Restored shaders from compile cache");
+    } else {
+        build_into(config, staging.path(), state)?;
+        if let Some((cache, key)) = &cache
+            && let Err(e) = cache.store(key, staging.path())
+        {
+            eprintln!("Failed to update compile cache: {}", e);
+        }
+    }
+    Ok(staging)
+}
+
+/// Compiles the shader crate and writes post-processed modules into `staging`.
+#[cfg(feature = "compiler")]
+fn build_into(
+    config: &CompileConfig,
+    staging: &Path,
+    state: &Mutex<CompileState>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let target_dir = config.spirv_target_dir();
+    wait_for_build_locks(&target_dir, &config.target, "release", state)?;
+
+    // Compile with spirv-builder, in the build runner
+    let (result, diagnostics) = runner::build(config);
+    let result = result.map_err(|e| -> Box<dyn std::error::Error> {
+        let suggestions = recognise(&diagnostics, &config.capabilities, &config.extensions);
+        if suggestions.is_empty() {
+            return e;
+        }
+        Box::new(MissingRequirements {
+            suggestions,
+            error: e.to_string(),
+        })
+    })?;
+
+    match &result {
+        ModuleResult::SingleModule(module_path) if config.split_entry_points => {
+            write_split_modules(module_path, staging, &config.post_process)?;
+        }
+        ModuleResult::SingleModule(module_path) => {
+            stage_module(module_path, staging, &config.post_process)?;
+        }
+        ModuleResult::MultiModule(modules) => {
+            for module_path in modules.values() {
+                stage_module(module_path, staging, &config.post_process)?;
             }
         }
     }
 
+    for link in &config.links {
+        let inputs: Vec<PathBuf> = link
+            .inputs
+            .iter()
+            .map(|input| staging.join(input))
+            .collect();
+        link_modules(&inputs, staging.join(&link.output))?;
+    }
+
     Ok(())
 }
 
-fn load_shader_from_file(
+/// Copies a compiled module into `staging`, applying the configured rewrites.
+#[cfg(feature = "compiler")]
+fn stage_module(
+    module_path: &Path,
+    staging: &Path,
+    post_process: &PostProcess,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let file_name = module_path
+        .file_name()
+        .ok_or_else(|| format!("{}: not a file", module_path.display()))?;
+    let mut words = read_spirv_words(module_path)?;
+    if !post_process.is_empty() {
+        // Strip and rewrite capabilities, extensions and instruction sets
+        let mut module = Module::from_words(&words)?;
+        post_process
+            .apply(&mut module)
+            .map_err(|e| format!("{}: {}", module_path.display(), e))?;
+        words = module.to_words();
+    }
+    write_spirv_words(&staging.join(file_name), &words)?;
+    Ok(())
+}
+
+/// Links SPIR-V module files into a single module containing all their entry points.
+///
+/// Ids are renumbered, and identical types, constants and extended instruction
+/// set imports are merged, so one `ShaderModule` can serve a whole pipeline.
+///
+/// # Arguments
+///
+/// * `inputs` - Paths to the modules to link
+/// * `output` - Path to write the linked module to
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - An input cannot be read or is not valid SPIR-V
+/// - Two inputs define an entry point with the same name and execution model
+/// - The inputs use different memory models
+/// - Some inputs are SPIR-V 1.4 or later and others are older
+/// - The linked module cannot be written
+pub fn link_modules(
+    inputs: &[impl AsRef<Path>],
+    output: impl AsRef<Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let modules = inputs
+        .iter()
+        .map(|input| {
+            let input = input.as_ref();
+            Module::from_words(&read_spirv_words(input)?)
+                .map_err(|e| format!("{}: {}", input.display(), e).into())
+        })
+        .collect::<Result<Vec<_>, Box<dyn std::error::Error>>>()?;
+    let linked = spv::link_modules(&modules)?;
+    write_spirv_words(output.as_ref(), &linked.to_words())?;
+    Ok(())
+}
+
+/// Splits a single-module build into one `.spv` per entry point in `out_dir`.
+///
+/// Files are named after their entry point, with the execution model appended
+/// when several entry points share a name. The generation's `manifest.json`
+/// lists each file with its entry point.
+#[cfg(feature = "compiler")]
+fn write_split_modules(
+    module_path: &Path,
+    out_dir: &Path,
+    post_process: &PostProcess,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut module = Module::from_words(&read_spirv_words(module_path)?)?;
+    post_process
+        .apply(&mut module)
+        .map_err(|e| format!("{}: {}", module_path.display(), e))?;
+    let split = spv::split_entry_points(&module)?;
+
+    for (entry_point, module) in &split {
+        let execution_model = format!("{:?}", entry_point.execution_model);
+        let mut file = entry_point_file_stem(&entry_point.name);
+        if split
+            .iter()
+            .filter(|(other, _)| other.name == entry_point.name)
+            .count()
+            > 1
+        {
+            file = format!("{}-{}", file, execution_model.to_lowercase());
+        }
+        file.push_str(".spv");
+
+        write_spirv_words(&out_dir.join(&file), &module.to_words())?;
+    }
+    Ok(())
+}
+
+/// Turns an entry point name such as `lighting::main_fs` into `lighting-main_fs`.
+#[cfg(feature = "compiler")]
+fn entry_point_file_stem(name: &str) -> String {
+    name.replace("::", "-")
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn read_spirv_words(path: &Path) -> Result<Vec<u32>, Box<dyn std::error::Error>> {
+    let shader_bytes = std::fs::read(path)?;
+    Ok(spv::words_from_bytes(&shader_bytes).map_err(|e| format!("{}: {}", path.display(), e))?)
+}
+
+fn write_spirv_words(path: &Path, words: &[u32]) -> std::io::Result<()> {
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    std::fs::write(path, bytes)
+}
+
+/// Loads an in-memory SPIR-V binary into a Vulkan shader module.
+///
+/// The binary may be in either byte order. It is checked before any Vulkan
+/// call is made, and refused if it declares capabilities, extensions or a
+/// SPIR-V version beyond the device's [`DeviceProfile`].
+///
+/// # Arguments
+///
+/// * `device` - Vulkan device to create the shader module on
+/// * `bytes` - SPIR-V binary
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The length is not a multiple of four or the header is incomplete
+/// - The magic number does not match in either byte order
+/// - The SPIR-V version is unknown or newer than the device accepts
+/// - A result id lies outside the declared id bound
+/// - The module declares a capability or extension the device has not enabled
+/// - Vulkan shader module creation fails
+pub fn load_shader_from_bytes(
     device: Arc<Device>,
-    path: &Path,
+    bytes: &[u8],
 ) -> Result<Arc<ShaderModule>, Box<dyn std::error::Error>> {
-    let shader_bytes = std::fs::read(path)?;
-    let shader_words: Vec<u32> = shader_bytes
-        .chunks_exact(4)
-        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-        .collect();
+    let shader_words = spv::words_from_bytes(bytes)?;
+    let module = Module::from_words(&shader_words)?;
+    module.validate()?;
+
+    // Refuse modules the device cannot load before Vulkan sees them
+    DeviceProfile::from_device(&device).check(&module)?;
 
     unsafe {
         Ok(ShaderModule::new(
@@ -260,3 +1170,18 @@ fn load_shader_from_file(
         )?)
     }
 }
+
+/// Highest SPIR-V version a Vulkan API version accepts.
+pub(crate) fn api_spirv_version(api_version: Version, khr_spirv_1_4: bool) -> (u8, u8) {
+    if api_version >= Version::V1_3 {
+        (1, 6)
+    } else if api_version >= Version::V1_2 {
+        (1, 5)
+    } else if khr_spirv_1_4 {
+        (1, 4)
+    } else if api_version >= Version::V1_1 {
+        (1, 3)
+    } else {
+        (1, 0)
+    }
+}
@@ -0,0 +1,478 @@
+//! On-disk cache of post-processed compile outputs, shared across sessions.
+//!
+//! Entries are keyed by a hash of everything that affects the output, so
+//! switching back to a branch that was compiled before restores its shaders
+//! without invoking spirv-builder.
+
+use crate::compile::CompileConfig;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File in every entry recording when it was last stored or restored.
+const LAST_USED_FILE: &str = ".last_used";
+
+/// Compile outputs stored by cache key under a local directory.
+pub(crate) struct CompileCache {
+    dir: PathBuf,
+    size_limit: u64,
+}
+
+impl CompileCache {
+    pub fn new(dir: impl Into<PathBuf>, size_limit: u64) -> Self {
+        Self {
+            dir: dir.into(),
+            size_limit,
+        }
+    }
+
+    /// Copies the entry for `key` into `staging`. Returns false on a miss.
+    pub fn restore(&self, key: &str, staging: &Path) -> io::Result<bool> {
+        let entry = self.dir.join(key);
+        if !entry.is_dir() {
+            return Ok(false);
+        }
+        copy_dir(&entry, staging)?;
+        let _ = fs::remove_file(staging.join(LAST_USED_FILE));
+        touch(&entry)?;
+        Ok(true)
+    }
+
+    /// Stores the contents of `staging` under `key`, then prunes the cache to its size limit.
+    pub fn store(&self, key: &str, staging: &Path) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        // Fill a temporary directory first so a crash never leaves a partial entry
+        let tmp = self.dir.join(format!(".tmp-{key}"));
+        if tmp.exists() {
+            fs::remove_dir_all(&tmp)?;
+        }
+        copy_dir(staging, &tmp)?;
+        touch(&tmp)?;
+        if fs::rename(&tmp, self.dir.join(key)).is_err() {
+            // Another process stored the same entry first
+            fs::remove_dir_all(&tmp)?;
+        }
+
+        self.prune(key)
+    }
+
+    /// Removes the least recently used entries until the cache fits its size limit.
+    fn prune(&self, keep: &str) -> io::Result<()> {
+        let mut entries = Vec::new();
+        let mut total = 0;
+        for entry in fs::read_dir(&self.dir)? {
+            let entry = entry?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with('.') || name == keep || !entry.path().is_dir() {
+                total += dir_size(&entry.path()).unwrap_or(0);
+                continue;
+            }
+            let size = dir_size(&entry.path())?;
+            let last_used = fs::read_to_string(entry.path().join(LAST_USED_FILE))
+                .ok()
+                .and_then(|secs| secs.trim().parse::<u64>().ok())
+                .unwrap_or(0);
+            total += size;
+            entries.push((last_used, size, entry.path()));
+        }
+
+        entries.sort();
+        for (_, size, path) in entries {
+            if total <= self.size_limit {
+                break;
+            }
+            fs::remove_dir_all(&path)?;
+            total -= size;
+        }
+        Ok(())
+    }
+}
+
+/// Hashes everything that affects the compile output into a cache key.
+///
+/// Covers the sources of the shader crate and every path dependency it reaches,
+/// the workspace `Cargo.lock`, the toolchain selected for the shader crate,
+/// every compile and post-process setting, and the environment of the build.
+pub(crate) fn cache_key(config: &CompileConfig) -> io::Result<String> {
+    let mut hasher = blake3::Hasher::new();
+    hash_field(
+        &mut hasher,
+        "crate-version",
+        env!("CARGO_PKG_VERSION").as_bytes(),
+    );
+
+    let packages = local_packages(&config.shader_crate_path);
+    let root = packages
+        .workspace_root
+        .as_deref()
+        .unwrap_or(&config.shader_crate_path);
+    for dir in &packages.dirs {
+        let mut sources = Vec::new();
+        collect_sources(dir, &mut sources)?;
+        sources.sort();
+        for path in sources {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            hash_field(&mut hasher, "path", relative.to_string_lossy().as_bytes());
+            hash_field(&mut hasher, "contents", &fs::read(&path)?);
+        }
+    }
+
+    let lockfile = match &packages.workspace_root {
+        Some(root) => Some(root.join("Cargo.lock")).filter(|path| path.is_file()),
+        None => config
+            .shader_crate_path
+            .ancestors()
+            .map(|dir| dir.join("Cargo.lock"))
+            .find(|path| path.is_file()),
+    };
+    if let Some(lockfile) = lockfile {
+        hash_field(&mut hasher, "lockfile", &fs::read(lockfile)?);
+    }
+
+    // rustup takes the nearest toolchain file; the version alone misses a
+    // change of components or of a nightly's date within the same version
+    let toolchain_file = config.shader_crate_path.ancestors().find_map(|dir| {
+        ["rust-toolchain.toml", "rust-toolchain"]
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+    });
+    if let Some(toolchain_file) = toolchain_file {
+        hash_field(&mut hasher, "toolchain-file", &fs::read(toolchain_file)?);
+    }
+    let toolchain = config.toolchain_version.get_or_init(|| {
+        // rustup resolves the shader crate's toolchain override from its directory
+        Command::new("rustc")
+            .arg("--version")
+            .current_dir(&config.shader_crate_path)
+            .output()
+            .map(|output| output.stdout)
+            .unwrap_or_default()
+    });
+    hash_field(&mut hasher, "toolchain", toolchain);
+
+    let settings = format!(
+        "{:?}",
+        (
+            &config.target,
+            &config.capabilities,
+            &config.extensions,
+            config.multimodule,
+            config.split_entry_points,
+            &config.post_process,
+            &config.links,
+            &config.features,
+            &config.options,
+        )
+    );
+    hash_field(&mut hasher, "settings", settings.as_bytes());
+    // Includes the `RUSTGPU_RUSTFLAGS` inherited from this process
+    let env = format!("{:?}", config.options.build_env());
+    hash_field(&mut hasher, "build-env", env.as_bytes());
+
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+/// Directories whose sources a shader crate is built from.
+struct LocalPackages {
+    /// The shader crate and every path dependency it reaches, sorted.
+    dirs: Vec<PathBuf>,
+    /// Root of the shader crate's workspace, if `cargo metadata` succeeded.
+    workspace_root: Option<PathBuf>,
+}
+
+/// Finds the shader crate's path dependencies with `cargo metadata`.
+///
+/// Falls back to the shader crate alone if cargo fails, e.g. on a broken
+/// manifest, which the build then reports.
+fn local_packages(crate_path: &Path) -> LocalPackages {
+    let crate_dir = fs::canonicalize(crate_path).unwrap_or_else(|_| crate_path.to_path_buf());
+    let mut packages = LocalPackages {
+        dirs: Vec::new(),
+        workspace_root: None,
+    };
+    // Path dependencies of every package seen in `cargo metadata` output
+    let mut dependencies: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    let mut pending = vec![crate_dir.clone()];
+    while let Some(dir) = pending.pop() {
+        if packages.dirs.contains(&dir) {
+            continue;
+        }
+        if !dependencies.contains_key(&dir) {
+            // Path dependencies outside the workspace need their own query
+            match cargo_metadata(&dir) {
+                Some(metadata) => {
+                    if dir == crate_dir {
+                        packages.workspace_root = metadata.workspace_root;
+                    }
+                    dependencies.extend(metadata.packages);
+                }
+                None if dir == crate_dir => {
+                    return LocalPackages {
+                        dirs: vec![crate_path.to_path_buf()],
+                        workspace_root: None,
+                    };
+                }
+                None => {}
+            }
+        }
+        if let Some(paths) = dependencies.get(&dir) {
+            pending.extend(paths.iter().cloned());
+        }
+        packages.dirs.push(dir);
+    }
+    packages.dirs.sort();
+    packages
+}
+
+/// Workspace root, and path dependencies by package directory, of one `cargo metadata` query.
+struct Metadata {
+    workspace_root: Option<PathBuf>,
+    packages: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+fn cargo_metadata(dir: &Path) -> Option<Metadata> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--no-deps",
+            "--offline",
+        ])
+        .arg("--manifest-path")
+        .arg(dir.join("Cargo.toml"))
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    let metadata: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+
+    let canonical = |path: &str| fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+    let packages = metadata["packages"]
+        .as_array()?
+        .iter()
+        .filter_map(|package| {
+            let manifest = canonical(package["manifest_path"].as_str()?);
+            let paths = package["dependencies"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|dependency| Some(canonical(dependency["path"].as_str()?)))
+                .collect();
+            Some((manifest.parent()?.to_path_buf(), paths))
+        })
+        .collect();
+    Some(Metadata {
+        workspace_root: metadata["workspace_root"].as_str().map(PathBuf::from),
+        packages,
+    })
+}
+
+/// Hashes a labelled, length-prefixed field so adjacent fields cannot run together.
+fn hash_field(hasher: &mut blake3::Hasher, label: &str, bytes: &[u8]) {
+    hasher.update(label.as_bytes());
+    hasher.update(&(bytes.len() as u64).to_le_bytes());
+    hasher.update(bytes);
+}
+
+/// Collects source files, skipping build output and hidden directories.
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        if name.starts_with('.') || name == "target" {
+            continue;
+        }
+        if path.is_dir() {
+            collect_sources(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        if path.is_dir() {
+            copy_dir(&path, &to.join(name))?;
+        } else {
+            fs::copy(&path, to.join(name))?;
+        }
+    }
+    Ok(())
+}
+
+fn dir_size(dir: &Path) -> io::Result<u64> {
+    let mut size = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        size += if entry.path().is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            entry.metadata()?.len()
+        };
+    }
+    Ok(size)
+}
+
+fn touch(entry: &Path) -> io::Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    fs::write(entry.join(LAST_USED_FILE), now.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "rust-gpu-hotreload-compile-cache-{}-{name}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A config for a shader crate holding `lib.rs`, with a fixed toolchain.
+    fn shader_crate(dir: &Path, lib: &str) -> CompileConfig {
+        let crate_dir = dir.join("shaders");
+        fs::create_dir_all(crate_dir.join("src")).unwrap();
+        fs::write(crate_dir.join("src/lib.rs"), lib).unwrap();
+        let config = CompileConfig::new(&crate_dir);
+        config
+            .toolchain_version
+            .set(b"rustc 1.0.0".to_vec())
+            .unwrap();
+        config
+    }
+
+    /// Adds an entry of `size` bytes last used at `last_used`.
+    fn entry(cache: &Path, key: &str, size: usize, last_used: u64) {
+        let entry = cache.join(key);
+        fs::create_dir_all(&entry).unwrap();
+        fs::write(entry.join("shader.spv"), vec![0; size]).unwrap();
+        fs::write(entry.join(LAST_USED_FILE), last_used.to_string()).unwrap();
+    }
+
+    fn entries(cache: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(cache)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn keys_change_with_sources_and_settings() {
+        let dir = temp_dir("keys");
+        let mut config = shader_crate(&dir, "pub fn main() {}");
+        let key = cache_key(&config).unwrap();
+        assert_eq!(cache_key(&config).unwrap(), key);
+
+        // Hidden files and build output are not sources
+        let crate_dir = config.shader_crate_path.clone();
+        fs::write(crate_dir.join(".notes"), "todo").unwrap();
+        fs::create_dir_all(crate_dir.join("target")).unwrap();
+        fs::write(crate_dir.join("target/out.spv"), [1, 2, 3]).unwrap();
+        assert_eq!(cache_key(&config).unwrap(), key);
+
+        fs::write(crate_dir.join("src/lib.rs"), "pub fn main() { }").unwrap();
+        let edited = cache_key(&config).unwrap();
+        assert_ne!(edited, key);
+
+        config.features.push("fancy".to_string());
+        assert_ne!(cache_key(&config).unwrap(), edited);
+        config.features.clear();
+
+        config.options.rustflags.push("-Cdebuginfo=2".to_string());
+        assert_ne!(cache_key(&config).unwrap(), edited);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keys_include_inherited_rustflags() {
+        let dir = temp_dir("rustflags");
+        let config = shader_crate(&dir, "pub fn main() {}");
+        let key = cache_key(&config).unwrap();
+
+        // SAFETY: no other test reads or writes this variable
+        unsafe { std::env::set_var("RUSTGPU_RUSTFLAGS", "-Copt-level=0") };
+        let inherited = cache_key(&config);
+        unsafe { std::env::remove_var("RUSTGPU_RUSTFLAGS") };
+        assert_ne!(inherited.unwrap(), key);
+        assert_eq!(cache_key(&config).unwrap(), key);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn restores_stored_entries() {
+        let dir = temp_dir("restore");
+        let cache = CompileCache::new(dir.join("cache"), u64::MAX);
+        let staging = dir.join("staging");
+        fs::create_dir_all(staging.join("nested")).unwrap();
+        fs::write(staging.join("shader.spv"), [1, 2, 3, 4]).unwrap();
+        fs::write(staging.join("nested/manifest.json"), "{}").unwrap();
+
+        let restored = dir.join("restored");
+        assert!(!cache.restore("key", &restored).unwrap());
+        assert!(!restored.exists());
+
+        cache.store("key", &staging).unwrap();
+        assert_eq!(entries(&dir.join("cache")), ["key"]);
+        assert!(cache.restore("key", &restored).unwrap());
+        assert_eq!(fs::read(restored.join("shader.spv")).unwrap(), [1, 2, 3, 4]);
+        assert_eq!(
+            fs::read_to_string(restored.join("nested/manifest.json")).unwrap(),
+            "{}"
+        );
+        // Bookkeeping stays in the cache
+        assert!(!restored.join(LAST_USED_FILE).exists());
+        assert!(!cache.restore("other", &dir.join("missed")).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn prunes_least_recently_used_entries() {
+        let dir = temp_dir("prune");
+        let cache_dir = dir.join("cache");
+        entry(&cache_dir, "a", 1000, 1);
+        entry(&cache_dir, "b", 1000, 2);
+        entry(&cache_dir, "c", 1000, 3);
+        // Hidden entries count towards the size but are never removed
+        entry(&cache_dir, ".tmp-crashed", 500, 0);
+        let staging = dir.join("staging");
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(staging.join("shader.spv"), vec![0; 1000]).unwrap();
+
+        // Restoring `a` makes `b` the least recently used
+        let cache = CompileCache::new(&cache_dir, 3600);
+        assert!(cache.restore("a", &dir.join("restored")).unwrap());
+        cache.store("d", &staging).unwrap();
+        assert_eq!(entries(&cache_dir), [".tmp-crashed", "a", "c", "d"]);
+
+        // The entry just stored is kept even when it alone exceeds the limit
+        let cache = CompileCache::new(&cache_dir, 0);
+        cache.store("e", &staging).unwrap();
+        assert_eq!(entries(&cache_dir), [".tmp-crashed", "e"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,948 @@
+//! SPIR-V capabilities, extensions and version a Vulkan device accepts.
+//!
+//! The mapping from Vulkan features and extensions is kept in tables, so a
+//! profile depends only on plain vulkano structs and can be built without a GPU.
+
+use crate::compile::api_spirv_version;
+use crate::requirements;
+use crate::spv::Module;
+use crate::targets::target_spirv_version;
+use spirv::Capability;
+use std::fmt;
+use std::sync::Arc;
+use vulkano::Version;
+use vulkano::device::{Device, DeviceExtensions, DeviceFeatures};
+
+/// Capabilities every Vulkan implementation supports.
+const BASE_CAPABILITIES: &[Capability] = &[
+    Capability::Shader,
+    Capability::Matrix,
+    Capability::InputAttachment,
+    Capability::Sampled1D,
+    Capability::Image1D,
+    Capability::SampledBuffer,
+    Capability::ImageBuffer,
+    Capability::ImageQuery,
+    Capability::DerivativeControl,
+];
+
+/// Whether a device enables something, and the capabilities that enables.
+type CapabilityRow<T> = (fn(&T) -> bool, &'static [Capability]);
+/// Whether a device extension is enabled, its SPIR-V extension and the SPIR-V
+/// version that made it core.
+type ExtensionRow = (
+    fn(&DeviceExtensions) -> bool,
+    &'static str,
+    Option<(u8, u8)>,
+);
+
+/// Capabilities enabled by a device feature.
+const FEATURE_CAPABILITIES: &[CapabilityRow<DeviceFeatures>] = &[
+    (|f| f.shader_float64, &[Capability::Float64]),
+    (|f| f.shader_int64, &[Capability::Int64]),
+    (|f| f.shader_int16, &[Capability::Int16]),
+    (|f| f.shader_int8, &[Capability::Int8]),
+    (|f| f.shader_float16, &[Capability::Float16]),
+    (|f| f.geometry_shader, &[Capability::Geometry]),
+    (|f| f.tessellation_shader, &[Capability::Tessellation]),
+    (|f| f.shader_clip_distance, &[Capability::ClipDistance]),
+    (|f| f.shader_cull_distance, &[Capability::CullDistance]),
+    (|f| f.sample_rate_shading, &[Capability::SampleRateShading]),
+    (|f| f.image_cube_array, &[Capability::ImageCubeArray]),
+    (|f| f.multi_viewport, &[Capability::MultiViewport]),
+    (|f| f.shader_resource_min_lod, &[Capability::MinLod]),
+    (
+        |f| f.shader_resource_residency,
+        &[Capability::SparseResidency],
+    ),
+    (
+        |f| f.shader_image_gather_extended,
+        &[Capability::ImageGatherExtended],
+    ),
+    (
+        |f| f.shader_storage_image_extended_formats,
+        &[Capability::StorageImageExtendedFormats],
+    ),
+    (
+        |f| f.shader_storage_image_multisample,
+        &[Capability::StorageImageMultisample],
+    ),
+    (
+        |f| f.shader_storage_image_read_without_format,
+        &[Capability::StorageImageReadWithoutFormat],
+    ),
+    (
+        |f| f.shader_storage_image_write_without_format,
+        &[Capability::StorageImageWriteWithoutFormat],
+    ),
+    (
+        |f| f.shader_uniform_buffer_array_dynamic_indexing,
+        &[Capability::UniformBufferArrayDynamicIndexing],
+    ),
+    (
+        |f| f.shader_sampled_image_array_dynamic_indexing,
+        &[Capability::SampledImageArrayDynamicIndexing],
+    ),
+    (
+        |f| f.shader_storage_buffer_array_dynamic_indexing,
+        &[Capability::StorageBufferArrayDynamicIndexing],
+    ),
+    (
+        |f| f.shader_storage_image_array_dynamic_indexing,
+        &[Capability::StorageImageArrayDynamicIndexing],
+    ),
+    (
+        |f| f.storage_buffer16_bit_access,
+        &[Capability::StorageBuffer16BitAccess],
+    ),
+    (
+        |f| f.uniform_and_storage_buffer16_bit_access,
+        &[Capability::UniformAndStorageBuffer16BitAccess],
+    ),
+    (
+        |f| f.storage_push_constant16,
+        &[Capability::StoragePushConstant16],
+    ),
+    (
+        |f| f.storage_input_output16,
+        &[Capability::StorageInputOutput16],
+    ),
+    (
+        |f| f.storage_buffer8_bit_access,
+        &[Capability::StorageBuffer8BitAccess],
+    ),
+    (
+        |f| f.uniform_and_storage_buffer8_bit_access,
+        &[Capability::UniformAndStorageBuffer8BitAccess],
+    ),
+    (
+        |f| f.storage_push_constant8,
+        &[Capability::StoragePushConstant8],
+    ),
+    (|f| f.multiview, &[Capability::MultiView]),
+    (
+        |f| f.variable_pointers_storage_buffer,
+        &[Capability::VariablePointersStorageBuffer],
+    ),
+    (|f| f.variable_pointers, &[Capability::VariablePointers]),
+    (|f| f.shader_draw_parameters, &[Capability::DrawParameters]),
+    (
+        |f| f.runtime_descriptor_array,
+        &[Capability::RuntimeDescriptorArray],
+    ),
+    (
+        |f| f.shader_input_attachment_array_dynamic_indexing,
+        &[Capability::InputAttachmentArrayDynamicIndexing],
+    ),
+    (
+        |f| f.shader_uniform_buffer_array_non_uniform_indexing,
+        &[Capability::UniformBufferArrayNonUniformIndexing],
+    ),
+    (
+        |f| f.shader_sampled_image_array_non_uniform_indexing,
+        &[Capability::SampledImageArrayNonUniformIndexing],
+    ),
+    (
+        |f| f.shader_storage_buffer_array_non_uniform_indexing,
+        &[Capability::StorageBufferArrayNonUniformIndexing],
+    ),
+    (
+        |f| f.shader_storage_image_array_non_uniform_indexing,
+        &[Capability::StorageImageArrayNonUniformIndexing],
+    ),
+    (|f| f.vulkan_memory_model, &[Capability::VulkanMemoryModel]),
+    (
+        |f| f.vulkan_memory_model_device_scope,
+        &[Capability::VulkanMemoryModelDeviceScope],
+    ),
+    (
+        |f| f.buffer_device_address,
+        &[Capability::PhysicalStorageBufferAddresses],
+    ),
+    (
+        |f| f.shader_demote_to_helper_invocation,
+        &[Capability::DemoteToHelperInvocation],
+    ),
+    (
+        |f| f.shader_integer_dot_product,
+        &[
+            Capability::DotProduct,
+            Capability::DotProductInputAll,
+            Capability::DotProductInput4x8Bit,
+            Capability::DotProductInput4x8BitPacked,
+        ],
+    ),
+    (
+        |f| {
+            f.shader_buffer_int64_atomics
+                || f.shader_shared_int64_atomics
+                || f.shader_image_int64_atomics
+        },
+        &[Capability::Int64Atomics],
+    ),
+    (
+        |f| f.shader_image_int64_atomics,
+        &[Capability::Int64ImageEXT],
+    ),
+    (
+        |f| {
+            f.shader_buffer_float32_atomic_add
+                || f.shader_shared_float32_atomic_add
+                || f.shader_image_float32_atomic_add
+                || f.sparse_image_float32_atomic_add
+        },
+        &[Capability::AtomicFloat32AddEXT],
+    ),
+    (
+        |f| f.shader_buffer_float64_atomic_add || f.shader_shared_float64_atomic_add,
+        &[Capability::AtomicFloat64AddEXT],
+    ),
+    (
+        |f| f.shader_subgroup_clock || f.shader_device_clock,
+        &[Capability::ShaderClockKHR],
+    ),
+    (|f| f.ray_tracing_pipeline, &[Capability::RayTracingKHR]),
+    (|f| f.ray_query, &[Capability::RayQueryKHR]),
+    (
+        |f| f.fragment_shader_pixel_interlock,
+        &[Capability::FragmentShaderPixelInterlockEXT],
+    ),
+    (
+        |f| f.fragment_shader_sample_interlock,
+        &[Capability::FragmentShaderSampleInterlockEXT],
+    ),
+    (
+        |f| {
+            f.pipeline_fragment_shading_rate
+                || f.primitive_fragment_shading_rate
+                || f.attachment_fragment_shading_rate
+        },
+        &[Capability::FragmentShadingRateKHR],
+    ),
+    (|f| f.mesh_shader, &[Capability::MeshShadingEXT]),
+    (
+        |f| f.workgroup_memory_explicit_layout,
+        &[Capability::WorkgroupMemoryExplicitLayoutKHR],
+    ),
+    (
+        |f| f.workgroup_memory_explicit_layout8_bit_access,
+        &[Capability::WorkgroupMemoryExplicitLayout8BitAccessKHR],
+    ),
+    (
+        |f| f.workgroup_memory_explicit_layout16_bit_access,
+        &[Capability::WorkgroupMemoryExplicitLayout16BitAccessKHR],
+    ),
+    (
+        |f| f.shader_subgroup_rotate,
+        &[Capability::GroupNonUniformRotateKHR],
+    ),
+    (
+        |f| f.cooperative_matrix,
+        &[Capability::CooperativeMatrixKHR],
+    ),
+    (|f| f.shader_expect_assume, &[Capability::ExpectAssumeKHR]),
+];
+
+/// Capabilities a device extension enables without a feature, e.g. on
+/// devices older than the feature struct.
+const EXTENSION_CAPABILITIES: &[CapabilityRow<DeviceExtensions>] = &[
+    (
+        |e| e.khr_shader_draw_parameters,
+        &[Capability::DrawParameters],
+    ),
+    (
+        |e| e.khr_format_feature_flags2,
+        &[
+            Capability::StorageImageReadWithoutFormat,
+            Capability::StorageImageWriteWithoutFormat,
+        ],
+    ),
+    (
+        |e| e.ext_shader_stencil_export,
+        &[Capability::StencilExportEXT],
+    ),
+    (
+        |e| e.ext_shader_viewport_index_layer,
+        &[Capability::ShaderViewportIndexLayerEXT],
+    ),
+];
+
+/// Capabilities every device of an API version supports, with no feature or
+/// extension to enable.
+const CORE_CAPABILITIES: &[(Version, &[Capability])] = &[
+    (Version::V1_1, &[Capability::GroupNonUniform]),
+    // Format feature flags decide per format, see VK_KHR_format_feature_flags2
+    (
+        Version::V1_3,
+        &[
+            Capability::StorageImageReadWithoutFormat,
+            Capability::StorageImageWriteWithoutFormat,
+        ],
+    ),
+];
+
+/// SPIR-V extensions enabled by a device extension, with the SPIR-V version
+/// they became core in.
+const EXTENSIONS: &[ExtensionRow] = &[
+    (
+        |e| e.khr_storage_buffer_storage_class,
+        "SPV_KHR_storage_buffer_storage_class",
+        Some((1, 3)),
+    ),
+    (
+        |e| e.khr_16bit_storage,
+        "SPV_KHR_16bit_storage",
+        Some((1, 3)),
+    ),
+    (|e| e.khr_8bit_storage, "SPV_KHR_8bit_storage", Some((1, 5))),
+    (|e| e.khr_multiview, "SPV_KHR_multiview", Some((1, 3))),
+    (
+        |e| e.khr_variable_pointers,
+        "SPV_KHR_variable_pointers",
+        Some((1, 3)),
+    ),
+    (
+        |e| e.khr_shader_draw_parameters,
+        "SPV_KHR_shader_draw_parameters",
+        Some((1, 3)),
+    ),
+    (
+        |e| e.khr_shader_float_controls,
+        "SPV_KHR_float_controls",
+        Some((1, 4)),
+    ),
+    (
+        |e| e.ext_descriptor_indexing,
+        "SPV_EXT_descriptor_indexing",
+        Some((1, 5)),
+    ),
+    (
+        |e| e.khr_vulkan_memory_model,
+        "SPV_KHR_vulkan_memory_model",
+        Some((1, 5)),
+    ),
+    (
+        |e| e.khr_buffer_device_address,
+        "SPV_KHR_physical_storage_buffer",
+        Some((1, 5)),
+    ),
+    (
+        |e| e.ext_shader_demote_to_helper_invocation,
+        "SPV_EXT_demote_to_helper_invocation",
+        Some((1, 6)),
+    ),
+    (
+        |e| e.khr_shader_integer_dot_product,
+        "SPV_KHR_integer_dot_product",
+        Some((1, 6)),
+    ),
+    (
+        |e| e.khr_shader_non_semantic_info,
+        "SPV_KHR_non_semantic_info",
+        Some((1, 6)),
+    ),
+    (
+        |e| e.ext_shader_stencil_export,
+        "SPV_EXT_shader_stencil_export",
+        None,
+    ),
+    (
+        |e| e.ext_shader_viewport_index_layer,
+        "SPV_EXT_shader_viewport_index_layer",
+        None,
+    ),
+    (
+        |e| e.ext_shader_image_atomic_int64,
+        "SPV_EXT_shader_image_int64",
+        None,
+    ),
+    (
+        |e| e.ext_shader_atomic_float,
+        "SPV_EXT_shader_atomic_float_add",
+        None,
+    ),
+    (|e| e.khr_shader_clock, "SPV_KHR_shader_clock", None),
+    (|e| e.khr_ray_tracing_pipeline, "SPV_KHR_ray_tracing", None),
+    (|e| e.khr_ray_query, "SPV_KHR_ray_query", None),
+    (
+        |e| e.ext_fragment_shader_interlock,
+        "SPV_EXT_fragment_shader_interlock",
+        None,
+    ),
+    (
+        |e| e.khr_fragment_shading_rate,
+        "SPV_KHR_fragment_shading_rate",
+        None,
+    ),
+    (|e| e.ext_mesh_shader, "SPV_EXT_mesh_shader", None),
+    (
+        |e| e.khr_workgroup_memory_explicit_layout,
+        "SPV_KHR_workgroup_memory_explicit_layout",
+        None,
+    ),
+    (
+        |e| e.khr_shader_subgroup_rotate,
+        "SPV_KHR_subgroup_rotate",
+        None,
+    ),
+    (
+        |e| e.khr_cooperative_matrix,
+        "SPV_KHR_cooperative_matrix",
+        None,
+    ),
+    (
+        |e| e.khr_shader_expect_assume,
+        "SPV_KHR_expect_assume",
+        None,
+    ),
+];
+
+/// What SPIR-V modules a Vulkan device can load.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use rust_gpu_hotreload::DeviceProfile;
+/// use vulkano::Version;
+/// use vulkano::device::{DeviceExtensions, DeviceFeatures};
+///
+/// let profile = DeviceProfile::new(
+///     Version::V1_2,
+///     &DeviceFeatures {
+///         shader_int8: true,
+///         ..DeviceFeatures::empty()
+///     },
+///     &DeviceExtensions::empty(),
+/// );
+/// assert_eq!(profile.target, "spirv-unknown-vulkan1.2");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceProfile {
+    /// rust-gpu target matching the device's API version.
+    pub target: String,
+    /// Highest SPIR-V version the device accepts.
+    pub spirv_version: (u8, u8),
+    /// Capabilities the device's API version, enabled features and enabled
+    /// extensions allow, in table order.
+    pub capabilities: Vec<Capability>,
+    /// SPIR-V extensions the device's enabled extensions allow, in table order.
+    pub extensions: Vec<String>,
+}
+
+impl DeviceProfile {
+    /// Derives a profile from a device's API version, enabled features and enabled extensions.
+    ///
+    /// Capabilities whose SPIR-V extension is missing at the derived target's
+    /// version are left out, so the profile always passes builder validation.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_version` - Vulkan API version of the device
+    /// * `features` - Features enabled on the device
+    /// * `extensions` - Extensions enabled on the device
+    pub fn new(
+        api_version: Version,
+        features: &DeviceFeatures,
+        extensions: &DeviceExtensions,
+    ) -> Self {
+        let target = if api_version >= Version::V1_3 {
+            "spirv-unknown-vulkan1.3"
+        } else if api_version >= Version::V1_2 {
+            "spirv-unknown-vulkan1.2"
+        } else if api_version >= Version::V1_1 && extensions.khr_spirv_1_4 {
+            "spirv-unknown-vulkan1.1spv1.4"
+        } else if api_version >= Version::V1_1 {
+            "spirv-unknown-vulkan1.1"
+        } else {
+            "spirv-unknown-vulkan1.0"
+        };
+        let target_version = target_spirv_version(target).expect("profile target is known");
+
+        let spirv_extensions: Vec<String> = EXTENSIONS
+            .iter()
+            .filter(|(enabled, _, _)| enabled(extensions))
+            .map(|(_, name, _)| name.to_string())
+            .collect();
+
+        let mut capabilities = BASE_CAPABILITIES.to_vec();
+        let enabled = CORE_CAPABILITIES
+            .iter()
+            .filter(|(core_since, _)| api_version >= *core_since)
+            .map(|(_, enables)| *enables)
+            .chain(
+                FEATURE_CAPABILITIES
+                    .iter()
+                    .filter(|(enabled, _)| enabled(features))
+                    .map(|(_, enables)| *enables),
+            )
+            .chain(
+                EXTENSION_CAPABILITIES
+                    .iter()
+                    .filter(|(enabled, _)| enabled(extensions))
+                    .map(|(_, enables)| *enables),
+            );
+        for enables in enabled {
+            for &capability in enables {
+                let available = requirements::requirement(capability).is_none_or(|requirement| {
+                    requirement.is_satisfied(target_version, spirv_extensions.as_slice())
+                });
+                if available && !capabilities.contains(&capability) {
+                    capabilities.push(capability);
+                }
+            }
+        }
+
+        Self {
+            target: target.to_string(),
+            spirv_version: api_spirv_version(api_version, extensions.khr_spirv_1_4),
+            capabilities,
+            extensions: spirv_extensions,
+        }
+    }
+
+    /// Checks that a module only uses what the device allows.
+    ///
+    /// Capabilities and extensions this profile has no table entry for are
+    /// left for Vulkan to judge.
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - Parsed SPIR-V module
+    ///
+    /// # Errors
+    ///
+    /// Returns everything the module declares beyond the profile.
+    pub fn check(&self, module: &Module) -> Result<(), ProfileMismatch> {
+        let version = module.header.version();
+        let mismatch = ProfileMismatch {
+            spirv_version: (version > self.spirv_version).then_some(version),
+            device_spirv_version: self.spirv_version,
+            capabilities: module
+                .capabilities()
+                .into_iter()
+                .filter(|capability| {
+                    is_gated(*capability) && !self.capabilities.contains(capability)
+                })
+                .collect(),
+            extensions: module
+                .extensions()
+                .into_iter()
+                .filter(|extension| !self.allows_extension(extension))
+                .collect(),
+        };
+        if mismatch.spirv_version.is_none()
+            && mismatch.capabilities.is_empty()
+            && mismatch.extensions.is_empty()
+        {
+            Ok(())
+        } else {
+            Err(mismatch)
+        }
+    }
+
+    fn allows_extension(&self, extension: &str) -> bool {
+        match EXTENSIONS.iter().find(|(_, name, _)| *name == extension) {
+            Some((_, _, core_since)) => {
+                self.extensions.iter().any(|enabled| enabled == extension)
+                    || core_since.is_some_and(|core| self.spirv_version >= core)
+            }
+            None => true,
+        }
+    }
+
+    /// Derives a profile from what is enabled on a device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - Vulkan device shaders will be loaded on
+    pub fn from_device(device: &Arc<Device>) -> Self {
+        Self::new(
+            device.api_version(),
+            device.enabled_features(),
+            device.enabled_extensions(),
+        )
+    }
+}
+
+/// Returns true if a device feature or extension has to be enabled for the capability.
+fn is_gated(capability: Capability) -> bool {
+    FEATURE_CAPABILITIES
+        .iter()
+        .map(|(_, enables)| enables)
+        .chain(EXTENSION_CAPABILITIES.iter().map(|(_, enables)| enables))
+        .any(|enables| enables.contains(&capability))
+}
+
+/// What a module declares beyond a [`DeviceProfile`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProfileMismatch {
+    /// The module's SPIR-V version, if newer than the device accepts.
+    pub spirv_version: Option<(u8, u8)>,
+    /// Highest SPIR-V version the device accepts.
+    pub device_spirv_version: (u8, u8),
+    /// Capabilities whose device feature is not enabled.
+    pub capabilities: Vec<Capability>,
+    /// Extensions whose device extension is not enabled.
+    pub extensions: Vec<String>,
+}
+
+impl fmt::Display for ProfileMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut problems = Vec::new();
+        if let Some((major, minor)) = self.spirv_version {
+            let (max_major, max_minor) = self.device_spirv_version;
+            problems.push(format!(
+                "SPIR-V {major}.{minor} is newer than the {max_major}.{max_minor} the device accepts"
+            ));
+        }
+        if !self.capabilities.is_empty() {
+            let capabilities: Vec<String> = self
+                .capabilities
+                .iter()
+                .map(|capability| format!("{:?}", capability))
+                .collect();
+            problems.push(format!(
+                "capabilities not enabled on the device: {}",
+                capabilities.join(", ")
+            ));
+        }
+        if !self.extensions.is_empty() {
+            problems.push(format!(
+                "extensions not enabled on the device: {}",
+                self.extensions.join(", ")
+            ));
+        }
+        write!(f, "{}", problems.join("; "))
+    }
+}
+
+impl std::error::Error for ProfileMismatch {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spv::{Instruction, encode_string, test_module};
+    use spirv::Op;
+
+    fn derive(
+        api_version: Version,
+        features: DeviceFeatures,
+        extensions: DeviceExtensions,
+    ) -> DeviceProfile {
+        DeviceProfile::new(api_version, &features, &extensions)
+    }
+
+    fn module(version: (u8, u8), capabilities: &[Capability], extensions: &[&str]) -> Module {
+        let mut instructions: Vec<Instruction> = capabilities
+            .iter()
+            .map(|&capability| Instruction::new(Op::Capability, vec![capability as u32]))
+            .collect();
+        instructions.extend(
+            extensions
+                .iter()
+                .map(|extension| Instruction::new(Op::Extension, encode_string(extension))),
+        );
+        test_module(version, 1, instructions)
+    }
+
+    #[test]
+    fn picks_target_per_api_version() {
+        let cases = [
+            (Version::V1_0, false, "spirv-unknown-vulkan1.0", (1, 0)),
+            (Version::V1_1, false, "spirv-unknown-vulkan1.1", (1, 3)),
+            (Version::V1_1, true, "spirv-unknown-vulkan1.1spv1.4", (1, 4)),
+            (Version::V1_2, false, "spirv-unknown-vulkan1.2", (1, 5)),
+            (Version::V1_2, true, "spirv-unknown-vulkan1.2", (1, 5)),
+            (Version::V1_3, false, "spirv-unknown-vulkan1.3", (1, 6)),
+        ];
+        for (api_version, khr_spirv_1_4, target, spirv_version) in cases {
+            let extensions = DeviceExtensions {
+                khr_spirv_1_4,
+                ..DeviceExtensions::empty()
+            };
+            let profile = derive(api_version, DeviceFeatures::empty(), extensions);
+            assert_eq!(profile.target, target, "{api_version:?}");
+            assert_eq!(profile.spirv_version, spirv_version, "{api_version:?}");
+        }
+    }
+
+    #[test]
+    fn features_enable_capabilities() {
+        let features = DeviceFeatures {
+            shader_int8: true,
+            shader_float64: true,
+            shader_integer_dot_product: true,
+            ..DeviceFeatures::empty()
+        };
+        let profile = derive(Version::V1_3, features, DeviceExtensions::empty());
+        for capability in [
+            Capability::Shader,
+            Capability::GroupNonUniform,
+            Capability::Int8,
+            Capability::Float64,
+            Capability::DotProduct,
+            Capability::DotProductInput4x8BitPacked,
+        ] {
+            assert!(profile.capabilities.contains(&capability), "{capability:?}");
+        }
+        assert!(!profile.capabilities.contains(&Capability::Int16));
+
+        let profile = derive(
+            Version::V1_0,
+            DeviceFeatures::empty(),
+            DeviceExtensions::empty(),
+        );
+        assert!(!profile.capabilities.contains(&Capability::GroupNonUniform));
+        assert!(!profile.capabilities.contains(&Capability::Int8));
+    }
+
+    /// Devices enabling only one of the features that enable a capability.
+    fn alternative_features() -> Vec<(DeviceFeatures, Capability)> {
+        vec![
+            (
+                DeviceFeatures {
+                    shader_shared_int64_atomics: true,
+                    ..DeviceFeatures::empty()
+                },
+                Capability::Int64Atomics,
+            ),
+            (
+                DeviceFeatures {
+                    shader_image_int64_atomics: true,
+                    ..DeviceFeatures::empty()
+                },
+                Capability::Int64Atomics,
+            ),
+            (
+                DeviceFeatures {
+                    shader_shared_float32_atomic_add: true,
+                    ..DeviceFeatures::empty()
+                },
+                Capability::AtomicFloat32AddEXT,
+            ),
+            (
+                DeviceFeatures {
+                    shader_image_float32_atomic_add: true,
+                    ..DeviceFeatures::empty()
+                },
+                Capability::AtomicFloat32AddEXT,
+            ),
+            (
+                DeviceFeatures {
+                    sparse_image_float32_atomic_add: true,
+                    ..DeviceFeatures::empty()
+                },
+                Capability::AtomicFloat32AddEXT,
+            ),
+            (
+                DeviceFeatures {
+                    shader_shared_float64_atomic_add: true,
+                    ..DeviceFeatures::empty()
+                },
+                Capability::AtomicFloat64AddEXT,
+            ),
+            (
+                DeviceFeatures {
+                    primitive_fragment_shading_rate: true,
+                    ..DeviceFeatures::empty()
+                },
+                Capability::FragmentShadingRateKHR,
+            ),
+            (
+                DeviceFeatures {
+                    attachment_fragment_shading_rate: true,
+                    ..DeviceFeatures::empty()
+                },
+                Capability::FragmentShadingRateKHR,
+            ),
+        ]
+    }
+
+    /// Device extensions the capabilities of [`alternative_features`] need.
+    fn alternative_extensions() -> DeviceExtensions {
+        DeviceExtensions {
+            ext_shader_atomic_float: true,
+            khr_fragment_shading_rate: true,
+            ..DeviceExtensions::empty()
+        }
+    }
+
+    #[test]
+    fn any_alternative_feature_enables_a_capability() {
+        for (features, capability) in alternative_features() {
+            let profile = derive(Version::V1_2, features, alternative_extensions());
+            assert!(profile.capabilities.contains(&capability), "{capability:?}");
+
+            let bare = derive(
+                Version::V1_2,
+                DeviceFeatures::empty(),
+                alternative_extensions(),
+            );
+            assert!(!bare.capabilities.contains(&capability), "{capability:?}");
+        }
+    }
+
+    #[test]
+    fn accepts_modules_using_an_alternative_feature() {
+        for (features, capability) in alternative_features() {
+            let extensions: &[&str] = requirements::requirement(capability)
+                .map_or(&[], |requirement| requirement.extensions);
+            let module = module((1, 5), &[Capability::Shader, capability], extensions);
+
+            let profile = derive(Version::V1_2, features, alternative_extensions());
+            assert_eq!(profile.check(&module), Ok(()), "{capability:?}");
+
+            let bare = derive(
+                Version::V1_2,
+                DeviceFeatures::empty(),
+                alternative_extensions(),
+            );
+            let mismatch = bare.check(&module).unwrap_err();
+            assert_eq!(mismatch.capabilities, [capability]);
+        }
+    }
+
+    #[test]
+    fn leaves_out_capabilities_missing_their_spirv_extension() {
+        // 8-bit storage needs SPV_KHR_8bit_storage before SPIR-V 1.5
+        let features = DeviceFeatures {
+            storage_buffer8_bit_access: true,
+            ..DeviceFeatures::empty()
+        };
+        let without = derive(Version::V1_1, features, DeviceExtensions::empty());
+        assert!(
+            !without
+                .capabilities
+                .contains(&Capability::StorageBuffer8BitAccess)
+        );
+
+        let extensions = DeviceExtensions {
+            khr_8bit_storage: true,
+            ..DeviceExtensions::empty()
+        };
+        let with = derive(Version::V1_1, features, extensions);
+        assert!(
+            with.capabilities
+                .contains(&Capability::StorageBuffer8BitAccess)
+        );
+        assert_eq!(with.extensions, ["SPV_KHR_8bit_storage"]);
+
+        let core = derive(Version::V1_2, features, DeviceExtensions::empty());
+        assert!(
+            core.capabilities
+                .contains(&Capability::StorageBuffer8BitAccess)
+        );
+    }
+
+    #[test]
+    fn extensions_enable_capabilities_without_features() {
+        let extensions = DeviceExtensions {
+            khr_shader_draw_parameters: true,
+            ..DeviceExtensions::empty()
+        };
+        let profile = derive(Version::V1_0, DeviceFeatures::empty(), extensions);
+        assert!(profile.capabilities.contains(&Capability::DrawParameters));
+        assert_eq!(
+            profile.check(&module(
+                (1, 0),
+                &[Capability::Shader, Capability::DrawParameters],
+                &["SPV_KHR_shader_draw_parameters"],
+            )),
+            Ok(())
+        );
+
+        let bare = DeviceProfile::new(
+            Version::V1_0,
+            &DeviceFeatures::empty(),
+            &DeviceExtensions::empty(),
+        );
+        let mismatch = bare
+            .check(&module(
+                (1, 0),
+                &[Capability::DrawParameters],
+                &["SPV_KHR_shader_draw_parameters"],
+            ))
+            .unwrap_err();
+        assert_eq!(mismatch.capabilities, [Capability::DrawParameters]);
+        assert_eq!(mismatch.extensions, ["SPV_KHR_shader_draw_parameters"]);
+    }
+
+    #[test]
+    fn promoted_capabilities_need_no_feature() {
+        let write_without_format =
+            module((1, 6), &[Capability::StorageImageWriteWithoutFormat], &[]);
+        let v1_3 = derive(
+            Version::V1_3,
+            DeviceFeatures::empty(),
+            DeviceExtensions::empty(),
+        );
+        assert_eq!(v1_3.check(&write_without_format), Ok(()));
+
+        let write_without_format =
+            module((1, 5), &[Capability::StorageImageWriteWithoutFormat], &[]);
+        let v1_2 = derive(
+            Version::V1_2,
+            DeviceFeatures::empty(),
+            DeviceExtensions::empty(),
+        );
+        assert_eq!(
+            v1_2.check(&write_without_format).unwrap_err().capabilities,
+            [Capability::StorageImageWriteWithoutFormat]
+        );
+
+        let extensions = DeviceExtensions {
+            khr_format_feature_flags2: true,
+            ..DeviceExtensions::empty()
+        };
+        let flags2 = derive(Version::V1_2, DeviceFeatures::empty(), extensions);
+        assert_eq!(flags2.check(&write_without_format), Ok(()));
+
+        // Promoted SPIR-V extensions need no device extension once core
+        let storage_class = module((1, 3), &[], &["SPV_KHR_storage_buffer_storage_class"]);
+        let v1_1 = derive(
+            Version::V1_1,
+            DeviceFeatures::empty(),
+            DeviceExtensions::empty(),
+        );
+        assert_eq!(v1_1.check(&storage_class), Ok(()));
+        let v1_0 = derive(
+            Version::V1_0,
+            DeviceFeatures::empty(),
+            DeviceExtensions::empty(),
+        );
+        assert!(v1_0.check(&storage_class).is_err());
+    }
+
+    #[test]
+    fn reports_every_mismatch() {
+        let profile = derive(
+            Version::V1_1,
+            DeviceFeatures::empty(),
+            DeviceExtensions::empty(),
+        );
+        let mismatch = profile
+            .check(&module(
+                (1, 5),
+                &[
+                    Capability::Shader,
+                    Capability::Int16,
+                    Capability::ImageGatherExtended,
+                ],
+                &["SPV_KHR_ray_query", "SPV_VENDOR_unknown"],
+            ))
+            .unwrap_err();
+        assert_eq!(mismatch.spirv_version, Some((1, 5)));
+        assert_eq!(mismatch.device_spirv_version, (1, 3));
+        assert_eq!(
+            mismatch.capabilities,
+            [Capability::Int16, Capability::ImageGatherExtended]
+        );
+        assert_eq!(mismatch.extensions, ["SPV_KHR_ray_query"]);
+        assert_eq!(
+            mismatch.to_string(),
+            "SPIR-V 1.5 is newer than the 1.3 the device accepts; \
+             capabilities not enabled on the device: Int16, ImageGatherExtended; \
+             extensions not enabled on the device: SPV_KHR_ray_query"
+        );
+    }
+}
@@ -0,0 +1,278 @@
+//! Recognition of compile errors caused by undeclared capabilities or extensions.
+//!
+//! The build runner's stderr carries cargo's diagnostics; they are scanned for
+//! the messages rust-gpu and spirv-val give for a missing declaration.
+
+use crate::compile::CompileConfig;
+use spirv::Capability;
+use std::fmt;
+use std::str::FromStr;
+
+/// A declaration the builder was missing, recognised from a compile error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Suggestion {
+    Capability(Capability),
+    Extension(String),
+}
+
+impl fmt::Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Capability(capability) => write!(f, ".capability(Capability::{:?})", capability),
+            Self::Extension(extension) => write!(f, ".extension(\"{}\")", extension),
+        }
+    }
+}
+
+impl Suggestion {
+    /// Adds the declaration to `config` if it is not there yet.
+    pub(crate) fn apply(&self, config: &mut CompileConfig) {
+        match self {
+            Self::Capability(capability) => {
+                if !config.capabilities.contains(capability) {
+                    config.capabilities.push(*capability);
+                }
+            }
+            Self::Extension(extension) => {
+                if !config.extensions.contains(extension) {
+                    config.extensions.push(extension.clone());
+                }
+            }
+        }
+    }
+}
+
+/// Compile error whose diagnostics name capabilities or extensions the builder
+/// does not declare.
+#[derive(Debug)]
+pub struct MissingRequirements {
+    /// Declarations to add to the builder.
+    pub suggestions: Vec<Suggestion>,
+    /// The original compile error.
+    pub error: String,
+}
+
+impl fmt::Display for MissingRequirements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suggestions: Vec<String> = self.suggestions.iter().map(|s| s.to_string()).collect();
+        write!(
+            f,
+            "{}\nThe shader crate needs declarations the builder is missing; add {}",
+            self.error,
+            suggestions.join(" and ")
+        )
+    }
+}
+
+impl std::error::Error for MissingRequirements {}
+
+/// Finds capabilities and extensions that diagnostics report as missing.
+///
+/// Only the forms in [`CAPABILITY_FORMS`] and [`EXTENSION_FORMS`] are
+/// recognised, and only when they name a single declaration. Names in
+/// `declared_capabilities` and `declared_extensions` are skipped.
+pub(crate) fn recognise(
+    output: &str,
+    declared_capabilities: &[Capability],
+    declared_extensions: &[String],
+) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+    for line in output.lines() {
+        for form in CAPABILITY_FORMS {
+            for name in form.names(line) {
+                if let Ok(capability) = Capability::from_str(name)
+                    && !declared_capabilities.contains(&capability)
+                {
+                    suggestions.push(Suggestion::Capability(capability));
+                }
+            }
+        }
+        for form in EXTENSION_FORMS {
+            for name in form.names(line) {
+                if name.starts_with("SPV_")
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                    && !declared_extensions.iter().any(|ext| ext == name)
+                {
+                    suggestions.push(Suggestion::Extension(name.to_string()));
+                }
+            }
+        }
+    }
+    let mut unique = Vec::new();
+    for suggestion in suggestions {
+        if !unique.contains(&suggestion) {
+            unique.push(suggestion);
+        }
+    }
+    unique
+}
+
+/// A diagnostic naming one missing declaration between `before` and `after`.
+struct Form {
+    before: &'static str,
+    /// Text following the name, or `None` when the name ends the line.
+    after: Option<&'static str>,
+}
+
+/// Capability diagnostics of rust-gpu and spirv-val.
+const CAPABILITY_FORMS: &[Form] = &[
+    // rust-gpu: "`u8` without `OpCapability Int8`"
+    Form {
+        before: "without `OpCapability ",
+        after: Some("`"),
+    },
+    // Older rust-gpu: "u8 without OpCapability Int8"
+    Form {
+        before: "without OpCapability ",
+        after: None,
+    },
+    // spirv-val: "Capability StorageImageWriteWithoutFormat is required to write to storage image"
+    Form {
+        before: "Capability ",
+        after: Some(" is required"),
+    },
+    // spirv-val: "Using an 8-bit integer type requires the Int8 capability, ..."
+    Form {
+        before: "requires the ",
+        after: Some(" capability"),
+    },
+    // spirv-val: "Operand 2 of TypeInt requires one of these capabilities: Int8"
+    Form {
+        before: "requires one of these capabilities: ",
+        after: None,
+    },
+];
+
+/// Extension diagnostics of spirv-val.
+const EXTENSION_FORMS: &[Form] = &[
+    // "Operand 2 of Capability requires one of these extensions: SPV_KHR_ray_query"
+    Form {
+        before: "requires one of these extensions: ",
+        after: None,
+    },
+];
+
+impl Form {
+    /// Names this form gives in `line`. A list of several alternatives names
+    /// none, since any one of them would do.
+    fn names<'a>(&self, line: &'a str) -> impl Iterator<Item = &'a str> {
+        line.match_indices(self.before).filter_map(|(start, _)| {
+            let rest = &line[start + self.before.len()..];
+            let name = match self.after {
+                Some(after) => &rest[..rest.find(after)?],
+                None => rest.trim_end(),
+            };
+            (!name.is_empty() && !name.contains(char::is_whitespace)).then_some(name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// rust-gpu rejecting a `u8` without the `Int8` capability.
+    const INT8: &str = "\
+error: `u8` without `OpCapability Int8`
+  --> src/lib.rs:14:13
+   |
+14 |     let lut: [u8; 4] = [0, 1, 2, 3];
+   |             ^^^^^^^
+   |
+note: used from within `shader::main_fs`
+  --> src/lib.rs:10:8
+   |
+10 | pub fn main_fs(output: &mut Vec4) {
+   |        ^^^^^^^
+
+error: could not compile `shader` (lib) due to 1 previous error
+";
+
+    /// spirv-val rejecting a write to an image of unknown format.
+    const WRITE_WITHOUT_FORMAT: &str = "\
+error: error:0:0 - Capability StorageImageWriteWithoutFormat is required to write to storage image
+  |
+  = note: spirv-val failed
+  = note: module `target/spirv-builder/spirv-unknown-vulkan1.2/release/deps/shader.spv`
+";
+
+    /// spirv-val rejecting a ray query type.
+    const RAY_QUERY: &str = "\
+error: error:0:0 - Operand 2 of Capability requires one of these extensions: SPV_KHR_ray_query
+  |
+  = note: spirv-val failed
+";
+
+    #[test]
+    fn recognises_rust_gpu_capability() {
+        assert_eq!(
+            recognise(INT8, &[], &[]),
+            vec![Suggestion::Capability(Capability::Int8)]
+        );
+        assert_eq!(
+            recognise("error: u16 without OpCapability Int16", &[], &[]),
+            vec![Suggestion::Capability(Capability::Int16)]
+        );
+    }
+
+    #[test]
+    fn recognises_spirv_val_capability() {
+        assert_eq!(
+            recognise(WRITE_WITHOUT_FORMAT, &[], &[]),
+            vec![Suggestion::Capability(
+                Capability::StorageImageWriteWithoutFormat
+            )]
+        );
+        let int8 = "error: error:0:0 - Using an 8-bit integer type requires the Int8 capability, \
+                    or an extension that explicitly enables 8-bit integers.";
+        assert_eq!(
+            recognise(int8, &[], &[]),
+            vec![Suggestion::Capability(Capability::Int8)]
+        );
+        let operand =
+            "error: error:0:0 - Operand 2 of TypeFloat requires one of these capabilities: Float64";
+        assert_eq!(
+            recognise(operand, &[], &[]),
+            vec![Suggestion::Capability(Capability::Float64)]
+        );
+    }
+
+    #[test]
+    fn recognises_extension() {
+        assert_eq!(
+            recognise(RAY_QUERY, &[], &[]),
+            vec![Suggestion::Extension("SPV_KHR_ray_query".to_string())]
+        );
+    }
+
+    #[test]
+    fn skips_declared() {
+        assert!(recognise(INT8, &[Capability::Int8], &[]).is_empty());
+        assert!(recognise(RAY_QUERY, &[], &["SPV_KHR_ray_query".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn ignores_alternatives() {
+        let output = "error: error:0:0 - Opcode ImageQuerySize requires one of these capabilities: Kernel ImageQuery";
+        assert!(recognise(output, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn ignores_capability_names_in_other_diagnostics() {
+        let output = "\
+error[E0412]: cannot find type `Shader` in this scope
+warning: unused variable: `Matrix`
+note: the capabilities Shader, Kernel and Linkage are declared by the module
+error: `f32` is not a valid type for capability Float64 here
+warning: missing capability documentation for `Int8`
+error: Capability Int64 is not allowed by Vulkan 1.2 specification (or requires extension)
+";
+        assert!(recognise(output, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn deduplicates() {
+        let output = format!("{}{}", INT8, INT8);
+        assert_eq!(recognise(&output, &[], &[]).len(), 1);
+    }
+}
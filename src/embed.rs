@@ -0,0 +1,260 @@
+//! Shaders compiled by a build script and included in the executable.
+//!
+//! [`ShaderHotReloaderBuilder::embed`](crate::ShaderHotReloaderBuilder::embed)
+//! compiles every configured crate, target and permutation into `OUT_DIR` and
+//! writes a file listing the modules, which [`embed_shaders!`](crate::embed_shaders)
+//! includes. Sharing the builder configuration between `build.rs` and the
+//! application keeps the embedded shaders identical to hot-reloaded ones.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // shader_config.rs, included by both build.rs and main.rs
+//! fn shader_config() -> rust_gpu_hotreload::ShaderHotReloaderBuilder {
+//!     rust_gpu_hotreload::ShaderHotReloaderBuilder::new("../shader-source")
+//!         .target("spirv-unknown-vulkan1.3")
+//!         .split_entry_points(true)
+//! }
+//!
+//! // build.rs
+//! include!("src/shader_config.rs");
+//! fn main() {
+//!     shader_config().embed().expect("Failed to compile shaders");
+//! }
+//!
+//! // main.rs
+//! use rust_gpu_hotreload::{EmbeddedShaders, embed_shaders};
+//!
+//! static SHADERS: EmbeddedShaders = embed_shaders!();
+//!
+//! #[cfg(not(feature = "hot-reload"))]
+//! let shader_output_dir = SHADERS.output_dir("shader-source")?;
+//! #[cfg(feature = "hot-reload")]
+//! let shader_output_dir = reloader.output_dir();
+//! ```
+
+use crate::ShaderOutputDir;
+use std::path::PathBuf;
+#[cfg(feature = "compiler")]
+use {
+    crate::compile::{CompileConfig, compile_shaders},
+    crate::compile_cache,
+    crate::handles,
+    crate::manifest::{MANIFEST_FILE, Manifest},
+    crate::publish::{self, GenerationInfo, Publisher},
+    crate::status::CompileState,
+    parking_lot::Mutex,
+    std::fmt::Write,
+    std::path::Path,
+};
+
+/// File in `OUT_DIR` that [`embed_shaders!`](crate::embed_shaders) includes.
+#[cfg(feature = "compiler")]
+const GENERATED_FILE: &str = "rust_gpu_hotreload_shaders.rs";
+/// Directory in `OUT_DIR` the embedded modules are compiled into.
+#[cfg(feature = "compiler")]
+const EMBED_DIR: &str = "rust-gpu-hotreload";
+
+/// Publish root, cache key, generation and directory of every variant this
+/// build script compiled, so [`compile_into`] compiles a variant once even if
+/// both [`write_embedded`] and handle generation ask for it.
+#[cfg(feature = "compiler")]
+static COMPILED: Mutex<Vec<(PathBuf, String, GenerationInfo, PathBuf)>> = Mutex::new(Vec::new());
+
+/// Files of one embedded variant, by file name.
+pub type EmbeddedFiles = &'static [(&'static str, &'static [u8])];
+
+/// Crate name, target, permutation and files of one embedded variant, as
+/// written by the build script.
+#[doc(hidden)]
+pub type EmbeddedVariant = (
+    &'static str,
+    &'static str,
+    Option<&'static str>,
+    EmbeddedFiles,
+);
+
+/// Includes the shaders compiled by
+/// [`ShaderHotReloaderBuilder::embed`](crate::ShaderHotReloaderBuilder::embed)
+/// as an [`EmbeddedShaders`] value.
+///
+/// Can initialise a `static`.
+#[macro_export]
+macro_rules! embed_shaders {
+    () => {
+        $crate::EmbeddedShaders::from_generated(include!(concat!(
+            env!("OUT_DIR"),
+            "/rust_gpu_hotreload_shaders.rs"
+        )))
+    };
+}
+
+/// Shader modules included in the executable by [`embed_shaders!`](crate::embed_shaders).
+pub struct EmbeddedShaders {
+    variants: &'static [EmbeddedVariant],
+}
+
+impl EmbeddedShaders {
+    #[doc(hidden)]
+    pub const fn from_generated(variants: &'static [EmbeddedVariant]) -> Self {
+        Self { variants }
+    }
+
+    /// Returns the names of the embedded shader crates.
+    pub fn crate_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = Vec::new();
+        for &(crate_name, ..) in self.variants {
+            if !names.contains(&crate_name) {
+                names.push(crate_name);
+            }
+        }
+        names
+    }
+
+    /// Returns an output directory that loads a crate's embedded shaders.
+    ///
+    /// It behaves like one returned by the hot reloader: other targets and
+    /// permutations are reachable through
+    /// [`ShaderOutputDir::for_target`] and [`ShaderOutputDir::permutation`].
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no crate with that name was embedded.
+    pub fn output_dir(
+        &self,
+        crate_name: &str,
+    ) -> Result<ShaderOutputDir, Box<dyn std::error::Error>> {
+        let dirs: Vec<ShaderOutputDir> = self
+            .variants
+            .iter()
+            .filter(|(name, ..)| *name == crate_name)
+            .map(|&(name, target, permutation, files)| {
+                let mut path = PathBuf::from("embedded").join(name).join(target);
+                if let Some(permutation) = permutation {
+                    path = path.join(permutation);
+                }
+                ShaderOutputDir::embedded(path, target, permutation, files)
+            })
+            .collect();
+        if dirs.is_empty() {
+            return Err(format!("no embedded shader crate named {}", crate_name).into());
+        }
+        Ok(ShaderOutputDir::with_variants(dirs))
+    }
+}
+
+/// Compiles every variant of `configs` into `OUT_DIR` and writes the file
+/// [`embed_shaders!`](crate::embed_shaders) includes, along with the typed
+/// handles of [`include_shader_handles!`](crate::include_shader_handles).
+///
+/// Returns the path of the written file.
+#[cfg(feature = "compiler")]
+pub(crate) fn write_embedded(
+    configs: &[CompileConfig],
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let out_dir = out_dir()?;
+    let state = Mutex::new(CompileState::Idle);
+
+    let mut generated = String::from("&[\n");
+    let mut manifests = Vec::new();
+    for config in configs {
+        println!(
+            "cargo:rerun-if-changed={}",
+            config.shader_crate_path.display()
+        );
+        for (i, variant) in config.variants().into_iter().enumerate() {
+            let (info, generation) = compile_into(&out_dir, &variant, &state)?;
+            // The main target with the default features comes first
+            if i == 0 {
+                manifests.push((
+                    variant.crate_name().to_string(),
+                    Manifest::read(&generation)?,
+                ));
+            }
+
+            writeln!(
+                generated,
+                "    ({:?}, {:?}, {:?}, &[",
+                variant.crate_name(),
+                variant.target,
+                variant.permutation
+            )?;
+            let files = info.files.iter().map(String::as_str);
+            for file in files.chain([MANIFEST_FILE]) {
+                writeln!(
+                    generated,
+                    "        ({:?}, include_bytes!({:?}) as &[u8]),",
+                    file,
+                    absolute(&generation.join(file))?
+                )?;
+            }
+            generated.push_str("    ]),\n");
+        }
+    }
+    generated.push_str("]\n");
+
+    let path = out_dir.join(GENERATED_FILE);
+    std::fs::write(&path, generated)?;
+    handles::write(&out_dir, &manifests)?;
+    Ok(path)
+}
+
+/// Directory a build script writes generated files to.
+#[cfg(feature = "compiler")]
+pub(crate) fn out_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    Ok(PathBuf::from(std::env::var_os("OUT_DIR").ok_or(
+        "OUT_DIR is not set; call this from a build script",
+    )?))
+}
+
+/// Compiles one variant into its own publish root under `out_dir`.
+///
+/// Returns the published generation and its directory. A variant this build
+/// script already compiled, with unchanged sources and settings, is returned
+/// without compiling again.
+#[cfg(feature = "compiler")]
+pub(crate) fn compile_into(
+    out_dir: &Path,
+    variant: &CompileConfig,
+    state: &Mutex<CompileState>,
+) -> Result<(GenerationInfo, PathBuf), Box<dyn std::error::Error>> {
+    let mut root = out_dir
+        .join(EMBED_DIR)
+        .join(variant.crate_name())
+        .join(&variant.target);
+    if let Some(permutation) = &variant.permutation {
+        root = root.join(permutation);
+    }
+    let key = compile_cache::cache_key(variant)?;
+    if let Some((.., info, generation)) = COMPILED
+        .lock()
+        .iter()
+        .find(|(compiled, compiled_key, ..)| *compiled == root && *compiled_key == key)
+        .filter(|(.., generation)| generation.is_dir())
+    {
+        return Ok((info.clone(), generation.clone()));
+    }
+
+    let publisher = Publisher::new(&root, 1);
+    let info = compile_shaders(variant, &publisher, publish::compile_id(), state)
+        .map_err(|e| format!("{} ({}): {}", variant.crate_name(), variant.target, e))?;
+    let generation =
+        publish::current_generation(&root).ok_or("compiled shaders were not published")?;
+    let mut compiled = COMPILED.lock();
+    compiled.retain(|(compiled, ..)| *compiled != root);
+    compiled.push((root, key, info.clone(), generation.clone()));
+    Ok((info, generation))
+}
+
+/// Path as a string `include_bytes!` resolves the same from any source file.
+#[cfg(feature = "compiler")]
+fn absolute(path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    Ok(std::path::absolute(path)?
+        .to_str()
+        .ok_or_else(|| format!("{}: path is not valid UTF-8", path.display()))?
+        .to_string())
+}
@@ -0,0 +1,458 @@
+//! Typed handles for compiled shader modules and their entry points.
+//!
+//! A build script calling
+//! [`ShaderHotReloaderBuilder::generate_handles`](crate::ShaderHotReloaderBuilder::generate_handles)
+//! (or [`embed`](crate::ShaderHotReloaderBuilder::embed)) writes a constant for
+//! every module and entry point the shader crates compile to, which
+//! [`include_shader_handles!`](crate::include_shader_handles) includes. Loading
+//! through them instead of string names turns a renamed or removed entry point
+//! into a build error.
+//!
+//! Constants are grouped in a module per shader crate, named after the crate
+//! with `-` replaced by `_`. Module constants are named after the file, e.g.
+//! `MAIN_VS_SPV` for `main_vs.spv`. Entry point constants are in a module named
+//! after the file stem, so a split module and the linked module declaring the
+//! same entry point do not clash, and are named after the entry point, e.g.
+//! `lighting::LIGHTING_MAIN_FS` for `lighting::main_fs` in `lighting.spv`.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! // build.rs
+//! include!("src/shader_config.rs");
+//! fn main() {
+//!     shader_config().generate_handles().expect("Failed to compile shaders");
+//! }
+//!
+//! // main.rs
+//! mod shaders {
+//!     rust_gpu_hotreload::include_shader_handles!();
+//! }
+//!
+//! let module = shader_output_dir.load(device.clone(), shaders::shader_source::MAIN_VS_SPV)?;
+//! let entry_point =
+//!     shader_output_dir.load_entry_point(device, shaders::shader_source::main_vs::MAIN_VS)?;
+//! ```
+
+pub use spirv::ExecutionModel;
+use vulkano::shader::spirv::ExecutionModel as VulkanoExecutionModel;
+#[cfg(feature = "compiler")]
+use {
+    crate::manifest::Manifest,
+    std::collections::HashSet,
+    std::fmt::Write,
+    std::path::{Path, PathBuf},
+};
+
+/// File in `OUT_DIR` that [`include_shader_handles!`](crate::include_shader_handles) includes.
+#[cfg(feature = "compiler")]
+const GENERATED_FILE: &str = "rust_gpu_hotreload_handles.rs";
+
+/// Includes the handles generated by
+/// [`ShaderHotReloaderBuilder::generate_handles`](crate::ShaderHotReloaderBuilder::generate_handles).
+///
+/// Expands to a module per shader crate, so invoke it inside a module of its own.
+#[macro_export]
+macro_rules! include_shader_handles {
+    () => {
+        #[doc(hidden)]
+        pub use $crate::handles as __shader_handles;
+        include!(concat!(env!("OUT_DIR"), "/rust_gpu_hotreload_handles.rs"));
+    };
+}
+
+/// A compiled shader module, identified by its file name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShaderHandle {
+    file: &'static str,
+}
+
+impl ShaderHandle {
+    #[doc(hidden)]
+    pub const fn new(file: &'static str) -> Self {
+        Self { file }
+    }
+
+    /// File name of the module within a generation directory.
+    pub const fn file(&self) -> &'static str {
+        self.file
+    }
+}
+
+/// An entry point of a compiled shader module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EntryPointHandle {
+    module: ShaderHandle,
+    name: &'static str,
+    execution_model: ExecutionModel,
+}
+
+impl EntryPointHandle {
+    #[doc(hidden)]
+    pub const fn new(
+        module: ShaderHandle,
+        name: &'static str,
+        execution_model: ExecutionModel,
+    ) -> Self {
+        Self {
+            module,
+            name,
+            execution_model,
+        }
+    }
+
+    /// Module the entry point is in.
+    pub const fn module(&self) -> ShaderHandle {
+        self.module
+    }
+
+    /// Name of the entry point, as declared in the module.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Execution model of the entry point.
+    pub const fn execution_model(&self) -> ExecutionModel {
+        self.execution_model
+    }
+
+    /// Execution model of the entry point as vulkano names it.
+    pub(crate) fn vulkano_execution_model(&self) -> VulkanoExecutionModel {
+        match self.execution_model {
+            ExecutionModel::Vertex => VulkanoExecutionModel::Vertex,
+            ExecutionModel::TessellationControl => VulkanoExecutionModel::TessellationControl,
+            ExecutionModel::TessellationEvaluation => VulkanoExecutionModel::TessellationEvaluation,
+            ExecutionModel::Geometry => VulkanoExecutionModel::Geometry,
+            ExecutionModel::Fragment => VulkanoExecutionModel::Fragment,
+            ExecutionModel::GLCompute => VulkanoExecutionModel::GLCompute,
+            ExecutionModel::Kernel => VulkanoExecutionModel::Kernel,
+            ExecutionModel::TaskNV => VulkanoExecutionModel::TaskNV,
+            ExecutionModel::MeshNV => VulkanoExecutionModel::MeshNV,
+            ExecutionModel::RayGenerationNV => VulkanoExecutionModel::RayGenerationKHR,
+            ExecutionModel::IntersectionNV => VulkanoExecutionModel::IntersectionKHR,
+            ExecutionModel::AnyHitNV => VulkanoExecutionModel::AnyHitKHR,
+            ExecutionModel::ClosestHitNV => VulkanoExecutionModel::ClosestHitKHR,
+            ExecutionModel::MissNV => VulkanoExecutionModel::MissKHR,
+            ExecutionModel::CallableNV => VulkanoExecutionModel::CallableKHR,
+            ExecutionModel::TaskEXT => VulkanoExecutionModel::TaskEXT,
+            ExecutionModel::MeshEXT => VulkanoExecutionModel::MeshEXT,
+        }
+    }
+}
+
+/// Writes the handles of each crate's modules to `out_dir`.
+///
+/// # Arguments
+///
+/// * `out_dir` - Build script output directory
+/// * `crates` - Shader crate names, with the manifest of a generation each compiled
+///
+/// # Errors
+///
+/// Returns an error if two modules or entry points of a crate would share a
+/// constant name, or the file cannot be written.
+#[cfg(feature = "compiler")]
+pub(crate) fn write(
+    out_dir: &Path,
+    crates: &[(String, Manifest)],
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let mut generated = String::new();
+    for (crate_name, manifest) in crates {
+        // Applications rarely use every handle
+        writeln!(generated, "/// Shaders of the `{}` crate.", crate_name)?;
+        writeln!(generated, "#[allow(dead_code, unused_imports)]")?;
+        writeln!(generated, "pub mod {} {{", identifier(crate_name, false))?;
+        writeln!(
+            generated,
+            "    use super::__shader_handles::{{EntryPointHandle, ExecutionModel, ShaderHandle}};"
+        )?;
+
+        let mut names = HashSet::new();
+        for module in &manifest.modules {
+            let module_const = identifier(&module.file, true);
+            if !names.insert(module_const.clone()) {
+                return Err(duplicate(crate_name, &module_const));
+            }
+            writeln!(generated, "\n    /// `{}`", module.file)?;
+            writeln!(
+                generated,
+                "    pub const {}: ShaderHandle = ShaderHandle::new({:?});",
+                module_const, module.file
+            )?;
+            if module.info.entry_points.is_empty() {
+                continue;
+            }
+
+            // Split and linked modules can declare the same entry point
+            let stem = module
+                .file
+                .rsplit_once('.')
+                .map_or(&*module.file, |(stem, _)| stem);
+            let module_name = identifier(stem, false);
+            if !names.insert(module_name.clone()) {
+                return Err(duplicate(crate_name, &module_name));
+            }
+            writeln!(generated, "\n    /// Entry points of `{}`", module.file)?;
+            writeln!(generated, "    pub mod {} {{", module_name)?;
+            writeln!(
+                generated,
+                "        use super::{{EntryPointHandle, ExecutionModel}};"
+            )?;
+            let mut entry_names = HashSet::new();
+            for entry_point in &module.info.entry_points {
+                // Entry points sharing a name differ in execution model
+                let mut name = identifier(&entry_point.name, true);
+                if entry_names.contains(&name) {
+                    name = format!(
+                        "{}_{}",
+                        name,
+                        identifier(&entry_point.execution_model, true)
+                    );
+                }
+                if !entry_names.insert(name.clone()) {
+                    return Err(duplicate(crate_name, &format!("{}::{}", module_name, name)));
+                }
+                writeln!(
+                    generated,
+                    "        /// `{}` ({})",
+                    entry_point.name, entry_point.execution_model
+                )?;
+                writeln!(
+                    generated,
+                    "        pub const {}: EntryPointHandle = EntryPointHandle::new(super::{}, {:?}, ExecutionModel::{});",
+                    name, module_const, entry_point.name, entry_point.execution_model
+                )?;
+            }
+            generated.push_str("    }\n");
+        }
+        generated.push_str("}\n");
+    }
+
+    let path = out_dir.join(GENERATED_FILE);
+    std::fs::write(&path, generated)?;
+    Ok(path)
+}
+
+#[cfg(feature = "compiler")]
+fn duplicate(crate_name: &str, name: &str) -> Box<dyn std::error::Error> {
+    format!(
+        "{}: more than one shader handle would be named {}",
+        crate_name, name
+    )
+    .into()
+}
+
+/// Turns a name into a Rust identifier, replacing each run of characters other
+/// than ASCII letters and digits with one `_`.
+///
+/// A leading digit gets a `_` prepended, and a keyword or a lone `_` a `_`
+/// appended.
+#[cfg(feature = "compiler")]
+fn identifier(name: &str, upper: bool) -> String {
+    let mut identifier = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            identifier.push(if upper {
+                c.to_ascii_uppercase()
+            } else {
+                c.to_ascii_lowercase()
+            });
+        } else if !identifier.ends_with('_') {
+            identifier.push('_');
+        }
+    }
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if identifier == "_" || KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Strict and reserved keywords of the 2024 edition, none of which a generated
+/// module may be named.
+#[cfg(feature = "compiler")]
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+#[cfg(all(test, feature = "compiler"))]
+mod tests {
+    use super::*;
+    use crate::manifest::ManifestModule;
+    use crate::reflect::{EntryPointInfo, ModuleInfo};
+
+    fn module(file: &str, entry_points: &[(&str, &str)]) -> ManifestModule {
+        ManifestModule {
+            file: file.to_string(),
+            size: 0,
+            hash: String::new(),
+            info: ModuleInfo {
+                spirv_version: (1, 3),
+                capabilities: Vec::new(),
+                extensions: Vec::new(),
+                entry_points: entry_points
+                    .iter()
+                    .map(|&(name, execution_model)| EntryPointInfo {
+                        name: name.to_string(),
+                        execution_model: execution_model.to_string(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    fn write_modules(
+        name: &str,
+        modules: Vec<ManifestModule>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let out_dir = std::env::temp_dir().join(format!(
+            "rust-gpu-hotreload-handles-{}-{}",
+            std::process::id(),
+            name
+        ));
+        std::fs::create_dir_all(&out_dir)?;
+        let manifest = Manifest {
+            generation: 1,
+            compiled_at: 0,
+            target: "spirv-unknown-vulkan1.2".to_string(),
+            modules,
+        };
+        let written = write(&out_dir, &[("shader-source".to_string(), manifest)]);
+        let generated = written.and_then(|path| Ok(std::fs::read_to_string(path)?));
+        std::fs::remove_dir_all(&out_dir)?;
+        generated
+    }
+
+    #[test]
+    fn identifiers() {
+        assert_eq!(identifier("main_vs.spv", true), "MAIN_VS_SPV");
+        assert_eq!(identifier("lighting::main_fs", true), "LIGHTING_MAIN_FS");
+        assert_eq!(identifier("shader-source", false), "shader_source");
+        assert_eq!(identifier("a--b..c", false), "a_b_c");
+    }
+
+    #[test]
+    fn identifiers_of_leading_digits() {
+        assert_eq!(identifier("2d_blit", true), "_2D_BLIT");
+        assert_eq!(identifier("1", false), "_1");
+    }
+
+    #[test]
+    fn identifiers_of_keywords() {
+        assert_eq!(identifier("mod", false), "mod_");
+        assert_eq!(identifier("Self", false), "self_");
+        assert_eq!(identifier("type", false), "type_");
+        // Upper case names are never keywords
+        assert_eq!(identifier("type", true), "TYPE");
+        assert_eq!(identifier("", false), "__");
+        assert_eq!(identifier("::", true), "__");
+    }
+
+    #[test]
+    fn namespaces_entry_points_by_module() {
+        let generated = write_modules(
+            "split",
+            vec![
+                module("main_vs.spv", &[("main_vs", "Vertex")]),
+                module("main_fs.spv", &[("main_fs", "Fragment")]),
+                module(
+                    "linked.spv",
+                    &[("main_vs", "Vertex"), ("main_fs", "Fragment")],
+                ),
+            ],
+        )
+        .unwrap();
+        assert!(generated.contains("pub mod shader_source {"));
+        assert!(generated.contains("pub mod main_vs {"));
+        assert!(generated.contains("pub mod linked {"));
+        assert!(generated.contains(
+            "pub const MAIN_VS: EntryPointHandle = EntryPointHandle::new(super::LINKED_SPV, \"main_vs\", ExecutionModel::Vertex);"
+        ));
+        assert!(generated.contains(
+            "pub const MAIN_VS: EntryPointHandle = EntryPointHandle::new(super::MAIN_VS_SPV, \"main_vs\", ExecutionModel::Vertex);"
+        ));
+    }
+
+    #[test]
+    fn suffixes_entry_points_sharing_a_name() {
+        let generated = write_modules(
+            "shared-name",
+            vec![module(
+                "mod.spv",
+                &[("main", "Vertex"), ("main", "Fragment")],
+            )],
+        )
+        .unwrap();
+        assert!(generated.contains("pub mod mod_ {"));
+        assert!(generated.contains("pub const MAIN: EntryPointHandle"));
+        assert!(generated.contains("pub const MAIN_FRAGMENT: EntryPointHandle"));
+    }
+
+    #[test]
+    fn loads_entry_points_sharing_a_name_by_execution_model() {
+        let generated = write_modules(
+            "shared-name-models",
+            vec![module(
+                "mod.spv",
+                &[("main", "Vertex"), ("main", "Fragment")],
+            )],
+        )
+        .unwrap();
+        assert!(generated.contains(
+            "pub const MAIN: EntryPointHandle = EntryPointHandle::new(super::MOD_SPV, \"main\", ExecutionModel::Vertex);"
+        ));
+        assert!(generated.contains(
+            "pub const MAIN_FRAGMENT: EntryPointHandle = EntryPointHandle::new(super::MOD_SPV, \"main\", ExecutionModel::Fragment);"
+        ));
+
+        // vulkano finds neither by name alone, so each is looked up with its model
+        let module = ShaderHandle::new("mod.spv");
+        let main = EntryPointHandle::new(module, "main", ExecutionModel::Vertex);
+        let main_fragment = EntryPointHandle::new(module, "main", ExecutionModel::Fragment);
+        assert_eq!(
+            main.vulkano_execution_model(),
+            VulkanoExecutionModel::Vertex
+        );
+        assert_eq!(
+            main_fragment.vulkano_execution_model(),
+            VulkanoExecutionModel::Fragment
+        );
+    }
+
+    #[test]
+    fn rejects_colliding_modules() {
+        let error = write_modules(
+            "modules",
+            vec![module("a-b.spv", &[]), module("a_b.spv", &[])],
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("A_B_SPV"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_colliding_entry_points() {
+        let error = write_modules(
+            "entry-points",
+            vec![module(
+                "lighting.spv",
+                &[
+                    ("a::b", "Fragment"),
+                    ("a_b", "Fragment"),
+                    ("a.b", "Fragment"),
+                ],
+            )],
+        )
+        .unwrap_err();
+        assert!(
+            error.to_string().contains("lighting::A_B_FRAGMENT"),
+            "{}",
+            error
+        );
+    }
+}
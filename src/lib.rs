@@ -55,16 +55,80 @@
 //!     }
 //! }
 //! ```
+//!
+//! # Cargo features
+//!
+//! - `hot-reload` (default): [`ShaderHotReloader`]. Enables `compiler`.
+//! - `compiler`: compiling shader crates with spirv-builder, including
+//!   [`ShaderHotReloaderBuilder::embed`] for build scripts.
+//! - `use-installed-tools` (default) / `use-compiled-tools`: which rust-gpu
+//!   codegen backend spirv-builder uses. Either enables `compiler`.
+//!
+//! Shipping builds can disable default features to keep only the runtime
+//! loader: [`ShaderOutputDir`], [`HotReloadable`], [`DeviceProfile`] and the
+//! [`spv`] reflection utilities work the same on precompiled shaders, without
+//! linking spirv-builder, notify or the rust-gpu toolchain. Shaders can be
+//! compiled into the executable with [`embed_shaders!`]; see [`embed`]. Typed
+//! constants for module and entry point names come from
+//! [`include_shader_handles!`]; see [`handles`].
+//!
+//! ```toml
+//! [dependencies]
+//! rust-gpu-hotreload = { version = "0.1", default-features = false }
+//! ```
 
+#[cfg(feature = "compiler")]
+mod build_lock;
+#[cfg(feature = "compiler")]
+mod build_options;
+#[cfg(feature = "compiler")]
 pub mod builder;
 pub mod compile;
+#[cfg(feature = "compiler")]
+mod compile_cache;
+pub mod device_profile;
+#[cfg(feature = "compiler")]
+pub mod diagnostics;
+pub mod embed;
+pub mod handles;
+pub mod manifest;
+mod module_cache;
+pub mod pack;
+#[cfg(feature = "compiler")]
+mod postprocess;
+mod publish;
+pub mod reflect;
+pub mod requirements;
+#[cfg(feature = "compiler")]
+mod runner;
+pub mod spv;
+#[cfg(feature = "compiler")]
+pub mod status;
+mod targets;
 pub mod vulkano_task;
+#[cfg(feature = "hot-reload")]
 pub mod watcher;
 
+#[cfg(feature = "compiler")]
 pub use builder::ShaderHotReloaderBuilder;
 pub use compile::ShaderOutputDir;
+pub use device_profile::{DeviceProfile, ProfileMismatch};
+#[cfg(feature = "compiler")]
+pub use diagnostics::{MissingRequirements, Suggestion};
+pub use embed::EmbeddedShaders;
+pub use handles::{EntryPointHandle, ShaderHandle};
+pub use pack::{Compression, ShaderPack};
+pub use publish::GenerationInfo;
+#[cfg(feature = "compiler")]
+pub use status::{CompileState, ReloadEvent, ReloadStatus};
 pub use vulkano_task::{HotReloadable, HotReloadableTask};
+#[cfg(feature = "hot-reload")]
 pub use watcher::ShaderHotReloader;
 
 const DEFAULT_TARGET: &str = "spirv-unknown-vulkan1.2";
+#[cfg(feature = "compiler")]
 const DEFAULT_DEBOUNCE_MS: u64 = 500;
+#[cfg(feature = "compiler")]
+const DEFAULT_KEPT_GENERATIONS: usize = 5;
+#[cfg(feature = "compiler")]
+const DEFAULT_COMPILE_CACHE_LIMIT: u64 = 256 * 1024 * 1024;
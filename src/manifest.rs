@@ -0,0 +1,88 @@
+//! `manifest.json`, describing every module of a published generation.
+//!
+//! Written next to the modules after each successful compile, so tools can
+//! inspect compiled shaders without parsing SPIR-V.
+
+use crate::reflect::ModuleInfo;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+#[cfg(feature = "compiler")]
+use {crate::publish::GenerationInfo, crate::reflect::content_hash};
+
+/// File in every generation directory holding its [`Manifest`].
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Description of the modules a compile produced.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub generation: u64,
+    /// Seconds since the Unix epoch when the compile was published.
+    pub compiled_at: u64,
+    /// SPIR-V target the modules were compiled for.
+    pub target: String,
+    pub modules: Vec<ManifestModule>,
+}
+
+/// A compiled module listed in a [`Manifest`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestModule {
+    /// File name within the generation directory.
+    pub file: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// BLAKE3 hash of the file, hex encoded.
+    pub hash: String,
+    #[serde(flatten)]
+    pub info: ModuleInfo,
+}
+
+impl Manifest {
+    /// Describes the `.spv` files of a generation whose files are in `dir`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a module cannot be read or is not valid SPIR-V.
+    #[cfg(feature = "compiler")]
+    pub(crate) fn describe(
+        info: &GenerationInfo,
+        dir: &Path,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut modules = Vec::new();
+        for file in info.files.iter().filter(|file| file.ends_with(".spv")) {
+            let bytes = std::fs::read(dir.join(file))?;
+            modules.push(ManifestModule {
+                file: file.clone(),
+                size: bytes.len() as u64,
+                hash: content_hash(&bytes),
+                info: ModuleInfo::from_bytes(&bytes).map_err(|e| format!("{}: {}", file, e))?,
+            });
+        }
+        Ok(Self {
+            generation: info.generation,
+            compiled_at: info.published_at,
+            target: info.target.clone(),
+            modules,
+        })
+    }
+
+    /// Reads the manifest of a generation directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Generation directory containing `manifest.json`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the manifest cannot be read or parsed.
+    pub fn read(dir: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = dir.as_ref().join(MANIFEST_FILE);
+        let json =
+            std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Returns the module with the given file name, if listed.
+    pub fn module(&self, file: &str) -> Option<&ManifestModule> {
+        self.modules.iter().find(|module| module.file == file)
+    }
+}
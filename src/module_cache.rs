@@ -0,0 +1,147 @@
+//! Vulkan shader modules shared by every `ShaderOutputDir` of a publish root.
+
+use crate::compile::load_shader_from_bytes;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use vulkano::device::Device;
+use vulkano::shader::ShaderModule;
+
+/// Device address and BLAKE3 hash of the SPIR-V bytes.
+///
+/// A cached module holds its device alive, so the address cannot be reused by
+/// another device while the entry exists.
+type CacheKey = (usize, [u8; 32]);
+
+/// Caches of the publish roots some `ShaderOutputDir` reads from, so every
+/// `ShaderOutputDir` of a root shares its modules.
+static CACHES: Mutex<Vec<(PathBuf, Weak<ShaderModuleCache>)>> = Mutex::new(Vec::new());
+
+/// Shader modules keyed by device and SPIR-V content.
+///
+/// Generic over the module type only so tests can cache values that need no device.
+pub(crate) struct ShaderModuleCache<M = ShaderModule> {
+    modules: Mutex<HashMap<CacheKey, Arc<M>>>,
+}
+
+impl<M> Default for ShaderModuleCache<M> {
+    fn default() -> Self {
+        Self {
+            modules: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl ShaderModuleCache {
+    /// Returns the cache of the publish root `root`, creating it if no
+    /// `ShaderOutputDir` of the root holds one.
+    pub fn for_root(root: &Path) -> Arc<Self> {
+        let mut caches = CACHES.lock();
+        caches.retain(|(_, cache)| cache.strong_count() > 0);
+        if let Some(cache) = caches
+            .iter()
+            .find(|(path, _)| path == root)
+            .and_then(|(_, cache)| cache.upgrade())
+        {
+            return cache;
+        }
+        let cache = Arc::new(Self::default());
+        caches.push((root.to_path_buf(), Arc::downgrade(&cache)));
+        cache
+    }
+
+    /// Returns the cached module for `bytes` on `device`, creating it on a miss.
+    pub fn get_or_load(
+        &self,
+        device: Arc<Device>,
+        bytes: &[u8],
+    ) -> Result<Arc<ShaderModule>, Box<dyn std::error::Error>> {
+        let key = (
+            Arc::as_ptr(&device) as usize,
+            *blake3::hash(bytes).as_bytes(),
+        );
+        self.get_or_insert_with(key, || load_shader_from_bytes(device, bytes))
+    }
+}
+
+impl<M> ShaderModuleCache<M> {
+    /// Returns the cached module for `key`, calling `load` on a miss.
+    ///
+    /// The module is created without holding the cache lock, so loads of other
+    /// modules are not held up. If another thread created the same module in
+    /// the meantime, its module is returned and this one dropped.
+    fn get_or_insert_with(
+        &self,
+        key: CacheKey,
+        load: impl FnOnce() -> Result<Arc<M>, Box<dyn std::error::Error>>,
+    ) -> Result<Arc<M>, Box<dyn std::error::Error>> {
+        if let Some(module) = self.modules.lock().get(&key) {
+            return Ok(module.clone());
+        }
+
+        let module = load()?;
+        Ok(self.modules.lock().entry(key).or_insert(module).clone())
+    }
+
+    /// Drops modules that are only referenced by the cache.
+    ///
+    /// Call once the pipelines built from replaced modules are gone, e.g.
+    /// after a reload rebuilt them.
+    pub fn evict_unused(&self) {
+        self.modules
+            .lock()
+            .retain(|_, module| Arc::strong_count(module) > 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shares_one_cache_per_root() {
+        let first = ShaderModuleCache::for_root(Path::new("/shaders/a.published"));
+        let again = ShaderModuleCache::for_root(Path::new("/shaders/a.published"));
+        let other = ShaderModuleCache::for_root(Path::new("/shaders/b.published"));
+        assert!(Arc::ptr_eq(&first, &again));
+        assert!(!Arc::ptr_eq(&first, &other));
+
+        // Nothing keeps a dropped cache registered
+        let dropped = Arc::downgrade(&other);
+        drop(other);
+        ShaderModuleCache::for_root(Path::new("/shaders/c.published"));
+        assert!(
+            CACHES
+                .lock()
+                .iter()
+                .all(|(_, cache)| !cache.ptr_eq(&dropped))
+        );
+    }
+
+    #[test]
+    fn drops_replaced_modules_after_reload() {
+        let cache = ShaderModuleCache::<&str>::default();
+        let load = |key: u8, module: &'static str| {
+            cache
+                .get_or_insert_with((0, [key; 32]), || Ok(Arc::new(module)))
+                .unwrap()
+        };
+
+        // A pipeline built from the first generation holds its module
+        let mut pipeline = load(1, "old");
+        let old = Arc::downgrade(&pipeline);
+        assert!(Arc::ptr_eq(&pipeline, &load(1, "unused")));
+
+        // Publishing alone must not drop a module a pipeline still uses
+        cache.evict_unused();
+        assert!(old.upgrade().is_some());
+
+        // The reload replaces the pipeline, after which the old module goes
+        pipeline = load(2, "new");
+        cache.evict_unused();
+        assert!(old.upgrade().is_none());
+        assert_eq!(*load(2, "unused"), "new");
+        drop(pipeline);
+    }
+}
@@ -0,0 +1,403 @@
+//! Single-file shader packs for shipping compiled shaders.
+//!
+//! A pack holds every file of one shader generation together with its
+//! reflection metadata and BLAKE3 hash, optionally deflate-compressed. Export
+//! one with [`ShaderOutputDir::export_pack`] and load from it with
+//! [`ShaderOutputDir::open_pack`]; each file's hash is checked as it is read.
+//!
+//! Layout, with integers in little endian:
+//!
+//! | Bytes | Content |
+//! |-------|---------|
+//! | 8 | Magic `RGPUPACK` |
+//! | 4 | Format version |
+//! | 4 | Header length |
+//! | n | [`PackHeader`] as JSON |
+//! | rest | File data, at the offsets the header lists |
+
+use crate::reflect::{ModuleInfo, content_hash};
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+const MAGIC: &[u8; 8] = b"RGPUPACK";
+const FORMAT_VERSION: u32 = 1;
+/// Magic, format version and header length.
+const PREAMBLE_LEN: usize = 16;
+
+/// How file data is stored in a pack.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    #[default]
+    None,
+    Deflate,
+}
+
+/// Contents of a shader pack.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackHeader {
+    /// SPIR-V target the shaders were compiled for.
+    pub target: String,
+    /// Feature permutation the shaders were built with, if not the default features.
+    pub permutation: Option<String>,
+    /// Generation the pack was exported from, if it came from a published generation.
+    pub generation: Option<u64>,
+    pub compression: Compression,
+    /// Capabilities any module declares, by name.
+    pub capabilities: Vec<String>,
+    /// Extensions any module declares.
+    pub extensions: Vec<String>,
+    pub files: Vec<PackFile>,
+}
+
+/// A file stored in a shader pack.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackFile {
+    pub name: String,
+    /// Offset of the stored data from the end of the header.
+    pub offset: u64,
+    /// Length of the stored data, after compression.
+    pub stored_size: u64,
+    /// Length of the file.
+    pub size: u64,
+    /// BLAKE3 hash of the file, hex encoded.
+    pub hash: String,
+    /// Metadata of the file if it is a SPIR-V module.
+    pub module: Option<ModuleInfo>,
+}
+
+/// A shader pack read into memory.
+pub struct ShaderPack {
+    /// File the pack was read from, or a name derived from its contents if it
+    /// was read from memory. Identifies the pack in paths and errors.
+    path: PathBuf,
+    header: PackHeader,
+    /// File data following the header.
+    data: Vec<u8>,
+}
+
+impl ShaderPack {
+    /// Reads a shader pack file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the pack file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a valid shader pack.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        Self::parse(path.to_path_buf(), bytes)
+            .map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// Reads a shader pack from memory, e.g. after downloading it.
+    ///
+    /// The pack is named `shader-pack-{hash}` after the start of its BLAKE3
+    /// hash, so packs with different contents are told apart.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Contents of a pack file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid shader pack.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, Box<dyn std::error::Error>> {
+        let name = format!("shader-pack-{}", &content_hash(&bytes)[..16]);
+        Self::parse(PathBuf::from(name), bytes)
+    }
+
+    fn parse(path: PathBuf, mut bytes: Vec<u8>) -> Result<Self, Box<dyn std::error::Error>> {
+        if bytes.len() < PREAMBLE_LEN || &bytes[..8] != MAGIC {
+            return Err("not a shader pack".into());
+        }
+        let version = u32::from_le_bytes(bytes[8..12].try_into()?);
+        if version != FORMAT_VERSION {
+            return Err(format!("unsupported shader pack version {}", version).into());
+        }
+        let header_len = u32::from_le_bytes(bytes[12..16].try_into()?) as usize;
+        let header_end = PREAMBLE_LEN
+            .checked_add(header_len)
+            .filter(|&end| end <= bytes.len())
+            .ok_or("shader pack header is truncated")?;
+        let header: PackHeader = serde_json::from_slice(&bytes[PREAMBLE_LEN..header_end])?;
+
+        let data = bytes.split_off(header_end);
+        for file in &header.files {
+            let in_bounds = file
+                .offset
+                .checked_add(file.stored_size)
+                .is_some_and(|end| end <= data.len() as u64);
+            if !in_bounds {
+                return Err(format!("{} lies outside the shader pack", file.name).into());
+            }
+        }
+        Ok(Self { path, header, data })
+    }
+
+    pub fn header(&self) -> &PackHeader {
+        &self.header
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads a file from the pack, checking its size and hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the file, as listed in [`PackHeader::files`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pack has no such file, it cannot be
+    /// decompressed, or its contents do not match the recorded hash.
+    pub fn read(&self, name: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let file = self
+            .header
+            .files
+            .iter()
+            .find(|file| file.name == name)
+            .ok_or_else(|| format!("{} is not in the shader pack", name))?;
+        let stored = &self.data[file.offset as usize..(file.offset + file.stored_size) as usize];
+        let bytes = match self.header.compression {
+            Compression::None => stored.to_vec(),
+            Compression::Deflate => {
+                // Read one byte past the recorded size to detect a mismatch
+                let mut bytes = Vec::new();
+                DeflateDecoder::new(stored)
+                    .take(file.size.saturating_add(1))
+                    .read_to_end(&mut bytes)
+                    .map_err(|e| format!("{}: {}", name, e))?;
+                bytes
+            }
+        };
+        if bytes.len() as u64 != file.size || content_hash(&bytes) != file.hash {
+            return Err(format!("{}: contents do not match the shader pack's hash", name).into());
+        }
+        Ok(bytes)
+    }
+
+    /// Reads every file in the pack, checking sizes and hashes.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first file that fails [`Self::read`].
+    pub fn verify(&self) -> Result<(), Box<dyn std::error::Error>> {
+        for file in &self.header.files {
+            self.read(&file.name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes `files` to a new shader pack at `path`, replacing any existing file.
+///
+/// Files named `*.spv` must be valid SPIR-V; their metadata is recorded in the
+/// header.
+pub(crate) fn write(
+    path: &Path,
+    target: &str,
+    permutation: Option<&str>,
+    generation: Option<u64>,
+    files: &[(String, Vec<u8>)],
+    compression: Compression,
+) -> Result<PackHeader, Box<dyn std::error::Error>> {
+    let mut header = PackHeader {
+        target: target.to_string(),
+        permutation: permutation.map(str::to_string),
+        generation,
+        compression,
+        capabilities: Vec::new(),
+        extensions: Vec::new(),
+        files: Vec::new(),
+    };
+    let mut data = Vec::new();
+    for (name, bytes) in files {
+        let module = if name.ends_with(".spv") {
+            let info = ModuleInfo::from_bytes(bytes).map_err(|e| format!("{}: {}", name, e))?;
+            for capability in &info.capabilities {
+                if !header.capabilities.contains(capability) {
+                    header.capabilities.push(capability.clone());
+                }
+            }
+            for extension in &info.extensions {
+                if !header.extensions.contains(extension) {
+                    header.extensions.push(extension.clone());
+                }
+            }
+            Some(info)
+        } else {
+            None
+        };
+
+        let stored = match compression {
+            Compression::None => bytes.clone(),
+            Compression::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(bytes)?;
+                encoder.finish()?
+            }
+        };
+        header.files.push(PackFile {
+            name: name.clone(),
+            offset: data.len() as u64,
+            stored_size: stored.len() as u64,
+            size: bytes.len() as u64,
+            hash: content_hash(bytes),
+            module,
+        });
+        data.extend_from_slice(&stored);
+    }
+
+    let header_json = serde_json::to_vec(&header)?;
+    let mut pack = Vec::with_capacity(PREAMBLE_LEN + header_json.len() + data.len());
+    pack.extend_from_slice(MAGIC);
+    pack.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    pack.extend_from_slice(&u32::try_from(header_json.len())?.to_le_bytes());
+    pack.extend_from_slice(&header_json);
+    pack.extend_from_slice(&data);
+
+    // Replace atomically, so a launcher never picks up a partial pack
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, pack)?;
+    std::fs::rename(&tmp, path)?;
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spv::{Instruction, test_module};
+    use spirv::{Capability, Op};
+
+    fn shader() -> Vec<u8> {
+        let shader = Instruction::new(Op::Capability, vec![Capability::Shader as u32]);
+        test_module((1, 3), 1, vec![shader])
+            .to_words()
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect()
+    }
+
+    fn files() -> Vec<(String, Vec<u8>)> {
+        vec![
+            ("main.spv".to_string(), shader()),
+            ("notes.txt".to_string(), b"hello hello hello hello".to_vec()),
+        ]
+    }
+
+    /// Writes a pack of [`files`] and returns its bytes.
+    fn pack(name: &str, compression: Compression) -> Vec<u8> {
+        let path = std::env::temp_dir().join(format!(
+            "rust-gpu-hotreload-pack-{}-{}",
+            std::process::id(),
+            name
+        ));
+        write(
+            &path,
+            "spirv-unknown-vulkan1.2",
+            None,
+            Some(3),
+            &files(),
+            compression,
+        )
+        .unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        bytes
+    }
+
+    /// Rewrites the header of `bytes` with `edit`.
+    fn edit_header(bytes: &[u8], edit: impl FnOnce(&mut PackHeader)) -> Vec<u8> {
+        let header_len = u32::from_le_bytes(bytes[12..16].try_into().unwrap()) as usize;
+        let header_end = PREAMBLE_LEN + header_len;
+        let mut header: PackHeader =
+            serde_json::from_slice(&bytes[PREAMBLE_LEN..header_end]).unwrap();
+        edit(&mut header);
+        let header_json = serde_json::to_vec(&header).unwrap();
+        let mut edited = bytes[..12].to_vec();
+        edited.extend_from_slice(&(header_json.len() as u32).to_le_bytes());
+        edited.extend_from_slice(&header_json);
+        edited.extend_from_slice(&bytes[header_end..]);
+        edited
+    }
+
+    #[test]
+    fn round_trips_files() {
+        for (name, compression) in [
+            ("plain", Compression::None),
+            ("deflate", Compression::Deflate),
+        ] {
+            let pack = ShaderPack::from_bytes(pack(name, compression)).unwrap();
+            let header = pack.header();
+            assert_eq!(header.compression, compression);
+            assert_eq!(header.generation, Some(3));
+            assert_eq!(header.capabilities, ["Shader"]);
+            assert!(header.files[0].module.is_some());
+            assert!(header.files[1].module.is_none());
+            for (name, bytes) in files() {
+                assert_eq!(pack.read(&name).unwrap(), bytes);
+            }
+            pack.verify().unwrap();
+            assert!(pack.read("missing.spv").is_err());
+        }
+    }
+
+    #[test]
+    fn names_packs_by_content() {
+        let plain = ShaderPack::from_bytes(pack("name-plain", Compression::None)).unwrap();
+        let again = ShaderPack::from_bytes(pack("name-again", Compression::None)).unwrap();
+        let deflate = ShaderPack::from_bytes(pack("name-deflate", Compression::Deflate)).unwrap();
+        assert_eq!(plain.path(), again.path());
+        assert_ne!(plain.path(), deflate.path());
+    }
+
+    #[test]
+    fn rejects_deflated_size_mismatch() {
+        let bytes = pack("size", Compression::Deflate);
+        for size in [22, 24] {
+            let edited = edit_header(&bytes, |header| header.files[1].size = size);
+            let pack = ShaderPack::from_bytes(edited).unwrap();
+            assert!(pack.read("notes.txt").is_err());
+            assert!(pack.verify().is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_hash_mismatch() {
+        let mut bytes = pack("hash", Compression::None);
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        let pack = ShaderPack::from_bytes(bytes).unwrap();
+        assert!(pack.read("main.spv").is_ok());
+        assert!(pack.read("notes.txt").is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_header() {
+        let bytes = pack("truncated", Compression::None);
+        assert!(ShaderPack::from_bytes(bytes[..PREAMBLE_LEN - 1].to_vec()).is_err());
+        let header_len = u32::from_le_bytes(bytes[12..16].try_into().unwrap()) as usize;
+        let cut = bytes[..PREAMBLE_LEN + header_len - 1].to_vec();
+        assert!(ShaderPack::from_bytes(cut).is_err());
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[0] = b'X';
+        assert!(ShaderPack::from_bytes(wrong_magic).is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_files() {
+        let bytes = pack("bounds", Compression::None);
+        let past_end = edit_header(&bytes, |header| header.files[1].offset += 1);
+        assert!(ShaderPack::from_bytes(past_end).is_err());
+        let overflow = edit_header(&bytes, |header| header.files[1].offset = u64::MAX);
+        assert!(ShaderPack::from_bytes(overflow).is_err());
+    }
+}
@@ -0,0 +1,600 @@
+//! Post-compile rewrites applied to every emitted SPIR-V module.
+
+use crate::requirements;
+use crate::spv::{Module, SpirvError, decode_string, encode_string, opcode_name};
+use spirv::{Capability, Decoration, Op};
+use std::collections::{HashMap, HashSet};
+
+const NON_SEMANTIC_PREFIX: &str = "NonSemantic.";
+const NON_SEMANTIC_INFO: &str = "SPV_KHR_non_semantic_info";
+
+/// A `(set, binding)` the shader declares and the one to move it to.
+type BindingRemap = ((u32, u32), (u32, u32));
+
+/// Rewrites configured on the builder, run after every successful compile.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PostProcess {
+    pub strip_capabilities: Vec<Capability>,
+    pub strip_extensions: Vec<String>,
+    pub rename_extensions: Vec<(String, String)>,
+    pub strip_ext_inst_imports: Vec<String>,
+    pub strip_non_semantic: bool,
+    pub binding_remaps: Vec<BindingRemap>,
+    pub entry_point_renames: Vec<(String, String)>,
+    pub spec_id_remaps: Vec<(u32, u32)>,
+}
+
+impl PostProcess {
+    /// Returns true if no rewrite is configured.
+    pub fn is_empty(&self) -> bool {
+        self.strip_capabilities.is_empty()
+            && self.strip_extensions.is_empty()
+            && self.rename_extensions.is_empty()
+            && self.strip_ext_inst_imports.is_empty()
+            && !self.strip_non_semantic
+            && self.binding_remaps.is_empty()
+            && self.entry_point_renames.is_empty()
+            && self.spec_id_remaps.is_empty()
+    }
+
+    /// Applies every configured rewrite to `module`.
+    ///
+    /// Fails if a remaining instruction still depends on something that was removed.
+    pub fn apply(&self, module: &mut Module) -> Result<(), SpirvError> {
+        let extensions_before = module.extensions();
+
+        module.instructions.retain(|inst| {
+            !(inst.is(Op::Capability)
+                && inst
+                    .operands
+                    .first()
+                    .and_then(|&cap| Capability::from_u32(cap))
+                    .is_some_and(|cap| self.strip_capabilities.contains(&cap)))
+        });
+
+        self.strip_ext_inst_sets(module)?;
+        self.rewrite_extensions(module);
+        self.validate_extensions(module, &extensions_before)?;
+
+        self.remap_bindings(module)?;
+        self.remap_spec_ids(module);
+        self.rename_entry_points(module)
+    }
+
+    /// Rewrites `DescriptorSet`/`Binding` decorations according to the remap table.
+    ///
+    /// Lookups use the original decorations, so remaps can swap bindings. Fails
+    /// if two remaps share a target, one binding is remapped to two targets, or
+    /// a resource is moved onto a binding another resource still uses.
+    fn remap_bindings(&self, module: &mut Module) -> Result<(), SpirvError> {
+        if self.binding_remaps.is_empty() {
+            return Ok(());
+        }
+        let mut targets = HashMap::new();
+        let mut remapped = HashMap::new();
+        for &(from, to) in &self.binding_remaps {
+            if let Some(other) = targets.insert(to, from)
+                && other != from
+            {
+                return Err(SpirvError::Validation(format!(
+                    "bindings {other:?} and {from:?} are both remapped to {to:?}"
+                )));
+            }
+            if let Some(other) = remapped.insert(from, to)
+                && other != to
+            {
+                return Err(SpirvError::Validation(format!(
+                    "binding {from:?} is remapped to both {other:?} and {to:?}"
+                )));
+            }
+        }
+
+        // Target id -> instruction indices of its DescriptorSet and Binding decorations.
+        let mut decorations: HashMap<u32, (Option<usize>, Option<usize>)> = HashMap::new();
+        for (index, inst) in module.instructions.iter().enumerate() {
+            if !inst.is(Op::Decorate) || inst.operands.len() < 3 {
+                continue;
+            }
+            let entry = decorations.entry(inst.operands[0]).or_default();
+            match Decoration::from_u32(inst.operands[1]) {
+                Some(Decoration::DescriptorSet) => entry.0 = Some(index),
+                Some(Decoration::Binding) => entry.1 = Some(index),
+                _ => {}
+            }
+        }
+
+        // Original binding of the resources at each final binding, to find collisions.
+        let mut sources: HashMap<(u32, u32), (u32, u32)> = HashMap::new();
+        let mut rewrites = Vec::new();
+        for (set_index, binding_index) in decorations.into_values() {
+            let (Some(set_index), Some(binding_index)) = (set_index, binding_index) else {
+                continue;
+            };
+            let from = (
+                module.instructions[set_index].operands[2],
+                module.instructions[binding_index].operands[2],
+            );
+            let to = self
+                .binding_remaps
+                .iter()
+                .find(|(src, _)| *src == from)
+                .map_or(from, |&(_, to)| to);
+            if let Some(&other) = sources.get(&to)
+                && other != from
+            {
+                let (moved, kept) = if to == other {
+                    (from, other)
+                } else {
+                    (other, from)
+                };
+                return Err(SpirvError::Validation(format!(
+                    "remapping {moved:?} to {to:?} collides with the resource at {kept:?}"
+                )));
+            }
+            sources.insert(to, from);
+            if to != from {
+                rewrites.push((set_index, binding_index, to));
+            }
+        }
+
+        for (set_index, binding_index, (set, binding)) in rewrites {
+            module.instructions[set_index].operands[2] = set;
+            module.instructions[binding_index].operands[2] = binding;
+        }
+        Ok(())
+    }
+
+    /// Rewrites `SpecId` decorations according to the remap table.
+    fn remap_spec_ids(&self, module: &mut Module) {
+        for inst in &mut module.instructions {
+            if inst.is(Op::Decorate)
+                && inst.operands.len() >= 3
+                && inst.operands[1] == Decoration::SpecId as u32
+                && let Some(&(_, to)) = self
+                    .spec_id_remaps
+                    .iter()
+                    .find(|(from, _)| *from == inst.operands[2])
+            {
+                inst.operands[2] = to;
+            }
+        }
+    }
+
+    /// Renames entry points, failing if two would end up with the same name and model.
+    fn rename_entry_points(&self, module: &mut Module) -> Result<(), SpirvError> {
+        if self.entry_point_renames.is_empty() {
+            return Ok(());
+        }
+
+        for inst in &mut module.instructions {
+            if !inst.is(Op::EntryPoint) || inst.operands.len() < 3 {
+                continue;
+            }
+            let Some((name, name_words)) = decode_string(&inst.operands[2..]) else {
+                continue;
+            };
+            if let Some((_, to)) = self
+                .entry_point_renames
+                .iter()
+                .find(|(from, _)| *from == name)
+            {
+                let interface = inst.operands.split_off(2 + name_words);
+                inst.operands.truncate(2);
+                inst.operands.extend(encode_string(to));
+                inst.operands.extend(interface);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        for entry_point in module.entry_points() {
+            if !seen.insert((entry_point.execution_model, entry_point.name.clone())) {
+                return Err(SpirvError::Validation(format!(
+                    "renaming left two {:?} entry points named \"{}\"",
+                    entry_point.execution_model, entry_point.name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn rewrite_extensions(&self, module: &mut Module) {
+        let drop_non_semantic_info = self.strip_non_semantic
+            && !module
+                .ext_inst_imports()
+                .iter()
+                .any(|(_, name)| name.starts_with(NON_SEMANTIC_PREFIX));
+
+        module.instructions.retain_mut(|inst| {
+            if !inst.is(Op::Extension) {
+                return true;
+            }
+            let Some((name, _)) = decode_string(&inst.operands) else {
+                return true;
+            };
+            if self.strip_extensions.contains(&name)
+                || (drop_non_semantic_info && name == NON_SEMANTIC_INFO)
+            {
+                return false;
+            }
+            if let Some((_, to)) = self
+                .rename_extensions
+                .iter()
+                .find(|(from, _)| *from == name)
+            {
+                inst.operands = encode_string(to);
+            }
+            true
+        });
+    }
+
+    /// Removes stripped `OpExtInstImport`s along with every `OpExtInst` from those sets.
+    fn strip_ext_inst_sets(&self, module: &mut Module) -> Result<(), SpirvError> {
+        let sets: HashSet<u32> = module
+            .ext_inst_imports()
+            .into_iter()
+            .filter(|(_, name)| {
+                self.strip_ext_inst_imports.contains(name)
+                    || (self.strip_non_semantic && name.starts_with(NON_SEMANTIC_PREFIX))
+            })
+            .map(|(id, _)| id)
+            .collect();
+        if sets.is_empty() {
+            return Ok(());
+        }
+
+        let mut removed = sets.clone();
+        module.instructions.retain(|inst| {
+            let remove = if inst.is(Op::ExtInstImport) {
+                inst.operands.first().is_some_and(|id| sets.contains(id))
+            } else if inst.is(Op::ExtInst) {
+                inst.operands.get(2).is_some_and(|set| sets.contains(set))
+            } else {
+                false
+            };
+            if remove {
+                removed.extend(inst.result_id());
+            }
+            !remove
+        });
+
+        // Names and decorations only describe their target, so they go with it.
+        module.instructions.retain(|inst| {
+            let is_attached = [
+                Op::Name,
+                Op::MemberName,
+                Op::Decorate,
+                Op::MemberDecorate,
+                Op::DecorateId,
+                Op::DecorateString,
+                Op::MemberDecorateString,
+            ]
+            .into_iter()
+            .any(|op| inst.is(op));
+            !(is_attached && inst.operands.first().is_some_and(|id| removed.contains(id)))
+        });
+
+        let layout = module.layout()?;
+        for (inst, spans) in module.instructions.iter().zip(&layout) {
+            for span in spans.iter().filter(|span| span.is_id()) {
+                let id = inst.operands[span.start];
+                if removed.contains(&id) {
+                    return Err(SpirvError::Validation(format!(
+                        "{} still references %{id}, which was removed with its extended instruction set",
+                        opcode_name(inst.opcode)
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that removing or renaming extensions left every remaining capability usable.
+    fn validate_extensions(
+        &self,
+        module: &Module,
+        extensions_before: &[String],
+    ) -> Result<(), SpirvError> {
+        let version = module.header.version();
+        let extensions = module.extensions();
+
+        for capability in module.capabilities() {
+            let Some(requirement) = requirements::requirement(capability) else {
+                continue;
+            };
+            if requirement.is_satisfied(version, extensions_before)
+                && !requirement.is_satisfied(version, &extensions)
+            {
+                return Err(SpirvError::Validation(format!(
+                    "capability {capability:?} still requires one of {:?}, which were stripped",
+                    requirement.extensions
+                )));
+            }
+        }
+
+        let uses_non_semantic = module
+            .ext_inst_imports()
+            .iter()
+            .any(|(_, name)| name.starts_with(NON_SEMANTIC_PREFIX));
+        if uses_non_semantic
+            && version < (1, 6)
+            && extensions_before.iter().any(|ext| ext == NON_SEMANTIC_INFO)
+            && !extensions.iter().any(|ext| ext == NON_SEMANTIC_INFO)
+        {
+            return Err(SpirvError::Validation(format!(
+                "{NON_SEMANTIC_INFO} was stripped but NonSemantic instruction sets are still imported"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spv::{Instruction, test_module};
+    use spirv::ExecutionModel;
+
+    fn capability(capability: Capability) -> Instruction {
+        Instruction::new(Op::Capability, vec![capability as u32])
+    }
+
+    fn extension(name: &str) -> Instruction {
+        Instruction::new(Op::Extension, encode_string(name))
+    }
+
+    fn import(id: u32, name: &str) -> Instruction {
+        let mut operands = vec![id];
+        operands.extend(encode_string(name));
+        Instruction::new(Op::ExtInstImport, operands)
+    }
+
+    #[test]
+    fn strips_and_renames_extensions() {
+        let mut module = test_module(
+            (1, 3),
+            1,
+            vec![
+                capability(Capability::Shader),
+                extension("SPV_GOOGLE_user_type"),
+                extension("SPV_KHR_old_name"),
+            ],
+        );
+        let post = PostProcess {
+            strip_extensions: vec!["SPV_GOOGLE_user_type".to_owned()],
+            rename_extensions: vec![("SPV_KHR_old_name".to_owned(), "SPV_KHR_new".to_owned())],
+            ..Default::default()
+        };
+        post.apply(&mut module).unwrap();
+        assert_eq!(module.extensions(), vec!["SPV_KHR_new".to_owned()]);
+    }
+
+    #[test]
+    fn refuses_to_strip_a_required_extension() {
+        let mut module = test_module(
+            (1, 4),
+            1,
+            vec![
+                capability(Capability::VulkanMemoryModel),
+                extension("SPV_KHR_vulkan_memory_model"),
+            ],
+        );
+        let post = PostProcess {
+            strip_extensions: vec!["SPV_KHR_vulkan_memory_model".to_owned()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            post.apply(&mut module),
+            Err(SpirvError::Validation(_))
+        ));
+
+        // Stripping the capability as well leaves nothing that needs it
+        let post = PostProcess {
+            strip_capabilities: vec![Capability::VulkanMemoryModel],
+            ..post
+        };
+        post.apply(&mut module).unwrap();
+        assert!(module.capabilities().is_empty());
+    }
+
+    #[test]
+    fn strips_non_semantic_sets_with_their_instructions() {
+        let mut module = test_module(
+            (1, 3),
+            10,
+            vec![
+                extension(NON_SEMANTIC_INFO),
+                import(1, "NonSemantic.DebugPrintf"),
+                import(2, "GLSL.std.450"),
+                Instruction::new(Op::TypeVoid, vec![3]),
+                Instruction::new(Op::Decorate, vec![4, Decoration::RelaxedPrecision as u32]),
+                Instruction::new(Op::ExtInst, vec![3, 4, 1, 1]),
+                Instruction::new(Op::ExtInst, vec![3, 5, 2, 1]),
+            ],
+        );
+        let post = PostProcess {
+            strip_non_semantic: true,
+            ..Default::default()
+        };
+        post.apply(&mut module).unwrap();
+        assert!(module.extensions().is_empty());
+        assert_eq!(
+            module.ext_inst_imports(),
+            vec![(2, "GLSL.std.450".to_owned())]
+        );
+        assert_eq!(module.instructions.len(), 3);
+        assert_eq!(module.instructions[2].result_id(), Some(5));
+    }
+
+    #[test]
+    fn refuses_to_strip_a_set_still_referenced() {
+        let mut module = test_module(
+            (1, 3),
+            10,
+            vec![
+                import(1, "NonSemantic.DebugPrintf"),
+                Instruction::new(Op::TypeVoid, vec![3]),
+                Instruction::new(Op::ExtInst, vec![3, 4, 1, 1]),
+                Instruction::new(Op::CopyObject, vec![3, 5, 4]),
+            ],
+        );
+        let post = PostProcess {
+            strip_ext_inst_imports: vec!["NonSemantic.DebugPrintf".to_owned()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            post.apply(&mut module),
+            Err(SpirvError::Validation(_))
+        ));
+    }
+
+    fn resource(id: u32, set: u32, binding: u32) -> [Instruction; 2] {
+        [
+            Instruction::new(
+                Op::Decorate,
+                vec![id, Decoration::DescriptorSet as u32, set],
+            ),
+            Instruction::new(Op::Decorate, vec![id, Decoration::Binding as u32, binding]),
+        ]
+    }
+
+    fn bindings(module: &Module) -> Vec<(u32, u32, u32)> {
+        module
+            .instructions
+            .chunks(2)
+            .map(|pair| {
+                (
+                    pair[0].operands[0],
+                    pair[0].operands[2],
+                    pair[1].operands[2],
+                )
+            })
+            .collect()
+    }
+
+    fn remap(remaps: &[BindingRemap]) -> PostProcess {
+        PostProcess {
+            binding_remaps: remaps.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn remaps_and_swaps_bindings() {
+        let resources = [resource(1, 0, 0), resource(2, 0, 1), resource(3, 1, 0)];
+        let mut module = test_module((1, 3), 4, resources.concat());
+        remap(&[((0, 0), (0, 1)), ((0, 1), (0, 0)), ((1, 0), (2, 5))])
+            .apply(&mut module)
+            .unwrap();
+        assert_eq!(bindings(&module), vec![(1, 0, 1), (2, 0, 0), (3, 2, 5)]);
+    }
+
+    #[test]
+    fn keeps_bindings_the_source_already_shares() {
+        let resources = [resource(1, 0, 0), resource(2, 0, 0)];
+        let mut module = test_module((1, 3), 3, resources.concat());
+        remap(&[((0, 0), (1, 0))]).apply(&mut module).unwrap();
+        assert_eq!(bindings(&module), vec![(1, 1, 0), (2, 1, 0)]);
+    }
+
+    #[test]
+    fn rejects_duplicate_remap_targets() {
+        let resources = [resource(1, 0, 0), resource(2, 0, 1)];
+        let mut module = test_module((1, 3), 3, resources.concat());
+        let result = remap(&[((0, 0), (1, 0)), ((0, 1), (1, 0))]).apply(&mut module);
+        assert!(matches!(result, Err(SpirvError::Validation(_))));
+    }
+
+    #[test]
+    fn rejects_remapping_a_binding_twice() {
+        let resources = [resource(1, 0, 1), resource(2, 1, 0)];
+        let mut module = test_module((1, 3), 3, resources.concat());
+        let result = remap(&[((0, 1), (2, 0)), ((0, 1), (3, 0))]).apply(&mut module);
+        assert!(matches!(result, Err(SpirvError::Validation(_))));
+        assert_eq!(bindings(&module), vec![(1, 0, 1), (2, 1, 0)]);
+
+        // Repeating the same remap is harmless
+        remap(&[((0, 1), (2, 0)), ((0, 1), (2, 0))])
+            .apply(&mut module)
+            .unwrap();
+        assert_eq!(bindings(&module), vec![(1, 2, 0), (2, 1, 0)]);
+    }
+
+    #[test]
+    fn rejects_remapping_onto_a_binding_in_use() {
+        let resources = [resource(1, 0, 0), resource(2, 0, 1)];
+        let mut module = test_module((1, 3), 3, resources.concat());
+        let result = remap(&[((0, 0), (0, 1))]).apply(&mut module);
+        assert!(matches!(result, Err(SpirvError::Validation(_))));
+        // Nothing is rewritten when the remap fails
+        assert_eq!(bindings(&module), vec![(1, 0, 0), (2, 0, 1)]);
+    }
+
+    #[test]
+    fn remaps_spec_ids() {
+        let spec_id =
+            |id, spec| Instruction::new(Op::Decorate, vec![id, Decoration::SpecId as u32, spec]);
+        let mut module = test_module((1, 3), 3, vec![spec_id(1, 0), spec_id(2, 1)]);
+        let post = PostProcess {
+            spec_id_remaps: vec![(0, 1), (1, 0)],
+            ..Default::default()
+        };
+        post.apply(&mut module).unwrap();
+        assert_eq!(module.instructions[0].operands[2], 1);
+        assert_eq!(module.instructions[1].operands[2], 0);
+    }
+
+    fn entry_point(model: ExecutionModel, function: u32, name: &str) -> Instruction {
+        let mut operands = vec![model as u32, function];
+        operands.extend(encode_string(name));
+        operands.push(9);
+        Instruction::new(Op::EntryPoint, operands)
+    }
+
+    fn renames(renames: &[(&str, &str)]) -> PostProcess {
+        PostProcess {
+            entry_point_renames: renames
+                .iter()
+                .map(|&(from, to)| (from.to_owned(), to.to_owned()))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renames_entry_points_keeping_the_interface() {
+        let mut module = test_module(
+            (1, 3),
+            10,
+            vec![
+                entry_point(ExecutionModel::Vertex, 1, "main_vs"),
+                entry_point(ExecutionModel::Fragment, 2, "main_fs"),
+            ],
+        );
+        renames(&[("main_vs", "a_much_longer_vertex_name")])
+            .apply(&mut module)
+            .unwrap();
+        let entry_points = module.entry_points();
+        assert_eq!(entry_points[0].name, "a_much_longer_vertex_name");
+        assert_eq!(entry_points[0].interface, vec![9]);
+        assert_eq!(entry_points[1].name, "main_fs");
+    }
+
+    #[test]
+    fn rejects_renames_that_clash() {
+        let mut module = test_module(
+            (1, 3),
+            10,
+            vec![
+                entry_point(ExecutionModel::Fragment, 1, "a"),
+                entry_point(ExecutionModel::Fragment, 2, "b"),
+                entry_point(ExecutionModel::Vertex, 3, "c"),
+            ],
+        );
+        assert!(matches!(
+            renames(&[("a", "b")]).apply(&mut module.clone()),
+            Err(SpirvError::Validation(_))
+        ));
+        // The same name is fine for a different execution model
+        renames(&[("c", "a")]).apply(&mut module).unwrap();
+    }
+}
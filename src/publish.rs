@@ -0,0 +1,451 @@
+//! Atomic publication of compiled shaders.
+//!
+//! Every compile is post-processed into a staging directory, which is then
+//! renamed to a numbered generation directory. A pointer file naming the
+//! current generation is replaced with a rename, so readers always see a
+//! complete set of modules from a single compile. Earlier generations stay on
+//! disk so the pointer can be moved back to one of them.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(feature = "compiler")]
+use {
+    crate::manifest::{MANIFEST_FILE, Manifest},
+    parking_lot::Mutex,
+    std::fs::{File, TryLockError},
+    std::io,
+    std::sync::atomic::{AtomicU64, Ordering},
+    std::time::{SystemTime, UNIX_EPOCH},
+};
+
+/// File in the publish root holding the name of the current generation.
+const POINTER_FILE: &str = "current";
+/// File in the publish root locked while a generation is published or checked out.
+#[cfg(feature = "compiler")]
+const ROOT_LOCK_FILE: &str = ".publish.lock";
+/// Prefix of staging directories, followed by the process id and a counter.
+#[cfg(feature = "compiler")]
+const STAGING_PREFIX: &str = ".staging";
+/// Extension of the file locked while a staging directory is in use.
+#[cfg(feature = "compiler")]
+const STAGING_LOCK_EXTENSION: &str = "lock";
+#[cfg(feature = "compiler")]
+const GENERATION_PREFIX: &str = "gen-";
+/// Metadata file written into every generation directory.
+const METADATA_FILE: &str = "generation.json";
+
+/// Distinguishes the staging directories of one process.
+#[cfg(feature = "compiler")]
+static NEXT_STAGING: AtomicU64 = AtomicU64::new(0);
+
+/// Metadata of a published shader generation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationInfo {
+    /// Generation number, increasing with every successful compile.
+    pub generation: u64,
+    /// Seconds since the Unix epoch when the generation was published.
+    pub published_at: u64,
+    /// SPIR-V target the shaders were compiled for.
+    pub target: String,
+    /// Shader files in the generation.
+    pub files: Vec<String>,
+    /// Identifies the compile that produced the generation. Targets and
+    /// permutations built by the same compile share it, while their generation
+    /// numbers may differ.
+    #[serde(default)]
+    pub compile_id: u64,
+    /// Shader crate features the shaders were built with.
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+/// Publishes generations of compiled shaders under a root directory.
+#[cfg(feature = "compiler")]
+pub(crate) struct Publisher {
+    root: PathBuf,
+    retained: usize,
+    /// Serialises publishing with moving the pointer from another thread; the
+    /// root lock file does the same across processes.
+    lock: Mutex<()>,
+}
+
+#[cfg(feature = "compiler")]
+impl Publisher {
+    /// Creates a publisher keeping the newest `retained` generations on disk.
+    pub fn new(root: impl Into<PathBuf>, retained: usize) -> Self {
+        Self {
+            root: root.into(),
+            retained: retained.max(1),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Creates an empty staging directory for the next generation.
+    ///
+    /// Every call gets its own directory, so compiles in other threads or
+    /// processes can stage at the same time. Staging directories left behind
+    /// by a process that exited mid-compile are removed first.
+    pub fn begin(&self) -> io::Result<Staging> {
+        let _guard = self.lock.lock();
+        fs::create_dir_all(&self.root)?;
+        self.sweep_staging()?;
+
+        let name = format!(
+            "{STAGING_PREFIX}-{}-{}",
+            std::process::id(),
+            NEXT_STAGING.fetch_add(1, Ordering::Relaxed)
+        );
+        let dir = self.root.join(&name);
+        let lock_path = dir.with_extension(STAGING_LOCK_EXTENSION);
+        let lock = File::create(&lock_path)?;
+        lock.lock()?;
+        let staging = Staging {
+            dir,
+            lock_path,
+            lock: Some(lock),
+        };
+        fs::create_dir(&staging.dir)?;
+        Ok(staging)
+    }
+
+    /// Removes staging directories whose lock no one holds.
+    fn sweep_staging(&self) -> io::Result<()> {
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let is_staging = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(STAGING_PREFIX));
+            if !is_staging || !path.is_dir() {
+                continue;
+            }
+            let lock_path = path.with_extension(STAGING_LOCK_EXTENSION);
+            let lock = match File::open(&lock_path) {
+                Ok(lock) => Some(lock),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+                Err(e) => return Err(e),
+            };
+            if let Some(lock) = &lock {
+                match lock.try_lock() {
+                    Ok(()) => {}
+                    Err(TryLockError::WouldBlock) => continue,
+                    Err(TryLockError::Error(e)) => return Err(e),
+                }
+            }
+            fs::remove_dir_all(&path)?;
+            drop(lock);
+            let _ = fs::remove_file(&lock_path);
+        }
+        Ok(())
+    }
+
+    /// Promotes the staging directory to a new generation and makes it current.
+    ///
+    /// `compile_id` is recorded in the generation's metadata; see [`compile_id`].
+    pub fn publish(
+        &self,
+        staging: Staging,
+        target: &str,
+        features: &[String],
+        compile_id: u64,
+    ) -> io::Result<GenerationInfo> {
+        let _guard = self.lock.lock();
+        let _root_lock = self.lock_root()?;
+        let dir = staging.path();
+        let generations = self.generation_dirs()?;
+        let generation = generations.last().map_or(1, |(number, _)| number + 1);
+
+        let mut files = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            if let Some(name) = entry?.file_name().to_str() {
+                files.push(name.to_string());
+            }
+        }
+        files.sort();
+        let info = GenerationInfo {
+            generation,
+            published_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+            target: target.to_string(),
+            files,
+            compile_id,
+            features: features.to_vec(),
+        };
+        let metadata = serde_json::to_string_pretty(&info).map_err(io::Error::other)?;
+        fs::write(dir.join(METADATA_FILE), metadata)?;
+        let manifest = Manifest::describe(&info, dir)
+            .and_then(|manifest| Ok(serde_json::to_string_pretty(&manifest)?))
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        fs::write(dir.join(MANIFEST_FILE), manifest)?;
+
+        fs::rename(dir, self.root.join(generation_name(generation)))?;
+        self.point_to(generation)?;
+
+        // A reader may still be loading from an older generation, so a failed
+        // removal is left for the next publish to retry.
+        let stale = (generations.len() + 1).saturating_sub(self.retained);
+        for (_, path) in generations.into_iter().take(stale) {
+            let _ = fs::remove_dir_all(path);
+        }
+        Ok(info)
+    }
+
+    /// Makes an existing generation current again.
+    pub fn checkout(&self, generation: u64) -> io::Result<()> {
+        let _guard = self.lock.lock();
+        let _root_lock = self.lock_root()?;
+        if !self.root.join(generation_name(generation)).is_dir() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("shader generation {generation} is not on disk"),
+            ));
+        }
+        self.point_to(generation)?;
+        Ok(())
+    }
+
+    /// Number of the generation published by compile `compile_id`, if it is still on disk.
+    pub fn find_compile(&self, compile_id: u64) -> io::Result<Option<u64>> {
+        Ok(self
+            .generations()?
+            .into_iter()
+            .find(|info| info.compile_id == compile_id)
+            .map(|info| info.generation))
+    }
+
+    /// Number of the current generation, if one has been published.
+    pub fn current(&self) -> Option<u64> {
+        let name = fs::read_to_string(self.root.join(POINTER_FILE)).ok()?;
+        parse_generation(name.trim())
+    }
+
+    /// Metadata of every generation on disk, oldest first.
+    pub fn generations(&self) -> io::Result<Vec<GenerationInfo>> {
+        let mut generations = Vec::new();
+        for (_, path) in self.generation_dirs()? {
+            let metadata = fs::read_to_string(path.join(METADATA_FILE))?;
+            generations.push(serde_json::from_str(&metadata).map_err(io::Error::other)?);
+        }
+        Ok(generations)
+    }
+
+    /// Locks the publish root until the returned file is dropped, so another
+    /// process cannot pick the same generation number or move the pointer
+    /// halfway through.
+    fn lock_root(&self) -> io::Result<File> {
+        fs::create_dir_all(&self.root)?;
+        let lock = File::create(self.root.join(ROOT_LOCK_FILE))?;
+        lock.lock()?;
+        Ok(lock)
+    }
+
+    /// Replaces the pointer file with one naming `generation`.
+    fn point_to(&self, generation: u64) -> io::Result<()> {
+        let pointer_tmp = self.root.join(format!("{POINTER_FILE}.tmp"));
+        fs::write(&pointer_tmp, generation_name(generation))?;
+        fs::rename(&pointer_tmp, self.root.join(POINTER_FILE))
+    }
+
+    /// Existing generation directories, oldest first.
+    fn generation_dirs(&self) -> io::Result<Vec<(u64, PathBuf)>> {
+        if !self.root.exists() {
+            return Ok(Vec::new());
+        }
+        let mut generations = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let number = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(parse_generation);
+            if let Some(number) = number
+                && path.is_dir()
+            {
+                generations.push((number, path));
+            }
+        }
+        generations.sort();
+        Ok(generations)
+    }
+}
+
+/// A staging directory, removed when dropped unless it was published.
+#[cfg(feature = "compiler")]
+pub(crate) struct Staging {
+    dir: PathBuf,
+    lock_path: PathBuf,
+    /// Held while the directory is in use, so sweeps by other publishers skip it.
+    lock: Option<File>,
+}
+
+#[cfg(feature = "compiler")]
+impl Staging {
+    /// Directory to write the generation's files into.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+}
+
+#[cfg(feature = "compiler")]
+impl Drop for Staging {
+    fn drop(&mut self) {
+        // Already gone once renamed to a generation
+        let _ = fs::remove_dir_all(&self.dir);
+        drop(self.lock.take());
+        let _ = fs::remove_file(&self.lock_path);
+    }
+}
+
+/// Directory of the current generation under `root`, if one has been published.
+pub(crate) fn current_generation(root: &Path) -> Option<PathBuf> {
+    let name = fs::read_to_string(root.join(POINTER_FILE)).ok()?;
+    let dir = root.join(name.trim());
+    dir.is_dir().then_some(dir)
+}
+
+/// Metadata of the generation in `dir`, if it is a published generation.
+pub(crate) fn generation_info(dir: &Path) -> Option<GenerationInfo> {
+    let metadata = fs::read_to_string(dir.join(METADATA_FILE)).ok()?;
+    serde_json::from_str(&metadata).ok()
+}
+
+/// Returns a new id for a compile, shared by every generation it publishes.
+#[cfg(feature = "compiler")]
+pub(crate) fn compile_id() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+#[cfg(feature = "compiler")]
+fn generation_name(generation: u64) -> String {
+    format!("{GENERATION_PREFIX}{generation:06}")
+}
+
+#[cfg(feature = "compiler")]
+fn parse_generation(name: &str) -> Option<u64> {
+    name.strip_prefix(GENERATION_PREFIX)?.parse().ok()
+}
+
+#[cfg(all(test, feature = "compiler"))]
+mod tests {
+    use super::*;
+
+    /// An empty publish root for one test.
+    fn root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "rust-gpu-hotreload-publish-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = fs::remove_dir_all(&root);
+        root
+    }
+
+    fn staging_dirs(root: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(root)
+            .unwrap()
+            .filter_map(|entry| entry.unwrap().file_name().into_string().ok())
+            .filter(|name| name.starts_with(STAGING_PREFIX))
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn stages_each_compile_separately() {
+        let root = root("separate");
+        let publisher = Publisher::new(&root, 2);
+        let first = publisher.begin().unwrap();
+        let second = publisher.begin().unwrap();
+        assert_ne!(first.path(), second.path());
+        fs::write(first.path().join("a.txt"), []).unwrap();
+
+        let info = publisher
+            .publish(first, "spirv-unknown-vulkan1.2", &["debug".to_string()], 7)
+            .unwrap();
+        assert_eq!(info.generation, 1);
+        assert_eq!(info.files, ["a.txt"]);
+        assert_eq!(generation_info(&root.join("gen-000001")), Some(info));
+        assert!(second.path().is_dir());
+
+        drop(second);
+        assert!(staging_dirs(&root).is_empty());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn sweeps_abandoned_staging() {
+        let root = root("sweep");
+        let publisher = Publisher::new(&root, 2);
+        let in_use = publisher.begin().unwrap();
+        // Left by an older version, and by a process that exited mid-compile
+        fs::create_dir(root.join(STAGING_PREFIX)).unwrap();
+        fs::create_dir(root.join(format!("{STAGING_PREFIX}-0-0"))).unwrap();
+        fs::write(root.join(format!("{STAGING_PREFIX}-0-0.lock")), []).unwrap();
+
+        let next = publisher.begin().unwrap();
+        let mut expected: Vec<String> = [in_use.path(), next.path()]
+            .into_iter()
+            .flat_map(|dir| {
+                let name = dir.file_name().unwrap().to_str().unwrap();
+                [name.to_string(), format!("{name}.lock")]
+            })
+            .collect();
+        expected.sort();
+        assert_eq!(staging_dirs(&root), expected);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn checks_out_kept_generations() {
+        let root = root("checkout");
+        let publisher = Publisher::new(&root, 2);
+        for compile_id in [10, 20, 30] {
+            let staging = publisher.begin().unwrap();
+            publisher
+                .publish(staging, "target", &[], compile_id)
+                .unwrap();
+        }
+        assert_eq!(publisher.current(), Some(3));
+        let kept: Vec<u64> = publisher
+            .generations()
+            .unwrap()
+            .iter()
+            .map(|info| info.generation)
+            .collect();
+        assert_eq!(kept, [2, 3]);
+        assert_eq!(publisher.find_compile(20).unwrap(), Some(2));
+        assert_eq!(publisher.find_compile(10).unwrap(), None);
+
+        publisher.checkout(2).unwrap();
+        assert_eq!(publisher.current(), Some(2));
+        assert_eq!(current_generation(&root), Some(root.join("gen-000002")));
+        assert!(publisher.checkout(1).is_err());
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn waits_for_other_publishers() {
+        let root = root("lock");
+        let publisher = Publisher::new(&root, 2);
+        let staging = publisher.begin().unwrap();
+        // Another process publishing into the same root
+        let other = File::create(root.join(ROOT_LOCK_FILE)).unwrap();
+        other.lock().unwrap();
+
+        std::thread::scope(|scope| {
+            let publish = scope.spawn(|| publisher.publish(staging, "target", &[], 1));
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            assert!(!publish.is_finished());
+            assert_eq!(publisher.current(), None);
+
+            drop(other);
+            assert_eq!(publish.join().unwrap().unwrap().generation, 1);
+        });
+        assert_eq!(publisher.current(), Some(1));
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,61 @@
+//! Metadata read from compiled modules, in a form that serialises to JSON.
+
+use crate::spv::{Module, SpirvError};
+use serde::{Deserialize, Serialize};
+
+/// What a compiled module declares, as recorded in shader packs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleInfo {
+    /// SPIR-V version from the module header, as `(major, minor)`.
+    pub spirv_version: (u8, u8),
+    /// Capabilities declared with `OpCapability`, by name.
+    pub capabilities: Vec<String>,
+    /// Extensions declared with `OpExtension`.
+    pub extensions: Vec<String>,
+    pub entry_points: Vec<EntryPointInfo>,
+}
+
+/// An entry point of a compiled module.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryPointInfo {
+    pub name: String,
+    /// Execution model by name, e.g. `Vertex` or `GLCompute`.
+    pub execution_model: String,
+}
+
+impl ModuleInfo {
+    /// Reads the metadata of a SPIR-V binary in either byte order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid SPIR-V module.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SpirvError> {
+        Ok(Self::from_module(&Module::from_bytes(bytes)?))
+    }
+
+    /// Reads the metadata of a decoded module.
+    pub fn from_module(module: &Module) -> Self {
+        Self {
+            spirv_version: module.header.version(),
+            capabilities: module
+                .capabilities()
+                .iter()
+                .map(|capability| format!("{:?}", capability))
+                .collect(),
+            extensions: module.extensions(),
+            entry_points: module
+                .entry_points()
+                .into_iter()
+                .map(|entry_point| EntryPointInfo {
+                    name: entry_point.name,
+                    execution_model: format!("{:?}", entry_point.execution_model),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// BLAKE3 hash of `bytes`, hex encoded.
+pub(crate) fn content_hash(bytes: &[u8]) -> String {
+    blake3::hash(bytes).to_hex().to_string()
+}
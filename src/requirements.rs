@@ -0,0 +1,417 @@
+//! Extension and SPIR-V version requirements of capabilities.
+//!
+//! Only capabilities that need an extension or a newer SPIR-V version are
+//! listed; anything else is available in every SPIR-V 1.0 module.
+
+use spirv::Capability;
+
+/// What a module must declare, or target, to use a capability.
+#[derive(Debug)]
+pub struct CapabilityRequirement {
+    pub capability: Capability,
+    /// Any one of these extensions enables the capability.
+    pub extensions: &'static [&'static str],
+    /// SPIR-V version from which the capability is core and needs no extension.
+    pub core_since: Option<(u8, u8)>,
+    /// Lowest SPIR-V version the capability can be used with at all.
+    pub min_version: (u8, u8),
+}
+
+impl CapabilityRequirement {
+    /// Returns true if a module with this version and these extensions may use the capability.
+    pub fn is_satisfied(&self, version: (u8, u8), extensions: &[impl AsRef<str>]) -> bool {
+        if version < self.min_version {
+            return false;
+        }
+        if self.core_since.is_some_and(|core| version >= core) {
+            return true;
+        }
+        self.extensions.is_empty()
+            || extensions
+                .iter()
+                .any(|ext| self.extensions.contains(&ext.as_ref()))
+    }
+}
+
+/// Looks up the requirement for a capability, if it has one.
+pub fn requirement(capability: Capability) -> Option<&'static CapabilityRequirement> {
+    REQUIREMENTS
+        .iter()
+        .find(|requirement| requirement.capability == capability)
+}
+
+macro_rules! req {
+    ($cap:ident, [$($ext:literal),*], $core:expr) => {
+        req!($cap, [$($ext),*], $core, (1, 0))
+    };
+    ($cap:ident, [$($ext:literal),*], $core:expr, $min:expr) => {
+        CapabilityRequirement {
+            capability: Capability::$cap,
+            extensions: &[$($ext),*],
+            core_since: $core,
+            min_version: $min,
+        }
+    };
+}
+
+static REQUIREMENTS: &[CapabilityRequirement] = &[
+    // SPIR-V 1.3
+    req!(GroupNonUniform, [], None, (1, 3)),
+    req!(GroupNonUniformVote, [], None, (1, 3)),
+    req!(GroupNonUniformArithmetic, [], None, (1, 3)),
+    req!(GroupNonUniformBallot, [], None, (1, 3)),
+    req!(GroupNonUniformShuffle, [], None, (1, 3)),
+    req!(GroupNonUniformShuffleRelative, [], None, (1, 3)),
+    req!(GroupNonUniformClustered, [], None, (1, 3)),
+    req!(GroupNonUniformQuad, [], None, (1, 3)),
+    req!(
+        StorageBuffer16BitAccess,
+        ["SPV_KHR_16bit_storage"],
+        Some((1, 3))
+    ),
+    req!(
+        UniformAndStorageBuffer16BitAccess,
+        ["SPV_KHR_16bit_storage"],
+        Some((1, 3))
+    ),
+    req!(
+        StoragePushConstant16,
+        ["SPV_KHR_16bit_storage"],
+        Some((1, 3))
+    ),
+    req!(
+        StorageInputOutput16,
+        ["SPV_KHR_16bit_storage"],
+        Some((1, 3))
+    ),
+    req!(
+        DrawParameters,
+        ["SPV_KHR_shader_draw_parameters"],
+        Some((1, 3))
+    ),
+    req!(MultiView, ["SPV_KHR_multiview"], Some((1, 3))),
+    req!(DeviceGroup, ["SPV_KHR_device_group"], Some((1, 3))),
+    req!(
+        VariablePointers,
+        ["SPV_KHR_variable_pointers"],
+        Some((1, 3))
+    ),
+    req!(
+        VariablePointersStorageBuffer,
+        ["SPV_KHR_variable_pointers"],
+        Some((1, 3))
+    ),
+    // SPIR-V 1.4
+    req!(DenormPreserve, ["SPV_KHR_float_controls"], Some((1, 4))),
+    req!(DenormFlushToZero, ["SPV_KHR_float_controls"], Some((1, 4))),
+    req!(
+        SignedZeroInfNanPreserve,
+        ["SPV_KHR_float_controls"],
+        Some((1, 4))
+    ),
+    req!(RoundingModeRTE, ["SPV_KHR_float_controls"], Some((1, 4))),
+    req!(RoundingModeRTZ, ["SPV_KHR_float_controls"], Some((1, 4))),
+    // SPIR-V 1.5
+    req!(
+        StorageBuffer8BitAccess,
+        ["SPV_KHR_8bit_storage"],
+        Some((1, 5))
+    ),
+    req!(
+        UniformAndStorageBuffer8BitAccess,
+        ["SPV_KHR_8bit_storage"],
+        Some((1, 5))
+    ),
+    req!(StoragePushConstant8, ["SPV_KHR_8bit_storage"], Some((1, 5))),
+    req!(
+        ShaderNonUniform,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        RuntimeDescriptorArray,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        InputAttachmentArrayDynamicIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        UniformTexelBufferArrayDynamicIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        StorageTexelBufferArrayDynamicIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        UniformBufferArrayNonUniformIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        SampledImageArrayNonUniformIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        StorageBufferArrayNonUniformIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        StorageImageArrayNonUniformIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        InputAttachmentArrayNonUniformIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        UniformTexelBufferArrayNonUniformIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        StorageTexelBufferArrayNonUniformIndexing,
+        ["SPV_EXT_descriptor_indexing"],
+        Some((1, 5))
+    ),
+    req!(
+        VulkanMemoryModel,
+        ["SPV_KHR_vulkan_memory_model"],
+        Some((1, 5))
+    ),
+    req!(
+        VulkanMemoryModelDeviceScope,
+        ["SPV_KHR_vulkan_memory_model"],
+        Some((1, 5))
+    ),
+    req!(
+        PhysicalStorageBufferAddresses,
+        [
+            "SPV_KHR_physical_storage_buffer",
+            "SPV_EXT_physical_storage_buffer"
+        ],
+        Some((1, 5))
+    ),
+    req!(ShaderViewportIndex, [], None, (1, 5)),
+    req!(ShaderLayer, [], None, (1, 5)),
+    // SPIR-V 1.6
+    req!(
+        DemoteToHelperInvocation,
+        ["SPV_EXT_demote_to_helper_invocation"],
+        Some((1, 6))
+    ),
+    req!(
+        DotProductInputAll,
+        ["SPV_KHR_integer_dot_product"],
+        Some((1, 6))
+    ),
+    req!(
+        DotProductInput4x8Bit,
+        ["SPV_KHR_integer_dot_product"],
+        Some((1, 6))
+    ),
+    req!(
+        DotProductInput4x8BitPacked,
+        ["SPV_KHR_integer_dot_product"],
+        Some((1, 6))
+    ),
+    req!(DotProduct, ["SPV_KHR_integer_dot_product"], Some((1, 6))),
+    // Extension only
+    req!(RayTracingKHR, ["SPV_KHR_ray_tracing"], None, (1, 4)),
+    req!(RayQueryKHR, ["SPV_KHR_ray_query"], None, (1, 4)),
+    req!(
+        RayTraversalPrimitiveCullingKHR,
+        ["SPV_KHR_ray_query", "SPV_KHR_ray_tracing"],
+        None,
+        (1, 4)
+    ),
+    req!(
+        RayTracingPositionFetchKHR,
+        ["SPV_KHR_ray_tracing_position_fetch"],
+        None,
+        (1, 4)
+    ),
+    req!(
+        RayQueryPositionFetchKHR,
+        ["SPV_KHR_ray_tracing_position_fetch"],
+        None,
+        (1, 4)
+    ),
+    req!(RayCullMaskKHR, ["SPV_KHR_ray_cull_mask"], None, (1, 4)),
+    req!(MeshShadingEXT, ["SPV_EXT_mesh_shader"], None, (1, 4)),
+    req!(MeshShadingNV, ["SPV_NV_mesh_shader"], None),
+    req!(SubgroupBallotKHR, ["SPV_KHR_shader_ballot"], None),
+    req!(SubgroupVoteKHR, ["SPV_KHR_subgroup_vote"], None),
+    req!(
+        FragmentShadingRateKHR,
+        ["SPV_KHR_fragment_shading_rate"],
+        None
+    ),
+    req!(
+        FragmentBarycentricKHR,
+        [
+            "SPV_KHR_fragment_shader_barycentric",
+            "SPV_NV_fragment_shader_barycentric"
+        ],
+        None
+    ),
+    req!(
+        FragmentDensityEXT,
+        ["SPV_EXT_fragment_invocation_density", "SPV_NV_shading_rate"],
+        None
+    ),
+    req!(
+        FragmentFullyCoveredEXT,
+        ["SPV_EXT_fragment_fully_covered"],
+        None
+    ),
+    req!(
+        FragmentShaderSampleInterlockEXT,
+        ["SPV_EXT_fragment_shader_interlock"],
+        None
+    ),
+    req!(
+        FragmentShaderPixelInterlockEXT,
+        ["SPV_EXT_fragment_shader_interlock"],
+        None
+    ),
+    req!(
+        FragmentShaderShadingRateInterlockEXT,
+        ["SPV_EXT_fragment_shader_interlock"],
+        None
+    ),
+    req!(
+        ShaderViewportIndexLayerEXT,
+        ["SPV_EXT_shader_viewport_index_layer"],
+        None
+    ),
+    req!(StencilExportEXT, ["SPV_EXT_shader_stencil_export"], None),
+    req!(
+        SampleMaskPostDepthCoverage,
+        ["SPV_KHR_post_depth_coverage"],
+        None
+    ),
+    req!(Int64ImageEXT, ["SPV_EXT_shader_image_int64"], None),
+    req!(ShaderClockKHR, ["SPV_KHR_shader_clock"], None),
+    req!(
+        AtomicFloat16AddEXT,
+        ["SPV_EXT_shader_atomic_float16_add"],
+        None
+    ),
+    req!(
+        AtomicFloat32AddEXT,
+        ["SPV_EXT_shader_atomic_float_add"],
+        None
+    ),
+    req!(
+        AtomicFloat64AddEXT,
+        ["SPV_EXT_shader_atomic_float_add"],
+        None
+    ),
+    req!(
+        AtomicFloat16MinMaxEXT,
+        ["SPV_EXT_shader_atomic_float_min_max"],
+        None
+    ),
+    req!(
+        AtomicFloat32MinMaxEXT,
+        ["SPV_EXT_shader_atomic_float_min_max"],
+        None
+    ),
+    req!(
+        AtomicFloat64MinMaxEXT,
+        ["SPV_EXT_shader_atomic_float_min_max"],
+        None
+    ),
+    req!(
+        WorkgroupMemoryExplicitLayoutKHR,
+        ["SPV_KHR_workgroup_memory_explicit_layout"],
+        None,
+        (1, 4)
+    ),
+    req!(
+        WorkgroupMemoryExplicitLayout8BitAccessKHR,
+        ["SPV_KHR_workgroup_memory_explicit_layout"],
+        None,
+        (1, 4)
+    ),
+    req!(
+        WorkgroupMemoryExplicitLayout16BitAccessKHR,
+        ["SPV_KHR_workgroup_memory_explicit_layout"],
+        None,
+        (1, 4)
+    ),
+    req!(
+        GroupNonUniformRotateKHR,
+        ["SPV_KHR_subgroup_rotate"],
+        None,
+        (1, 3)
+    ),
+    req!(
+        ComputeDerivativeGroupQuadsNV,
+        ["SPV_NV_compute_shader_derivatives"],
+        None
+    ),
+    req!(
+        ComputeDerivativeGroupLinearNV,
+        ["SPV_NV_compute_shader_derivatives"],
+        None
+    ),
+    req!(ImageFootprintNV, ["SPV_NV_shader_image_footprint"], None),
+    req!(CooperativeMatrixKHR, ["SPV_KHR_cooperative_matrix"], None),
+    req!(ExpectAssumeKHR, ["SPV_KHR_expect_assume"], None),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NONE: &[&str] = &[];
+
+    #[test]
+    fn checks_minimum_version() {
+        let requirement = requirement(Capability::GroupNonUniform).unwrap();
+        assert!(!requirement.is_satisfied((1, 2), NONE));
+        assert!(requirement.is_satisfied((1, 3), NONE));
+    }
+
+    #[test]
+    fn accepts_extension_or_core_version() {
+        let requirement = requirement(Capability::VulkanMemoryModel).unwrap();
+        assert!(!requirement.is_satisfied((1, 4), NONE));
+        assert!(requirement.is_satisfied((1, 4), &["SPV_KHR_vulkan_memory_model"]));
+        assert!(requirement.is_satisfied((1, 5), NONE));
+    }
+
+    #[test]
+    fn accepts_any_listed_extension() {
+        let requirement = requirement(Capability::FragmentBarycentricKHR).unwrap();
+        assert!(!requirement.is_satisfied((1, 6), NONE));
+        assert!(requirement.is_satisfied((1, 0), &["SPV_NV_fragment_shader_barycentric"]));
+        // An extension is not enough below the minimum version
+        let ray_query = super::requirement(Capability::RayQueryKHR).unwrap();
+        assert!(!ray_query.is_satisfied((1, 3), &["SPV_KHR_ray_query"]));
+    }
+
+    #[test]
+    fn lists_capabilities_once() {
+        let mut seen = std::collections::HashSet::new();
+        for requirement in REQUIREMENTS {
+            assert!(
+                seen.insert(requirement.capability),
+                "{:?}",
+                requirement.capability
+            );
+        }
+        assert!(super::requirement(Capability::Shader).is_none());
+    }
+}
@@ -0,0 +1,466 @@
+//! Helper program that runs spirv-builder for each compile.
+//!
+//! spirv-builder starts cargo with the environment of the process calling it.
+//! Calling it from a small runner, built into the target directory on first
+//! use, lets every compile set its own environment variables without touching
+//! the application's environment, which other threads may be reading. The
+//! runner's stderr is piped, so each compile also gets its own diagnostics.
+//! It is built against the spirv-builder revision in the application's
+//! `Cargo.lock`, the one this crate was compiled with.
+
+pub(crate) mod protocol;
+
+use crate::compile::{CompileConfig, try_spirv_target_dir};
+use parking_lot::Mutex;
+use protocol::{BuildRequest, BuildResponse};
+use spirv_builder::ModuleResult;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Directory under the workspace target directory the runner is built in.
+const RUNNER_DIR: &str = "rust-gpu-hotreload-runner";
+const RUNNER_NAME: &str = "rust-gpu-hotreload-runner";
+const RUNNER_MAIN: &str = include_str!("runner/main.rs");
+const RUNNER_PROTOCOL: &str = include_str!("runner/protocol.rs");
+
+/// Executable of the runner, once built by this process.
+static RUNNER: Mutex<Option<PathBuf>> = Mutex::new(None);
+/// Distinguishes the response files of concurrent builds.
+static NEXT_RESPONSE: AtomicU64 = AtomicU64::new(0);
+
+/// Compiles the shader crate of `config` in the runner.
+///
+/// Returns the outcome with the diagnostics the build wrote to stderr, which
+/// are also passed on to this process's stderr. The outcome is an error if the
+/// runner cannot be built or started, or the compile fails.
+pub(crate) fn build(
+    config: &CompileConfig,
+) -> (Result<ModuleResult, Box<dyn std::error::Error>>, String) {
+    let mut diagnostics = String::new();
+    let result = run(config, &mut diagnostics);
+    (result, diagnostics)
+}
+
+fn run(
+    config: &CompileConfig,
+    diagnostics: &mut String,
+) -> Result<ModuleResult, Box<dyn std::error::Error>> {
+    let runner = runner(config)?;
+    let response_path = runner.with_file_name(format!(
+        "response-{}-{}.json",
+        std::process::id(),
+        NEXT_RESPONSE.fetch_add(1, Ordering::Relaxed)
+    ));
+    let options = &config.options;
+    let request = BuildRequest {
+        crate_path: config.shader_crate_path.clone(),
+        target: config.target.clone(),
+        target_dir: config
+            .target_dir
+            .is_some()
+            .then(|| config.spirv_target_dir()),
+        capabilities: config
+            .capabilities
+            .iter()
+            .map(|&capability| capability as u32)
+            .collect(),
+        extensions: config.extensions.clone(),
+        multimodule: config.multimodule,
+        features: config.features.clone(),
+        spirv_metadata: options.metadata(),
+        shader_panic_strategy: options.panic_strategy(),
+        relax_struct_store: options.relax_struct_store,
+        relax_logical_pointer: options.relax_logical_pointer,
+        relax_block_layout: options.relax_block_layout,
+        uniform_buffer_standard_layout: options.uniform_buffer_standard_layout,
+        scalar_block_layout: options.scalar_block_layout,
+        skip_block_layout: options.skip_block_layout,
+        preserve_bindings: options.preserve_bindings,
+        extra_args: options.extra_args.clone(),
+        response_path: response_path.clone(),
+    };
+
+    let mut child = Command::new(&runner)
+        .envs(options.build_env())
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to start {}: {}", runner.display(), e))?;
+
+    // Pass diagnostics on line by line while keeping a copy to recognise
+    let stderr = BufReader::new(child.stderr.take().expect("stderr is piped"));
+    let reader = std::thread::spawn(move || {
+        let mut stderr = stderr;
+        let mut copy = String::new();
+        let mut line = Vec::new();
+        while stderr
+            .read_until(b'\n', &mut line)
+            .is_ok_and(|read| read > 0)
+        {
+            let text = String::from_utf8_lossy(&line);
+            eprint!("{}", text);
+            copy.push_str(&text);
+            line.clear();
+        }
+        copy
+    });
+    let sent = serde_json::to_writer(child.stdin.take().expect("stdin is piped"), &request);
+    let status = child.wait();
+    *diagnostics = reader.join().unwrap_or_default();
+    let status = status?;
+    sent?;
+
+    let response = fs::read(&response_path);
+    let _ = fs::remove_file(&response_path);
+    let Ok(response) = response else {
+        return Err(format!("build runner exited with {}", status).into());
+    };
+    match serde_json::from_slice(&response)? {
+        BuildResponse::SingleModule(path) => Ok(ModuleResult::SingleModule(path)),
+        BuildResponse::MultiModule(modules) => Ok(ModuleResult::MultiModule(modules)),
+        BuildResponse::Failed(error) => Err(error.into()),
+    }
+}
+
+/// Builds the runner on first use and returns the path of its executable.
+///
+/// The runner is built next to the shader builds if `config` has an absolute
+/// target directory, otherwise in the workspace target directory. Fails if no
+/// `Cargo.lock` says which spirv-builder this crate was built with, rather
+/// than building against a branch that may not match the toolchain.
+fn runner(config: &CompileConfig) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let mut runner = RUNNER.lock();
+    if let Some(path) = runner.as_ref() {
+        return Ok(path.clone());
+    }
+
+    let dir = match config.target_dir.as_deref() {
+        Some(target_dir) if target_dir.is_absolute() => target_dir.join(RUNNER_DIR),
+        _ => try_spirv_target_dir(Some(Path::new(RUNNER_DIR)))?,
+    };
+    // Build with the spirv-builder revision and dependencies the application
+    // resolved, so the runner needs no network and matches this crate's codegen
+    let lockfiles = host_lockfiles(config);
+    let locked = lockfiles.iter().find_map(|path| {
+        let lock = fs::read_to_string(path).ok()?;
+        let spec = locked_spirv_builder(&lock)?;
+        Some((lock, spec))
+    });
+    let Some((lock, spirv_builder)) = locked else {
+        let searched: Vec<String> = lockfiles
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        return Err(format!(
+            "cannot build the build runner: no Cargo.lock locks the spirv-builder this crate \
+             was built with from git or crates.io (searched: {}); run the application from \
+             its workspace or set CARGO_WORKSPACE_DIR",
+            if searched.is_empty() {
+                "none found".to_string()
+            } else {
+                searched.join(", ")
+            }
+        )
+        .into());
+    };
+    write_if_changed(&dir.join("Cargo.lock"), &lock)?;
+    write_if_changed(&dir.join("Cargo.toml"), &manifest(&spirv_builder))?;
+    write_if_changed(&dir.join("src").join("main.rs"), RUNNER_MAIN)?;
+    write_if_changed(&dir.join("src").join("protocol.rs"), RUNNER_PROTOCOL)?;
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command
+        .args(["build", "--release", "--quiet", "--manifest-path"])
+        .arg(dir.join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(dir.join("target"));
+    // Settings cargo passes to a build script calling this are not for the runner
+    for (key, _) in std::env::vars_os() {
+        if let Some(key) = key.to_str()
+            && (key.starts_with("CARGO_FEATURE_")
+                || key.starts_with("CARGO_CFG_")
+                || key == "CARGO_ENCODED_RUSTFLAGS"
+                || key == "CARGO_TARGET_DIR")
+        {
+            command.env_remove(key);
+        }
+    }
+    let status = command
+        .status()
+        .map_err(|e| format!("failed to run cargo: {}", e))?;
+    if !status.success() {
+        return Err(format!("failed to build the build runner in {}", dir.display()).into());
+    }
+
+    let path = dir.join("target").join("release").join(format!(
+        "{}{}",
+        RUNNER_NAME,
+        std::env::consts::EXE_SUFFIX
+    ));
+    *runner = Some(path.clone());
+    Ok(path)
+}
+
+/// `Cargo.toml` of the runner, using the same codegen backend as this crate.
+///
+/// `spirv_builder` is the source of the spirv-builder dependency, e.g. from
+/// [`locked_spirv_builder`].
+fn manifest(spirv_builder: &str) -> String {
+    let tools = if cfg!(feature = "use-compiled-tools") {
+        "use-compiled-tools"
+    } else {
+        "use-installed-tools"
+    };
+    format!(
+        r#"[package]
+name = "{RUNNER_NAME}"
+version = "{}"
+edition = "2021"
+publish = false
+
+[workspace]
+
+[dependencies]
+spirv-builder = {{ {spirv_builder}, default-features = false, features = ["{tools}"] }}
+serde = {{ version = "1", features = ["derive"] }}
+serde_json = "1"
+"#,
+        env!("CARGO_PKG_VERSION")
+    )
+}
+
+/// Lockfiles that may have resolved this crate, nearest first.
+///
+/// Searches above the workspace cargo is running, which is unknown when the
+/// application runs outside cargo, then above the shader crate and the
+/// application's executable.
+fn host_lockfiles(config: &CompileConfig) -> Vec<PathBuf> {
+    let mut starts: Vec<PathBuf> = ["CARGO_WORKSPACE_DIR", "CARGO_MANIFEST_DIR"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .collect();
+    starts.push(
+        fs::canonicalize(&config.shader_crate_path)
+            .unwrap_or_else(|_| config.shader_crate_path.clone()),
+    );
+    starts.extend(std::env::current_exe());
+
+    let mut lockfiles = Vec::new();
+    for start in starts {
+        for path in start.ancestors().map(|dir| dir.join("Cargo.lock")) {
+            if path.is_file() && !lockfiles.contains(&path) {
+                lockfiles.push(path);
+            }
+        }
+    }
+    lockfiles
+}
+
+/// A `[[package]]` entry of a `Cargo.lock`.
+#[derive(Debug, Default)]
+struct LockedPackage {
+    name: String,
+    version: String,
+    source: Option<String>,
+    /// `name`, `name version` or `name version (source)`.
+    dependencies: Vec<String>,
+}
+
+/// Reads the package entries of a `Cargo.lock`.
+fn locked_packages(lock: &str) -> Vec<LockedPackage> {
+    let mut packages: Vec<LockedPackage> = Vec::new();
+    let mut in_dependencies = false;
+    for line in lock.lines().map(str::trim) {
+        if line == "[[package]]" {
+            packages.push(LockedPackage::default());
+            in_dependencies = false;
+            continue;
+        }
+        let Some(package) = packages.last_mut() else {
+            continue;
+        };
+        if in_dependencies {
+            if line.starts_with(']') {
+                in_dependencies = false;
+            } else if let Some(dependency) = unquote(line.trim_end_matches(',')) {
+                package.dependencies.push(dependency.to_string());
+            }
+            continue;
+        }
+        let Some((key, value)) = line.split_once(" = ") else {
+            continue;
+        };
+        match key {
+            "name" => package.name = unquote(value).unwrap_or_default().to_string(),
+            "version" => package.version = unquote(value).unwrap_or_default().to_string(),
+            "source" => package.source = unquote(value).map(str::to_string),
+            "dependencies" => in_dependencies = value == "[",
+            _ => {}
+        }
+    }
+    packages
+}
+
+fn unquote(value: &str) -> Option<&str> {
+    value.strip_prefix('"')?.strip_suffix('"')
+}
+
+/// Source of the spirv-builder this crate was resolved with in `lock`, as
+/// dependency keys for the runner's `Cargo.toml`.
+///
+/// Copying `lock` next to the manifest then pins the same revision. Returns
+/// `None` if spirv-builder is not locked, or comes from a path or a registry
+/// other than crates.io.
+fn locked_spirv_builder(lock: &str) -> Option<String> {
+    let packages = locked_packages(lock);
+    let candidates: Vec<&LockedPackage> = packages
+        .iter()
+        .filter(|p| p.name == "spirv-builder")
+        .collect();
+    let package = if let [package] = candidates[..] {
+        package
+    } else {
+        // Several versions are locked; take the one this crate depends on
+        let this = packages
+            .iter()
+            .find(|p| p.name == env!("CARGO_PKG_NAME") && p.version == env!("CARGO_PKG_VERSION"))?;
+        let mut dependency = this
+            .dependencies
+            .iter()
+            .find(|dependency| dependency.split(' ').next() == Some("spirv-builder"))?
+            .splitn(3, ' ')
+            .skip(1);
+        let version = dependency.next()?;
+        let source = dependency
+            .next()
+            .map(|source| source.trim_start_matches('(').trim_end_matches(')'));
+        candidates
+            .into_iter()
+            .find(|p| p.version == version && (source.is_none() || p.source.as_deref() == source))?
+    };
+
+    let source = package.source.as_deref()?;
+    if let Some(git) = source.strip_prefix("git+") {
+        // The locked commit follows `#`; the copied lockfile pins it
+        let (location, _commit) = git.split_once('#')?;
+        let (url, query) = location.split_once('?').unwrap_or((location, ""));
+        let mut keys = format!("git = \"{}\"", url);
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=')?;
+            if matches!(key, "branch" | "tag" | "rev") {
+                keys.push_str(&format!(", {} = \"{}\"", key, value));
+            }
+        }
+        Some(keys)
+    } else if source.contains("crates.io-index") || source.contains("index.crates.io") {
+        Some(format!("version = \"={}\"", package.version))
+    } else {
+        None
+    }
+}
+
+/// Writes `contents` unless the file has them already, so cargo does not
+/// rebuild the runner needlessly.
+fn write_if_changed(path: &Path, contents: &str) -> io::Result<()> {
+    if fs::read_to_string(path).is_ok_and(|current| current == contents) {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GIT_LOCK: &str = r#"
+version = 4
+
+[[package]]
+name = "rust-gpu-hotreload"
+version = "0.1.0"
+dependencies = [
+ "spirv-builder",
+]
+
+[[package]]
+name = "spirv-builder"
+version = "0.9.0"
+source = "git+https://github.com/Rust-GPU/rust-gpu.git?branch=main#a30bd43db45f2bfe260051f44141e5eaffcbb4b0"
+dependencies = [
+ "serde",
+]
+"#;
+
+    #[test]
+    fn finds_the_lockfile_above_the_shader_crate() {
+        let dir = std::env::temp_dir().join(format!(
+            "rust-gpu-hotreload-runner-{}-lockfile",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("shaders")).unwrap();
+        fs::write(dir.join("Cargo.lock"), GIT_LOCK).unwrap();
+
+        let config = CompileConfig::new(&dir.join("shaders"));
+        let lockfiles = host_lockfiles(&config);
+        let lockfile = fs::canonicalize(dir.join("Cargo.lock")).unwrap();
+        assert!(lockfiles.contains(&lockfile), "{lockfiles:?}");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn mirrors_a_locked_git_source() {
+        assert_eq!(
+            locked_spirv_builder(GIT_LOCK).as_deref(),
+            Some(r#"git = "https://github.com/Rust-GPU/rust-gpu.git", branch = "main""#)
+        );
+    }
+
+    #[test]
+    fn picks_the_version_this_crate_depends_on() {
+        let lock = format!(
+            r#"
+[[package]]
+name = "{}"
+version = "{}"
+dependencies = [
+ "spirv-builder 0.9.0",
+]
+
+[[package]]
+name = "spirv-builder"
+version = "0.8.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "spirv-builder"
+version = "0.9.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#,
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(
+            locked_spirv_builder(&lock).as_deref(),
+            Some(r#"version = "=0.9.0""#)
+        );
+    }
+
+    #[test]
+    fn ignores_unlocked_and_path_sources() {
+        assert_eq!(locked_spirv_builder("version = 4\n"), None);
+        let path = GIT_LOCK.replace(
+            "source = \"git+https://github.com/Rust-GPU/rust-gpu.git?branch=main#a30bd43db45f2bfe260051f44141e5eaffcbb4b0\"\n",
+            "",
+        );
+        assert_eq!(locked_spirv_builder(&path), None);
+    }
+}
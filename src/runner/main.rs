@@ -0,0 +1,79 @@
+//! Build runner of rust-gpu-hotreload: runs one spirv-builder compile per
+//! invocation.
+//!
+//! Written into the target directory and built on first use. spirv-builder
+//! starts cargo with the environment of the process calling it and lets cargo
+//! write diagnostics to that process's stderr, so running it here gives each
+//! compile its own environment and its own diagnostics.
+
+mod protocol;
+
+use protocol::{BuildRequest, BuildResponse, Metadata, PanicStrategy};
+use spirv_builder::{Capability, ModuleResult, ShaderPanicStrategy, SpirvBuilder, SpirvMetadata};
+
+fn main() {
+    let request: BuildRequest =
+        serde_json::from_reader(std::io::stdin()).expect("Invalid build request");
+    let response_path = request.response_path.clone();
+    let response = match build(request) {
+        Ok(ModuleResult::SingleModule(path)) => BuildResponse::SingleModule(path),
+        Ok(ModuleResult::MultiModule(modules)) => {
+            BuildResponse::MultiModule(modules.into_iter().collect())
+        }
+        Err(e) => BuildResponse::Failed(e.to_string()),
+    };
+    let json = serde_json::to_vec(&response).expect("Failed to encode build response");
+    std::fs::write(response_path, json).expect("Failed to write build response");
+}
+
+fn build(request: BuildRequest) -> Result<ModuleResult, Box<dyn std::error::Error>> {
+    let mut builder = SpirvBuilder::new(&request.crate_path, &request.target)
+        .spirv_metadata(match request.spirv_metadata {
+            Metadata::None => SpirvMetadata::None,
+            Metadata::NameVariables => SpirvMetadata::NameVariables,
+            Metadata::Full => SpirvMetadata::Full,
+        })
+        .relax_struct_store(request.relax_struct_store)
+        .relax_logical_pointer(request.relax_logical_pointer)
+        .relax_block_layout(request.relax_block_layout)
+        .uniform_buffer_standard_layout(request.uniform_buffer_standard_layout)
+        .scalar_block_layout(request.scalar_block_layout)
+        .skip_block_layout(request.skip_block_layout)
+        .preserve_bindings(request.preserve_bindings);
+    if let Some(target_dir) = &request.target_dir {
+        builder = builder.target_dir_path(target_dir.to_string_lossy().into_owned());
+    }
+    for &capability in &request.capabilities {
+        let capability = Capability::from_u32(capability)
+            .ok_or_else(|| format!("unknown capability {}", capability))?;
+        builder = builder.capability(capability);
+    }
+    for extension in &request.extensions {
+        builder = builder.extension(extension.as_str());
+    }
+    if request.multimodule {
+        builder = builder.multimodule(true);
+    }
+    if !request.features.is_empty() {
+        builder = builder.shader_crate_features(request.features.iter().cloned());
+    }
+    if let Some(strategy) = request.shader_panic_strategy {
+        builder = builder.shader_panic_strategy(match strategy {
+            PanicStrategy::SilentExit => ShaderPanicStrategy::SilentExit,
+            PanicStrategy::DebugPrintfThenExit {
+                print_inputs,
+                print_backtrace,
+            } => ShaderPanicStrategy::DebugPrintfThenExit {
+                print_inputs,
+                print_backtrace,
+            },
+            PanicStrategy::UndefinedBehaviorViaUnreachable => {
+                ShaderPanicStrategy::UNSOUND_DO_NOT_USE_UndefinedBehaviorViaUnreachable
+            }
+        });
+    }
+    for arg in &request.extra_args {
+        builder = builder.extra_arg(arg.as_str());
+    }
+    Ok(builder.build()?.module)
+}
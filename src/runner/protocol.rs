@@ -0,0 +1,63 @@
+//! Messages exchanged with the build runner.
+//!
+//! Compiled into both this crate and the runner program, so the two sides
+//! always agree on the format.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// A compile for the runner to perform, sent as JSON on its stdin.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BuildRequest {
+    pub crate_path: PathBuf,
+    pub target: String,
+    pub target_dir: Option<PathBuf>,
+    /// Capabilities by SPIR-V enumerant value.
+    pub capabilities: Vec<u32>,
+    pub extensions: Vec<String>,
+    pub multimodule: bool,
+    pub features: Vec<String>,
+    pub spirv_metadata: Metadata,
+    pub shader_panic_strategy: Option<PanicStrategy>,
+    pub relax_struct_store: bool,
+    pub relax_logical_pointer: bool,
+    pub relax_block_layout: bool,
+    pub uniform_buffer_standard_layout: bool,
+    pub scalar_block_layout: bool,
+    pub skip_block_layout: bool,
+    pub preserve_bindings: bool,
+    pub extra_args: Vec<String>,
+    /// File to write the [`BuildResponse`] to.
+    pub response_path: PathBuf,
+}
+
+/// `SpirvMetadata` of spirv-builder.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum Metadata {
+    None,
+    NameVariables,
+    Full,
+}
+
+/// `ShaderPanicStrategy` of spirv-builder.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub enum PanicStrategy {
+    SilentExit,
+    DebugPrintfThenExit {
+        print_inputs: bool,
+        print_backtrace: bool,
+    },
+    UndefinedBehaviorViaUnreachable,
+}
+
+/// Outcome of a compile, written as JSON to the request's `response_path`.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum BuildResponse {
+    /// The module a single-module build produced.
+    SingleModule(PathBuf),
+    /// Modules of a multimodule build, by entry point.
+    MultiModule(BTreeMap<String, PathBuf>),
+    /// The build failed. Diagnostics went to the runner's stderr.
+    Failed(String),
+}
@@ -0,0 +1,823 @@
+//! Word-level SPIR-V module representation.
+//!
+//! The post-compile transforms work directly on the instruction stream instead
+//! of round-tripping through `spirv-dis`/`spirv-as`. Only what they need is
+//! modelled: the header, raw instructions, and the layout of each instruction's
+//! operands so ids can be found and rewritten.
+
+mod disasm;
+pub(crate) mod grammar;
+mod link;
+mod split;
+
+pub use disasm::{disassemble, disassemble_words};
+pub use link::link_modules;
+pub use split::{extract_entry_point, split_entry_points};
+
+use grammar::{OperandKind, Quantifier};
+use spirv::{Capability, ExecutionModel, Op};
+use std::collections::HashMap;
+use std::fmt;
+
+/// First word of every SPIR-V module.
+pub const MAGIC_NUMBER: u32 = 0x0723_0203;
+
+const HEADER_WORDS: usize = 5;
+
+/// Highest SPIR-V version this crate understands.
+pub const MAX_VERSION: (u8, u8) = (1, 6);
+
+/// Universal limit on the id bound, from the SPIR-V specification.
+pub const MAX_BOUND: u32 = 0x3f_ffff;
+
+/// Errors produced while decoding or rewriting a SPIR-V module.
+#[derive(Debug)]
+pub enum SpirvError {
+    /// The module is shorter than the five-word header.
+    Truncated,
+    /// The byte length is not a whole number of words.
+    Misaligned { len: usize },
+    /// The first word is not the SPIR-V magic number in either byte order.
+    BadMagic(u32),
+    /// The header declares a version this crate does not understand.
+    UnsupportedVersion { major: u8, minor: u8 },
+    /// The header's id bound exceeds the universal limit.
+    BoundTooLarge(u32),
+    /// An instruction defines an id outside the header's id bound.
+    IdOutOfBounds { id: u32, bound: u32 },
+    /// An instruction's word count is zero or runs past the end of the module.
+    InvalidInstruction { offset: usize },
+    /// The opcode is not described by the bundled grammar.
+    UnknownOpcode(u16),
+    /// The operands do not match the grammar of the opcode.
+    MalformedOperands { opcode: u16 },
+    /// A transform left the module in an inconsistent state.
+    Validation(String),
+}
+
+impl fmt::Display for SpirvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated => write!(f, "SPIR-V module is shorter than its header"),
+            Self::Misaligned { len } => {
+                write!(f, "SPIR-V byte length {len} is not a multiple of 4")
+            }
+            Self::BadMagic(magic) => write!(f, "invalid SPIR-V magic number {magic:#010x}"),
+            Self::UnsupportedVersion { major, minor } => {
+                write!(f, "unsupported SPIR-V version {major}.{minor}")
+            }
+            Self::BoundTooLarge(bound) => {
+                write!(
+                    f,
+                    "SPIR-V id bound {bound} exceeds the limit of {MAX_BOUND}"
+                )
+            }
+            Self::IdOutOfBounds { id, bound } => {
+                write!(f, "SPIR-V id %{id} is outside the id bound {bound}")
+            }
+            Self::InvalidInstruction { offset } => {
+                write!(f, "invalid instruction word count at word {offset}")
+            }
+            Self::UnknownOpcode(opcode) => write!(f, "unknown SPIR-V opcode {opcode}"),
+            Self::MalformedOperands { opcode } => {
+                write!(f, "malformed operands for {}", opcode_name(*opcode))
+            }
+            Self::Validation(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for SpirvError {}
+
+/// The five-word module header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// Raw version word, `0x00MMmm00`.
+    pub version: u32,
+    pub generator: u32,
+    pub bound: u32,
+    pub schema: u32,
+}
+
+impl Header {
+    /// Returns the `(major, minor)` SPIR-V version.
+    pub fn version(&self) -> (u8, u8) {
+        ((self.version >> 16) as u8, (self.version >> 8) as u8)
+    }
+}
+
+/// A single instruction: the opcode and every word after the first.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub opcode: u16,
+    pub operands: Vec<u32>,
+}
+
+impl Instruction {
+    pub fn new(op: Op, operands: Vec<u32>) -> Self {
+        Self {
+            opcode: op as u16,
+            operands,
+        }
+    }
+
+    /// Returns true if this instruction has the given opcode.
+    pub fn is(&self, op: Op) -> bool {
+        self.opcode == op as u16
+    }
+
+    /// Returns the result id, if the opcode defines one.
+    pub fn result_id(&self) -> Option<u32> {
+        let grammar = grammar::lookup(self.opcode)?;
+        if !grammar.has_result() {
+            return None;
+        }
+        self.operands
+            .get(usize::from(grammar.has_result_type()))
+            .copied()
+    }
+
+    /// Returns the result type id, if the opcode has one.
+    pub fn result_type(&self) -> Option<u32> {
+        let grammar = grammar::lookup(self.opcode)?;
+        if grammar.has_result_type() {
+            self.operands.first().copied()
+        } else {
+            None
+        }
+    }
+
+    /// Decodes the literal string starting at operand word `index`.
+    pub fn string_operand(&self, index: usize) -> Option<String> {
+        decode_string(self.operands.get(index..)?).map(|(string, _)| string)
+    }
+}
+
+/// An `OpEntryPoint` declaration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryPoint {
+    pub execution_model: ExecutionModel,
+    pub function: u32,
+    pub name: String,
+    pub interface: Vec<u32>,
+}
+
+/// A decoded SPIR-V module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Module {
+    pub header: Header,
+    pub instructions: Vec<Instruction>,
+}
+
+impl Module {
+    /// Decodes a module from native-endian words.
+    pub fn from_words(words: &[u32]) -> Result<Self, SpirvError> {
+        if words.len() < HEADER_WORDS {
+            return Err(SpirvError::Truncated);
+        }
+        if words[0] != MAGIC_NUMBER {
+            return Err(SpirvError::BadMagic(words[0]));
+        }
+        let header = Header {
+            version: words[1],
+            generator: words[2],
+            bound: words[3],
+            schema: words[4],
+        };
+
+        let mut instructions = Vec::new();
+        let mut offset = HEADER_WORDS;
+        while offset < words.len() {
+            let word_count = (words[offset] >> 16) as usize;
+            if word_count == 0 || offset + word_count > words.len() {
+                return Err(SpirvError::InvalidInstruction { offset });
+            }
+            instructions.push(Instruction {
+                opcode: words[offset] as u16,
+                operands: words[offset + 1..offset + word_count].to_vec(),
+            });
+            offset += word_count;
+        }
+
+        Ok(Self {
+            header,
+            instructions,
+        })
+    }
+
+    /// Decodes a module from bytes in either byte order, checking its header and ids.
+    ///
+    /// See [`words_from_bytes`] and [`Module::validate`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SpirvError> {
+        let module = Self::from_words(&words_from_bytes(bytes)?)?;
+        module.validate()?;
+        Ok(module)
+    }
+
+    /// Checks that the version is supported and every result id is within the bound.
+    pub fn validate(&self) -> Result<(), SpirvError> {
+        let (major, minor) = self.header.version();
+        let reserved = self.header.version & 0xff00_00ff;
+        if reserved != 0 || major != 1 || (major, minor) > MAX_VERSION {
+            return Err(SpirvError::UnsupportedVersion { major, minor });
+        }
+
+        let bound = self.header.bound;
+        if bound > MAX_BOUND {
+            return Err(SpirvError::BoundTooLarge(bound));
+        }
+        for id in self.instructions.iter().filter_map(Instruction::result_id) {
+            if id == 0 || id >= bound {
+                return Err(SpirvError::IdOutOfBounds { id, bound });
+            }
+        }
+        Ok(())
+    }
+
+    /// Encodes the module back into words.
+    pub fn to_words(&self) -> Vec<u32> {
+        let mut words = vec![
+            MAGIC_NUMBER,
+            self.header.version,
+            self.header.generator,
+            self.header.bound,
+            self.header.schema,
+        ];
+        for inst in &self.instructions {
+            words.push(((inst.operands.len() as u32 + 1) << 16) | u32::from(inst.opcode));
+            words.extend_from_slice(&inst.operands);
+        }
+        words
+    }
+
+    /// Capabilities declared with `OpCapability`.
+    pub fn capabilities(&self) -> Vec<Capability> {
+        self.instructions
+            .iter()
+            .filter(|inst| inst.is(Op::Capability))
+            .filter_map(|inst| inst.operands.first().copied())
+            .filter_map(Capability::from_u32)
+            .collect()
+    }
+
+    /// Extensions declared with `OpExtension`.
+    pub fn extensions(&self) -> Vec<String> {
+        self.instructions
+            .iter()
+            .filter(|inst| inst.is(Op::Extension))
+            .filter_map(|inst| inst.string_operand(0))
+            .collect()
+    }
+
+    /// Extended instruction sets imported with `OpExtInstImport`, as `(id, name)`.
+    pub fn ext_inst_imports(&self) -> Vec<(u32, String)> {
+        self.instructions
+            .iter()
+            .filter(|inst| inst.is(Op::ExtInstImport))
+            .filter_map(|inst| Some((*inst.operands.first()?, inst.string_operand(1)?)))
+            .collect()
+    }
+
+    /// Entry points declared with `OpEntryPoint`.
+    pub fn entry_points(&self) -> Vec<EntryPoint> {
+        self.instructions
+            .iter()
+            .filter(|inst| inst.is(Op::EntryPoint))
+            .filter_map(|inst| {
+                let execution_model = ExecutionModel::from_u32(*inst.operands.first()?)?;
+                let function = *inst.operands.get(1)?;
+                let (name, name_words) = decode_string(inst.operands.get(2..)?)?;
+                Some(EntryPoint {
+                    execution_model,
+                    function,
+                    name,
+                    interface: inst.operands[2 + name_words..].to_vec(),
+                })
+            })
+            .collect()
+    }
+
+    /// Computes the operand layout of every instruction, in order.
+    pub(crate) fn layout(&self) -> Result<Vec<Vec<Span>>, SpirvError> {
+        let context = LayoutContext::new(self);
+        self.instructions
+            .iter()
+            .map(|inst| context.spans(inst))
+            .collect()
+    }
+}
+
+/// What a run of operand words holds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SpanKind {
+    ResultType,
+    Result,
+    Id,
+    Literal,
+    String,
+    /// A literal whose width depends on a type, such as an `OpConstant` value.
+    Number,
+    /// The instruction number of an `OpExtInst`.
+    ExtInstNumber,
+    /// The wrapped opcode of an `OpSpecConstantOp`.
+    SpecConstantOpcode,
+    Enum(OperandKind),
+}
+
+/// A run of operand words within an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Span {
+    pub start: usize,
+    pub len: usize,
+    pub kind: SpanKind,
+}
+
+impl Span {
+    /// Whether the span holds an id, including result and result type ids.
+    pub fn is_id(&self) -> bool {
+        matches!(
+            self.kind,
+            SpanKind::ResultType | SpanKind::Result | SpanKind::Id
+        )
+    }
+}
+
+/// Module-wide facts needed to lay out operands whose width depends on a type.
+struct LayoutContext {
+    int_widths: HashMap<u32, u32>,
+    value_types: HashMap<u32, u32>,
+}
+
+impl LayoutContext {
+    fn new(module: &Module) -> Self {
+        let mut int_widths = HashMap::new();
+        let mut value_types = HashMap::new();
+        for inst in &module.instructions {
+            if inst.is(Op::TypeInt) && inst.operands.len() >= 2 {
+                int_widths.insert(inst.operands[0], inst.operands[1]);
+            }
+            if let (Some(ty), Some(id)) = (inst.result_type(), inst.result_id()) {
+                value_types.insert(id, ty);
+            }
+        }
+        Self {
+            int_widths,
+            value_types,
+        }
+    }
+
+    /// Words used by an `OpSwitch` case literal for the given selector.
+    fn selector_words(&self, selector: u32) -> usize {
+        let width = self
+            .value_types
+            .get(&selector)
+            .and_then(|ty| self.int_widths.get(ty))
+            .copied()
+            .unwrap_or(32);
+        if width > 32 { 2 } else { 1 }
+    }
+
+    fn spans(&self, inst: &Instruction) -> Result<Vec<Span>, SpirvError> {
+        let mut spans = Vec::new();
+        let end = self.push_spans(inst.opcode, &inst.operands, 0, false, &mut spans)?;
+        if end < inst.operands.len() {
+            // Trailing words the grammar does not know about, e.g. newer enumerant
+            // parameters. Keep them as opaque literals.
+            spans.push(Span {
+                start: end,
+                len: inst.operands.len() - end,
+                kind: SpanKind::Literal,
+            });
+        }
+        Ok(spans)
+    }
+
+    fn push_spans(
+        &self,
+        opcode: u16,
+        operands: &[u32],
+        start: usize,
+        skip_result: bool,
+        spans: &mut Vec<Span>,
+    ) -> Result<usize, SpirvError> {
+        let grammar = grammar::lookup(opcode).ok_or(SpirvError::UnknownOpcode(opcode))?;
+        self.push_layout(
+            opcode,
+            grammar.operands,
+            operands,
+            start,
+            skip_result,
+            spans,
+        )
+    }
+
+    /// Lays out operands following the kinds and quantifiers of `layout`.
+    fn push_layout(
+        &self,
+        opcode: u16,
+        layout: &[(OperandKind, Quantifier)],
+        operands: &[u32],
+        start: usize,
+        skip_result: bool,
+        spans: &mut Vec<Span>,
+    ) -> Result<usize, SpirvError> {
+        let mut pos = start;
+        for &(kind, quantifier) in layout {
+            if skip_result && matches!(kind, OperandKind::IdResultType | OperandKind::IdResult) {
+                continue;
+            }
+            let repeat = match quantifier {
+                Quantifier::One => {
+                    if pos >= operands.len() {
+                        return Err(SpirvError::MalformedOperands { opcode });
+                    }
+                    1
+                }
+                Quantifier::ZeroOrOne => usize::from(pos < operands.len()),
+                Quantifier::ZeroOrMore => usize::MAX,
+            };
+            let mut taken = 0;
+            while taken < repeat && pos < operands.len() {
+                pos = self.push_operand(opcode, kind, operands, pos, spans)?;
+                taken += 1;
+            }
+        }
+        Ok(pos)
+    }
+
+    fn push_operand(
+        &self,
+        opcode: u16,
+        kind: OperandKind,
+        operands: &[u32],
+        pos: usize,
+        spans: &mut Vec<Span>,
+    ) -> Result<usize, SpirvError> {
+        let malformed = SpirvError::MalformedOperands { opcode };
+        let mut push = |start: usize, len: usize, kind: SpanKind| -> Result<usize, SpirvError> {
+            if start + len > operands.len() {
+                return Err(SpirvError::MalformedOperands { opcode });
+            }
+            spans.push(Span { start, len, kind });
+            Ok(start + len)
+        };
+
+        match kind {
+            OperandKind::IdResultType => push(pos, 1, SpanKind::ResultType),
+            OperandKind::IdResult => push(pos, 1, SpanKind::Result),
+            OperandKind::IdRef | OperandKind::IdScope | OperandKind::IdMemorySemantics => {
+                push(pos, 1, SpanKind::Id)
+            }
+            OperandKind::LiteralInteger => push(pos, 1, SpanKind::Literal),
+            OperandKind::LiteralExtInstInteger => push(pos, 1, SpanKind::ExtInstNumber),
+            OperandKind::LiteralString => {
+                let (_, len) = decode_string(&operands[pos..]).ok_or(malformed)?;
+                push(pos, len, SpanKind::String)
+            }
+            OperandKind::LiteralContextDependentNumber => {
+                push(pos, operands.len() - pos, SpanKind::Number)
+            }
+            OperandKind::LiteralSpecConstantOpInteger => {
+                let inner = operands[pos] as u16;
+                let next = push(pos, 1, SpanKind::SpecConstantOpcode)?;
+                self.push_spans(inner, operands, next, true, spans)
+            }
+            OperandKind::PairLiteralIntegerIdRef => {
+                let words = self.selector_words(operands[0]);
+                let next = push(pos, words, SpanKind::Number)?;
+                push(next, 1, SpanKind::Id)
+            }
+            OperandKind::PairIdRefLiteralInteger => {
+                let next = push(pos, 1, SpanKind::Id)?;
+                push(next, 1, SpanKind::Literal)
+            }
+            OperandKind::PairIdRefIdRef => {
+                let next = push(pos, 1, SpanKind::Id)?;
+                push(next, 1, SpanKind::Id)
+            }
+            kind => {
+                let value = operands[pos];
+                let next = push(pos, 1, SpanKind::Enum(kind))?;
+                let params = enum_params(kind, value);
+                self.push_layout(opcode, &params, operands, next, false, spans)
+            }
+        }
+    }
+}
+
+/// Operands that follow an enumerant, or each set bit of a mask in ascending order.
+fn enum_params(kind: OperandKind, value: u32) -> Vec<(OperandKind, Quantifier)> {
+    if grammar::is_mask(kind) {
+        set_bits(value)
+            .flat_map(|bit| grammar::parameters(kind, bit).iter().copied())
+            .collect()
+    } else {
+        grammar::parameters(kind, value).to_vec()
+    }
+}
+
+/// Iterates the set bits of a mask in ascending order.
+fn set_bits(mask: u32) -> impl Iterator<Item = u32> {
+    (0..32)
+        .map(|shift| 1u32 << shift)
+        .filter(move |bit| mask & bit != 0)
+}
+
+/// Converts a SPIR-V binary into words, swapping byte order if the module is big-endian.
+///
+/// Fails if the length is not a multiple of four, the header is incomplete, or
+/// the magic number does not match in either byte order.
+pub fn words_from_bytes(bytes: &[u8]) -> Result<Vec<u32>, SpirvError> {
+    if !bytes.len().is_multiple_of(4) {
+        return Err(SpirvError::Misaligned { len: bytes.len() });
+    }
+    if bytes.len() < HEADER_WORDS * 4 {
+        return Err(SpirvError::Truncated);
+    }
+
+    let magic = [bytes[0], bytes[1], bytes[2], bytes[3]];
+    let from_bytes: fn([u8; 4]) -> u32 = if u32::from_le_bytes(magic) == MAGIC_NUMBER {
+        u32::from_le_bytes
+    } else if u32::from_be_bytes(magic) == MAGIC_NUMBER {
+        u32::from_be_bytes
+    } else {
+        return Err(SpirvError::BadMagic(u32::from_le_bytes(magic)));
+    };
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|chunk| from_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect())
+}
+
+/// Decodes a nul-terminated literal string, returning it and the words it occupies.
+pub fn decode_string(words: &[u32]) -> Option<(String, usize)> {
+    let mut bytes = Vec::new();
+    for (index, word) in words.iter().enumerate() {
+        for byte in word.to_le_bytes() {
+            if byte == 0 {
+                return Some((String::from_utf8_lossy(&bytes).into_owned(), index + 1));
+            }
+            bytes.push(byte);
+        }
+    }
+    None
+}
+
+/// Encodes a literal string, nul-terminated and padded to a word boundary.
+pub fn encode_string(string: &str) -> Vec<u32> {
+    let mut bytes = string.as_bytes().to_vec();
+    bytes.push(0);
+    bytes.resize(bytes.len().div_ceil(4) * 4, 0);
+    bytes
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect()
+}
+
+/// Returns `Op<Name>` for a known opcode, or a placeholder otherwise.
+pub fn opcode_name(opcode: u16) -> String {
+    match grammar::lookup(opcode) {
+        Some(grammar) => format!("Op{}", grammar.name),
+        None => format!("Op<{opcode}>"),
+    }
+}
+
+/// Builds a module from instructions, for tests.
+#[cfg(test)]
+pub(crate) fn test_module(version: (u8, u8), bound: u32, instructions: Vec<Instruction>) -> Module {
+    Module {
+        header: Header {
+            version: (u32::from(version.0) << 16) | (u32::from(version.1) << 8),
+            generator: 0,
+            bound,
+            schema: 0,
+        },
+        instructions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spirv::{BuiltIn, CooperativeMatrixOperands, Decoration, MemoryAccess, PackedVectorFormat};
+
+    fn kinds(inst: Instruction) -> Vec<(usize, usize, SpanKind)> {
+        let module = test_module((1, 3), 100, vec![inst]);
+        module.layout().unwrap()[0]
+            .iter()
+            .map(|span| (span.start, span.len, span.kind))
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_words() {
+        let shader = Instruction::new(Op::Capability, vec![Capability::Shader as u32]);
+        let extension = Instruction::new(Op::Extension, encode_string("SPV_KHR_abc"));
+        let module = test_module((1, 3), 1, vec![shader, extension]);
+
+        let words = module.to_words();
+        assert_eq!(words[0], MAGIC_NUMBER);
+        assert_eq!(words[1], 0x0001_0300);
+        let decoded = Module::from_words(&words).unwrap();
+        assert_eq!(decoded, module);
+        assert_eq!(decoded.capabilities(), vec![Capability::Shader]);
+        assert_eq!(decoded.extensions(), vec!["SPV_KHR_abc".to_owned()]);
+    }
+
+    #[test]
+    fn reads_either_byte_order() {
+        let words = test_module((1, 0), 1, vec![]).to_words();
+        let little: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+        let big: Vec<u8> = words.iter().flat_map(|word| word.to_be_bytes()).collect();
+        assert_eq!(words_from_bytes(&little).unwrap(), words);
+        assert_eq!(words_from_bytes(&big).unwrap(), words);
+    }
+
+    #[test]
+    fn rejects_bad_binaries() {
+        let bytes: Vec<u8> = test_module((1, 0), 1, vec![])
+            .to_words()
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        assert!(matches!(
+            words_from_bytes(&bytes[..19]),
+            Err(SpirvError::Misaligned { len: 19 })
+        ));
+        assert!(matches!(
+            words_from_bytes(&bytes[..16]),
+            Err(SpirvError::Truncated)
+        ));
+        let mut bad = bytes.clone();
+        bad[0] = 0;
+        assert!(matches!(
+            words_from_bytes(&bad),
+            Err(SpirvError::BadMagic(_))
+        ));
+
+        // A word count of zero, and one running past the end
+        let mut words = test_module((1, 0), 1, vec![]).to_words();
+        words.push(Op::Nop as u32);
+        assert!(matches!(
+            Module::from_words(&words),
+            Err(SpirvError::InvalidInstruction { offset: 5 })
+        ));
+        words[5] = (3 << 16) | Op::Nop as u32;
+        assert!(matches!(
+            Module::from_words(&words),
+            Err(SpirvError::InvalidInstruction { offset: 5 })
+        ));
+    }
+
+    #[test]
+    fn validates_header_and_ids() {
+        let defines = |id| Instruction::new(Op::TypeVoid, vec![id]);
+        assert!(test_module((1, 6), 2, vec![defines(1)]).validate().is_ok());
+        assert!(matches!(
+            test_module((1, 7), 2, vec![]).validate(),
+            Err(SpirvError::UnsupportedVersion { major: 1, minor: 7 })
+        ));
+        assert!(matches!(
+            test_module((1, 0), MAX_BOUND + 1, vec![]).validate(),
+            Err(SpirvError::BoundTooLarge(_))
+        ));
+        assert!(matches!(
+            test_module((1, 0), 2, vec![defines(2)]).validate(),
+            Err(SpirvError::IdOutOfBounds { id: 2, bound: 2 })
+        ));
+    }
+
+    #[test]
+    fn encodes_strings() {
+        assert_eq!(encode_string(""), vec![0]);
+        assert_eq!(encode_string("main").len(), 2);
+        assert_eq!(
+            decode_string(&encode_string("main")),
+            Some(("main".to_owned(), 2))
+        );
+        assert_eq!(decode_string(&[u32::from_le_bytes(*b"abcd")]), None);
+    }
+
+    #[test]
+    fn lays_out_enumerant_parameters() {
+        let builtin = Instruction::new(
+            Op::Decorate,
+            vec![1, Decoration::BuiltIn as u32, BuiltIn::Position as u32],
+        );
+        assert_eq!(
+            kinds(builtin),
+            vec![
+                (0, 1, SpanKind::Id),
+                (1, 1, SpanKind::Enum(OperandKind::Decoration)),
+                (2, 1, SpanKind::Enum(OperandKind::BuiltIn)),
+            ]
+        );
+
+        let aligned = (MemoryAccess::VOLATILE | MemoryAccess::ALIGNED).bits();
+        let load = Instruction::new(Op::Load, vec![1, 2, 3, aligned, 16]);
+        assert_eq!(
+            kinds(load),
+            vec![
+                (0, 1, SpanKind::ResultType),
+                (1, 1, SpanKind::Result),
+                (2, 1, SpanKind::Id),
+                (3, 1, SpanKind::Enum(OperandKind::MemoryAccess)),
+                (4, 1, SpanKind::Literal),
+            ]
+        );
+    }
+
+    #[test]
+    fn lays_out_mask_parameters_in_bit_order() {
+        // Bias (0x1) takes one id, Grad (0x4) two, ConstOffset (0x8) one
+        let sample = Instruction::new(
+            Op::ImageSampleExplicitLod,
+            vec![1, 2, 3, 4, 0xd, 5, 6, 7, 8],
+        );
+        let spans = kinds(sample);
+        assert_eq!(spans[4], (4, 1, SpanKind::Enum(OperandKind::ImageOperands)));
+        assert_eq!(
+            spans[5..],
+            [5, 6, 7, 8].map(|start| (start, 1, SpanKind::Id))
+        );
+    }
+
+    #[test]
+    fn lays_out_spirv_1_6_instructions() {
+        let packed = PackedVectorFormat::PackedVectorFormat4x8Bit as u32;
+        let sdot = Instruction::new(Op::SDot, vec![1, 2, 3, 4, packed]);
+        assert_eq!(
+            kinds(sdot),
+            vec![
+                (0, 1, SpanKind::ResultType),
+                (1, 1, SpanKind::Result),
+                (2, 1, SpanKind::Id),
+                (3, 1, SpanKind::Id),
+                (4, 1, SpanKind::Enum(OperandKind::PackedVectorFormat)),
+            ]
+        );
+
+        let saturating = CooperativeMatrixOperands::SATURATING_ACCUMULATION_KHR.bits();
+        let mul_add = Instruction::new(
+            Op::CooperativeMatrixMulAddKHR,
+            vec![1, 2, 3, 4, 5, saturating],
+        );
+        assert_eq!(
+            kinds(mul_add)[5],
+            (5, 1, SpanKind::Enum(OperandKind::CooperativeMatrixOperands))
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_trailing_words() {
+        let shader = Instruction::new(Op::Capability, vec![Capability::Shader as u32, 7]);
+        assert_eq!(
+            kinds(shader),
+            vec![
+                (0, 1, SpanKind::Enum(OperandKind::Capability)),
+                (1, 1, SpanKind::Literal),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_instructions() {
+        let layout = |inst| test_module((1, 3), 100, vec![inst]).layout();
+        assert!(matches!(
+            layout(Instruction {
+                opcode: 0xfffe,
+                operands: vec![]
+            }),
+            Err(SpirvError::UnknownOpcode(0xfffe))
+        ));
+        assert!(matches!(
+            layout(Instruction::new(Op::Load, vec![1, 2])),
+            Err(SpirvError::MalformedOperands { opcode }) if opcode == Op::Load as u16
+        ));
+        assert!(matches!(
+            layout(Instruction::new(
+                Op::Extension,
+                vec![u32::from_le_bytes(*b"abcd")]
+            )),
+            Err(SpirvError::MalformedOperands { .. })
+        ));
+    }
+
+    #[test]
+    fn sizes_switch_literals_by_selector_type() {
+        let module = test_module(
+            (1, 3),
+            100,
+            vec![
+                Instruction::new(Op::TypeInt, vec![1, 64, 0]),
+                Instruction::new(Op::Undef, vec![1, 2]),
+                Instruction::new(Op::Switch, vec![2, 3, 10, 0, 4]),
+            ],
+        );
+        let spans = &module.layout().unwrap()[2];
+        assert_eq!(spans[2].len, 2);
+        assert_eq!(spans[2].kind, SpanKind::Number);
+        assert_eq!(spans[3].start, 4);
+        assert!(spans[3].is_id());
+    }
+}
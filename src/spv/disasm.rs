@@ -0,0 +1,706 @@
+//! Textual disassembly in the format of `spirv-dis`.
+
+use super::grammar::OperandKind;
+use super::{Instruction, Module, Span, SpanKind, SpirvError, decode_string, grammar};
+use spirv::Op;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
+/// Column where the opcode of every instruction starts.
+const OPCODE_COLUMN: usize = 15;
+
+/// Tool names of the generator ids in the SPIR-V registry, as `spirv-dis` prints them.
+const GENERATORS: &[&str] = &[
+    "Khronos",
+    "LunarG",
+    "Valve",
+    "Codeplay",
+    "NVIDIA",
+    "ARM",
+    "Khronos LLVM/SPIR-V Translator",
+    "Khronos SPIR-V Tools Assembler",
+    "Khronos Glslang Reference Front End",
+    "Qualcomm",
+    "AMD",
+    "Intel",
+    "Imagination",
+    "Google Shaderc over Glslang",
+    "Google spiregg",
+    "Google rspirv",
+    "X-LEGEND Mesa-IR/SPIR-V Translator",
+    "Khronos SPIR-V Tools Linker",
+    "Wine VKD3D Shader Compiler",
+    "Tellusim Clay Shader Compiler",
+    "W3C WebGPU Group WHLSL Shader Translator",
+    "Google Clspv",
+    "Google MLIR SPIR-V Serializer",
+    "Google Tint Compiler",
+    "Google ANGLE Shader Compiler",
+    "Netease Games Messiah Shader Compiler",
+    "Xenia Xenia Emulator Microcode Translator",
+    "Embark Studios Rust GPU Compiler Backend",
+    "gfx-rs community Naga",
+];
+
+/// Scalar type of a literal number.
+#[derive(Clone, Copy)]
+enum NumberType {
+    Int { width: u32, signed: bool },
+    Float { width: u32 },
+}
+
+/// Disassembles a module into the textual assembly format.
+///
+/// Ids are shown by their `OpName` where one exists. Unnamed scalar, vector,
+/// matrix, pointer and array types and scalar constants get names derived from
+/// their definition, e.g. `%_ptr_Output_v4float` or `%uint_3`.
+pub fn disassemble(module: &Module) -> Result<String, SpirvError> {
+    let layout = module.layout()?;
+    let disassembler = Disassembler::new(module);
+
+    let (major, minor) = module.header.version();
+    let mut text = String::new();
+    let _ = writeln!(text, "; SPIR-V");
+    let _ = writeln!(text, "; Version: {major}.{minor}");
+    let tool = module.header.generator >> 16;
+    let _ = match GENERATORS.get(tool as usize) {
+        Some(name) => writeln!(
+            text,
+            "; Generator: {name}; {}",
+            module.header.generator & 0xffff
+        ),
+        None => writeln!(
+            text,
+            "; Generator: Unknown({tool}); {}",
+            module.header.generator & 0xffff
+        ),
+    };
+    let _ = writeln!(text, "; Bound: {}", module.header.bound);
+    let _ = writeln!(text, "; Schema: {}", module.header.schema);
+
+    for (inst, spans) in module.instructions.iter().zip(&layout) {
+        disassembler.write_instruction(&mut text, inst, spans);
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Decodes and disassembles a module given as words.
+///
+/// # Example
+///
+/// ```rust
+/// use rust_gpu_hotreload::spv::{Instruction, disassemble_words, encode_string};
+/// use spirv::Op;
+///
+/// let mut words = vec![0x0723_0203, 0x0001_0300, 0, 3, 0];
+/// let instructions = [
+///     Instruction::new(Op::Capability, vec![1]),
+///     Instruction::new(Op::MemoryModel, vec![0, 1]),
+///     Instruction::new(Op::Name, [vec![1], encode_string("Light")].concat()),
+///     Instruction::new(Op::TypeFloat, vec![2, 32]),
+///     Instruction::new(Op::TypeStruct, vec![1, 2, 2]),
+/// ];
+/// for inst in &instructions {
+///     words.push(((inst.operands.len() as u32 + 1) << 16) | u32::from(inst.opcode));
+///     words.extend(&inst.operands);
+/// }
+///
+/// let text = disassemble_words(&words).unwrap();
+/// assert!(text.contains("               OpCapability Shader\n"));
+/// assert!(text.contains("               OpName %Light \"Light\"\n"));
+/// assert!(text.contains("      %Light = OpTypeStruct %float %float\n"));
+/// ```
+pub fn disassemble_words(words: &[u32]) -> Result<String, SpirvError> {
+    disassemble(&Module::from_words(words)?)
+}
+
+struct Disassembler {
+    names: HashMap<u32, String>,
+    number_types: HashMap<u32, NumberType>,
+    /// Result type of every id that has one.
+    value_types: HashMap<u32, u32>,
+    ext_inst_imports: HashMap<u32, String>,
+}
+
+impl Disassembler {
+    fn new(module: &Module) -> Self {
+        let mut number_types = HashMap::new();
+        let mut value_types = HashMap::new();
+        for inst in &module.instructions {
+            if let Some(id) = inst.result_id() {
+                if inst.is(Op::TypeInt) && inst.operands.len() >= 3 {
+                    let (width, signed) = (inst.operands[1], inst.operands[2] != 0);
+                    number_types.insert(id, NumberType::Int { width, signed });
+                } else if inst.is(Op::TypeFloat) && inst.operands.len() >= 2 {
+                    number_types.insert(
+                        id,
+                        NumberType::Float {
+                            width: inst.operands[1],
+                        },
+                    );
+                }
+            }
+            if let (Some(ty), Some(id)) = (inst.result_type(), inst.result_id()) {
+                value_types.insert(id, ty);
+            }
+        }
+
+        let mut disassembler = Self {
+            names: HashMap::new(),
+            number_types,
+            value_types,
+            ext_inst_imports: module.ext_inst_imports().into_iter().collect(),
+        };
+        disassembler.names = disassembler.friendly_names(module);
+        disassembler
+    }
+
+    /// Picks a unique name for every id that has an `OpName` or a derivable one.
+    fn friendly_names(&self, module: &Module) -> HashMap<u32, String> {
+        let mut debug_names = HashMap::new();
+        for inst in &module.instructions {
+            if inst.is(Op::Name)
+                && let (Some(&target), Some(name)) = (inst.operands.first(), inst.string_operand(1))
+            {
+                debug_names.entry(target).or_insert(name);
+            }
+        }
+
+        let mut names = HashMap::new();
+        let mut used = HashSet::new();
+        for inst in &module.instructions {
+            let Some(id) = inst.result_id() else {
+                continue;
+            };
+            let name = match debug_names.get(&id) {
+                Some(name) => Some(sanitize(name)),
+                None => self.derived_name(inst, &names),
+            };
+            let Some(name) = name else {
+                continue;
+            };
+            let mut unique = name.clone();
+            let mut suffix = 0;
+            while !used.insert(unique.clone()) {
+                unique = format!("{name}_{suffix}");
+                suffix += 1;
+            }
+            names.insert(id, unique);
+        }
+        names
+    }
+
+    /// Name for an unnamed type or constant, built from the names of its operands.
+    fn derived_name(&self, inst: &Instruction, names: &HashMap<u32, String>) -> Option<String> {
+        let name_of = |index: usize| names.get(inst.operands.get(index)?).cloned();
+        let op = Op::from_u32(u32::from(inst.opcode))?;
+        match op {
+            Op::TypeVoid => Some("void".to_string()),
+            Op::TypeBool => Some("bool".to_string()),
+            Op::TypeInt => {
+                let (width, signed) = (*inst.operands.get(1)?, *inst.operands.get(2)? != 0);
+                let base = if signed { "int" } else { "uint" };
+                Some(match width {
+                    32 => base.to_string(),
+                    width => format!("{base}{width}"),
+                })
+            }
+            Op::TypeFloat => Some(match inst.operands.get(1)? {
+                16 => "half".to_string(),
+                32 => "float".to_string(),
+                64 => "double".to_string(),
+                width => format!("fp{width}"),
+            }),
+            Op::TypeVector => Some(format!("v{}{}", inst.operands.get(2)?, name_of(1)?)),
+            Op::TypeMatrix => Some(format!("mat{}{}", inst.operands.get(2)?, name_of(1)?)),
+            Op::TypePointer => {
+                let storage_class = spirv::StorageClass::from_u32(*inst.operands.get(1)?)?;
+                Some(format!("_ptr_{storage_class:?}_{}", name_of(2)?))
+            }
+            Op::TypeArray => Some(format!("_arr_{}_{}", name_of(1)?, name_of(2)?)),
+            Op::TypeRuntimeArray => Some(format!("_runtimearr_{}", name_of(1)?)),
+            Op::ConstantTrue => Some("true".to_string()),
+            Op::ConstantFalse => Some("false".to_string()),
+            Op::Constant => {
+                let ty = *inst.operands.first()?;
+                let value = self.format_number(ty, inst.operands.get(2..)?);
+                // `n` marks negative values; `sanitize` replaces the rest
+                let value = value.replace('-', "n");
+                Some(sanitize(&format!("{}_{value}", names.get(&ty)?)))
+            }
+            _ => None,
+        }
+    }
+
+    fn id(&self, id: u32) -> String {
+        match self.names.get(&id) {
+            Some(name) => format!("%{name}"),
+            None => format!("%{id}"),
+        }
+    }
+
+    fn write_instruction(&self, text: &mut String, inst: &Instruction, spans: &[Span]) {
+        match spans.iter().find(|span| span.kind == SpanKind::Result) {
+            Some(span) => {
+                let result = self.id(inst.operands[span.start]);
+                let _ = write!(text, "{result:>width$} = ", width = OPCODE_COLUMN - 3);
+            }
+            None => text.push_str(&" ".repeat(OPCODE_COLUMN)),
+        }
+        text.push_str(&super::opcode_name(inst.opcode));
+
+        for span in spans.iter().filter(|span| span.kind != SpanKind::Result) {
+            let words = &inst.operands[span.start..span.start + span.len];
+            text.push(' ');
+            match span.kind {
+                SpanKind::ResultType | SpanKind::Id => text.push_str(&self.id(words[0])),
+                SpanKind::Result => {}
+                SpanKind::Literal => {
+                    let literals: Vec<String> = words.iter().map(u32::to_string).collect();
+                    text.push_str(&literals.join(" "));
+                }
+                SpanKind::String => {
+                    let string = decode_string(words).map(|(s, _)| s).unwrap_or_default();
+                    text.push_str(&quote(&string));
+                }
+                SpanKind::Number => {
+                    // Constants carry their type; switch cases take the selector's type.
+                    let ty = if inst.is(Op::Switch) {
+                        self.value_types.get(&inst.operands[0]).copied()
+                    } else {
+                        inst.result_type()
+                    };
+                    text.push_str(&self.format_number(ty.unwrap_or(0), words));
+                }
+                SpanKind::ExtInstNumber => {
+                    let set = self.ext_inst_imports.get(&inst.operands[2]);
+                    let name = match set.map(String::as_str) {
+                        Some("GLSL.std.450") => {
+                            spirv::GLOp::from_u32(words[0]).map(|op| format!("{op:?}"))
+                        }
+                        Some("OpenCL.std") => {
+                            spirv::CLOp::from_u32(words[0]).map(|op| format!("{op:?}"))
+                        }
+                        _ => None,
+                    };
+                    text.push_str(&name.unwrap_or_else(|| words[0].to_string()));
+                }
+                SpanKind::SpecConstantOpcode => match grammar::lookup(words[0] as u16) {
+                    Some(grammar) => text.push_str(grammar.name),
+                    None => text.push_str(&words[0].to_string()),
+                },
+                SpanKind::Enum(kind) => text.push_str(&enum_name(kind, words[0])),
+            }
+        }
+    }
+
+    /// Formats a literal number according to the scalar type `ty`.
+    fn format_number(&self, ty: u32, words: &[u32]) -> String {
+        let wide =
+            |words: &[u32]| u64::from(words[0]) | (u64::from(*words.get(1).unwrap_or(&0)) << 32);
+        match self.number_types.get(&ty) {
+            Some(&NumberType::Int {
+                width,
+                signed: true,
+            }) if width > 32 => (wide(words) as i64).to_string(),
+            Some(&NumberType::Int { width, .. }) if width > 32 => wide(words).to_string(),
+            Some(&NumberType::Int { signed: true, .. }) => (words[0] as i32).to_string(),
+            // `spirv-dis` always prints half floats in hex
+            Some(&NumberType::Float { width: 16 }) => {
+                hex_float(u64::from(words[0] & 0xffff), 10, 5)
+            }
+            Some(&NumberType::Float { width: 32 }) => match f32::from_bits(words[0]) {
+                value if value == 0.0 || value.is_normal() => general(f64::from(value), 9),
+                _ => hex_float(u64::from(words[0]), 23, 8),
+            },
+            Some(&NumberType::Float { width: 64 }) => match f64::from_bits(wide(words)) {
+                value if value == 0.0 || value.is_normal() => general(value, 17),
+                _ => hex_float(wide(words), 52, 11),
+            },
+            _ => {
+                let literals: Vec<String> = words.iter().map(u32::to_string).collect();
+                literals.join(" ")
+            }
+        }
+    }
+}
+
+/// Formats `value` like C's `%.{precision}g`, as `spirv-dis` does with the
+/// type's `max_digits10` (9 for `f32`, 17 for `f64`).
+fn general(value: f64, precision: usize) -> String {
+    let scientific = format!("{value:.*e}", precision - 1);
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+    if exponent < -4 || exponent >= precision as i32 {
+        let sign = if exponent < 0 { '-' } else { '+' };
+        format!(
+            "{}e{sign}{:02}",
+            trim_fraction(mantissa),
+            exponent.unsigned_abs()
+        )
+    } else {
+        let decimals = (precision as i32 - 1 - exponent) as usize;
+        trim_fraction(&format!("{value:.decimals$}")).to_string()
+    }
+}
+
+/// Drops trailing zeros after the decimal point, and the point itself if bare.
+fn trim_fraction(number: &str) -> &str {
+    if number.contains('.') {
+        number.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        number
+    }
+}
+
+/// Formats the float with the given field widths as a hex float, which
+/// `spirv-dis` uses for half floats, subnormals, infinities and NaNs.
+///
+/// Subnormals are normalized so the leading digit is always 1, e.g. `0x1p-149`.
+fn hex_float(bits: u64, fraction_bits: u32, exponent_bits: u32) -> String {
+    let sign = if (bits >> (fraction_bits + exponent_bits)) & 1 != 0 {
+        "-"
+    } else {
+        ""
+    };
+    let biased = (bits >> fraction_bits) & ((1 << exponent_bits) - 1);
+    let digits = fraction_bits.div_ceil(4);
+    let width = digits * 4;
+    let mut fraction = (bits & ((1 << fraction_bits) - 1)) << (width - fraction_bits);
+    let mut exponent = biased as i64 - ((1 << (exponent_bits - 1)) - 1);
+
+    let leading = if biased == 0 && fraction == 0 {
+        exponent = 0;
+        '0'
+    } else {
+        '1'
+    };
+    if biased == 0 && fraction != 0 {
+        while fraction & (1 << (width - 1)) == 0 {
+            fraction <<= 1;
+            exponent -= 1;
+        }
+        // The leading 1 becomes implicit
+        fraction = (fraction << 1) & ((1 << width) - 1);
+    }
+
+    let fraction = format!("{fraction:0width$x}", width = digits as usize);
+    let fraction = fraction.trim_end_matches('0');
+    let point = if fraction.is_empty() { "" } else { "." };
+    format!("{sign}0x{leading}{point}{fraction}p{exponent:+}")
+}
+
+/// Replaces characters that are not valid in an id name.
+fn sanitize(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    // Names must not be mistaken for numeric ids.
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{name}")
+    } else {
+        name
+    }
+}
+
+fn quote(string: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in string.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Name of an enumerant, or the `|`-separated bits of a mask.
+fn enum_name(kind: OperandKind, value: u32) -> String {
+    macro_rules! debug_name {
+        ($ty:ident) => {
+            spirv::$ty::from_u32(value).map(|value| format!("{value:?}"))
+        };
+    }
+    let name = match kind {
+        OperandKind::AccessQualifier => debug_name!(AccessQualifier),
+        OperandKind::AddressingModel => debug_name!(AddressingModel),
+        OperandKind::BuiltIn => debug_name!(BuiltIn),
+        OperandKind::Capability => debug_name!(Capability),
+        OperandKind::Decoration => debug_name!(Decoration),
+        OperandKind::Dim => debug_name!(Dim).map(|dim| dim.trim_start_matches("Dim").to_string()),
+        OperandKind::ExecutionMode => debug_name!(ExecutionMode),
+        OperandKind::ExecutionModel => debug_name!(ExecutionModel),
+        OperandKind::FPRoundingMode => debug_name!(FPRoundingMode),
+        OperandKind::FunctionParameterAttribute => debug_name!(FunctionParameterAttribute),
+        OperandKind::GroupOperation => debug_name!(GroupOperation),
+        OperandKind::ImageFormat => debug_name!(ImageFormat),
+        OperandKind::LinkageType => debug_name!(LinkageType),
+        OperandKind::MemoryModel => debug_name!(MemoryModel),
+        OperandKind::PackedVectorFormat => debug_name!(PackedVectorFormat),
+        OperandKind::SamplerAddressingMode => debug_name!(SamplerAddressingMode),
+        OperandKind::SamplerFilterMode => debug_name!(SamplerFilterMode),
+        OperandKind::SourceLanguage => debug_name!(SourceLanguage),
+        OperandKind::StorageClass => debug_name!(StorageClass),
+        kind => grammar::mask_bits(kind).map(|bits| mask_name(bits, value)),
+    };
+    name.unwrap_or_else(|| value.to_string())
+}
+
+fn mask_name(bits: &[(u32, &str)], value: u32) -> String {
+    if value == 0 {
+        return "None".to_string();
+    }
+    let mut names = Vec::new();
+    let mut rest = value;
+    for &(bit, name) in bits {
+        if value & bit != 0 {
+            names.push(name.to_string());
+            rest &= !bit;
+        }
+    }
+    if rest != 0 {
+        names.push(format!("{rest:#x}"));
+    }
+    names.join("|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spv::{encode_string, test_module};
+
+    fn with_string(op: Op, before: &[u32], string: &str, after: &[u32]) -> Instruction {
+        Instruction::new(op, [before, &encode_string(string), after].concat())
+    }
+
+    /// A fragment shader writing `sqrt(0.5)` to every component of its output.
+    fn fragment_module() -> Module {
+        test_module(
+            (1, 3),
+            17,
+            vec![
+                Instruction::new(Op::Capability, vec![1]),
+                with_string(Op::ExtInstImport, &[1], "GLSL.std.450", &[]),
+                Instruction::new(Op::MemoryModel, vec![0, 1]),
+                with_string(Op::EntryPoint, &[4, 2], "main", &[3]),
+                Instruction::new(Op::ExecutionMode, vec![2, 7]),
+                with_string(Op::Name, &[2], "main", &[]),
+                with_string(Op::Name, &[3], "out_color", &[]),
+                Instruction::new(Op::Decorate, vec![3, 30, 0]),
+                Instruction::new(Op::TypeVoid, vec![4]),
+                Instruction::new(Op::TypeFunction, vec![5, 4]),
+                Instruction::new(Op::TypeFloat, vec![6, 32]),
+                Instruction::new(Op::TypeVector, vec![7, 6, 4]),
+                Instruction::new(Op::TypePointer, vec![8, 3, 7]),
+                Instruction::new(Op::TypeInt, vec![9, 32, 0]),
+                Instruction::new(Op::Constant, vec![9, 10, 3]),
+                Instruction::new(Op::Constant, vec![6, 11, 0.5f32.to_bits()]),
+                Instruction::new(Op::TypeInt, vec![15, 32, 1]),
+                Instruction::new(Op::Constant, vec![15, 16, u32::MAX]),
+                Instruction::new(Op::Variable, vec![8, 3, 3]),
+                Instruction::new(Op::Function, vec![4, 2, 0, 5]),
+                Instruction::new(Op::Label, vec![12]),
+                Instruction::new(Op::ExtInst, vec![6, 13, 1, 31, 11]),
+                Instruction::new(Op::CompositeConstruct, vec![7, 14, 13, 13, 13, 13]),
+                Instruction::new(Op::Store, vec![3, 14, 2, 16]),
+                Instruction::new(Op::Return, vec![]),
+                Instruction::new(Op::FunctionEnd, vec![]),
+            ],
+        )
+    }
+
+    /// `spirv-dis` output for [`fragment_module`].
+    const FRAGMENT_TEXT: &str = "\
+; SPIR-V
+; Version: 1.3
+; Generator: Khronos; 0
+; Bound: 17
+; Schema: 0
+               OpCapability Shader
+          %1 = OpExtInstImport \"GLSL.std.450\"
+               OpMemoryModel Logical GLSL450
+               OpEntryPoint Fragment %main \"main\" %out_color
+               OpExecutionMode %main OriginUpperLeft
+               OpName %main \"main\"
+               OpName %out_color \"out_color\"
+               OpDecorate %out_color Location 0
+       %void = OpTypeVoid
+          %5 = OpTypeFunction %void
+      %float = OpTypeFloat 32
+    %v4float = OpTypeVector %float 4
+%_ptr_Output_v4float = OpTypePointer Output %v4float
+       %uint = OpTypeInt 32 0
+     %uint_3 = OpConstant %uint 3
+  %float_0_5 = OpConstant %float 0.5
+        %int = OpTypeInt 32 1
+     %int_n1 = OpConstant %int -1
+  %out_color = OpVariable %_ptr_Output_v4float Output
+       %main = OpFunction %void None %5
+         %12 = OpLabel
+         %13 = OpExtInst %float %1 Sqrt %float_0_5
+         %14 = OpCompositeConstruct %v4float %13 %13 %13 %13
+               OpStore %out_color %14 Aligned 16
+               OpReturn
+               OpFunctionEnd
+";
+
+    #[test]
+    fn matches_spirv_dis() {
+        assert_eq!(disassemble(&fragment_module()).unwrap(), FRAGMENT_TEXT);
+    }
+
+    #[test]
+    fn round_trips_through_words() {
+        let module = fragment_module();
+        let words = module.to_words();
+        let decoded = Module::from_words(&words).unwrap();
+        assert_eq!(decoded, module);
+        assert_eq!(decoded.to_words(), words);
+        assert_eq!(disassemble_words(&words).unwrap(), FRAGMENT_TEXT);
+    }
+
+    /// Float constants of every width, including values that need `max_digits10`
+    /// digits to round trip and ones `spirv-dis` prints in hex.
+    fn constants_module() -> Module {
+        let constant = |ty, id, bits: u64| {
+            let mut operands = vec![ty, id, bits as u32];
+            if ty == 3 {
+                operands.push((bits >> 32) as u32);
+            }
+            Instruction::new(Op::Constant, operands)
+        };
+        test_module(
+            (1, 3),
+            16,
+            vec![
+                Instruction::new(Op::Capability, vec![1]),
+                Instruction::new(Op::Capability, vec![9]),
+                Instruction::new(Op::Capability, vec![10]),
+                Instruction::new(Op::MemoryModel, vec![0, 1]),
+                Instruction::new(Op::TypeFloat, vec![1, 16]),
+                Instruction::new(Op::TypeFloat, vec![2, 32]),
+                Instruction::new(Op::TypeFloat, vec![3, 64]),
+                constant(1, 4, 0x3c00),
+                constant(1, 5, 0x2e66),
+                constant(1, 6, 0x8000),
+                constant(2, 7, 0.1f32.to_bits().into()),
+                constant(2, 8, 1e20f32.to_bits().into()),
+                constant(2, 9, 1e-5f32.to_bits().into()),
+                constant(2, 10, 123_456_789f32.to_bits().into()),
+                constant(2, 11, (-0.0f32).to_bits().into()),
+                constant(2, 12, 1),
+                constant(3, 13, 0.1f64.to_bits()),
+                constant(3, 14, 1e300f64.to_bits()),
+                constant(3, 15, (2.0f64 / 3.0).to_bits()),
+            ],
+        )
+    }
+
+    /// `spirv-dis` output for [`constants_module`].
+    const CONSTANT_TEXT: &str = "\
+; SPIR-V
+; Version: 1.3
+; Generator: Khronos; 0
+; Bound: 16
+; Schema: 0
+               OpCapability Shader
+               OpCapability Float16
+               OpCapability Float64
+               OpMemoryModel Logical GLSL450
+       %half = OpTypeFloat 16
+      %float = OpTypeFloat 32
+     %double = OpTypeFloat 64
+%half_0x1p_0 = OpConstant %half 0x1p+0
+%half_0x1_998pn4 = OpConstant %half 0x1.998p-4
+%half_n0x0p_0 = OpConstant %half -0x0p+0
+%float_0_100000001 = OpConstant %float 0.100000001
+%float_1_00000002e_20 = OpConstant %float 1.00000002e+20
+%float_9_99999975en06 = OpConstant %float 9.99999975e-06
+%float_123456792 = OpConstant %float 123456792
+   %float_n0 = OpConstant %float -0
+%float_0x1pn149 = OpConstant %float 0x1p-149
+%double_0_10000000000000001 = OpConstant %double 0.10000000000000001
+%double_1_0000000000000001e_300 = OpConstant %double 1.0000000000000001e+300
+%double_0_66666666666666663 = OpConstant %double 0.66666666666666663
+";
+
+    #[test]
+    fn formats_floats_like_spirv_dis() {
+        let words = constants_module().to_words();
+        assert_eq!(Module::from_words(&words).unwrap(), constants_module());
+        assert_eq!(disassemble_words(&words).unwrap(), CONSTANT_TEXT);
+    }
+
+    #[test]
+    fn names_generators() {
+        let mut module = fragment_module();
+        module.header.generator = (27 << 16) | 1;
+        let text = disassemble(&module).unwrap();
+        assert!(text.contains("; Generator: Embark Studios Rust GPU Compiler Backend; 1\n"));
+
+        module.header.generator = (0xffff << 16) | 2;
+        let text = disassemble(&module).unwrap();
+        assert!(text.contains("; Generator: Unknown(65535); 2\n"));
+    }
+
+    #[test]
+    fn disambiguates_and_sanitizes_names() {
+        let module = test_module(
+            (1, 0),
+            5,
+            vec![
+                with_string(Op::Name, &[1], "light", &[]),
+                with_string(Op::Name, &[2], "light", &[]),
+                with_string(Op::Name, &[3], "0 \"odd\"", &[]),
+                Instruction::new(Op::TypeFloat, vec![4, 32]),
+                Instruction::new(Op::Undef, vec![4, 1]),
+                Instruction::new(Op::Undef, vec![4, 2]),
+                Instruction::new(Op::Undef, vec![4, 3]),
+            ],
+        );
+        let text = disassemble(&module).unwrap();
+        assert!(text.contains("               OpName %_0__odd_ \"0 \\\"odd\\\"\"\n"));
+        assert!(text.contains("      %light = OpUndef %float\n"));
+        assert!(text.contains("    %light_0 = OpUndef %float\n"));
+        assert!(text.contains("   %_0__odd_ = OpUndef %float\n"));
+    }
+
+    #[test]
+    fn formats_masks() {
+        assert_eq!(enum_name(OperandKind::FunctionControl, 0), "None");
+        assert_eq!(enum_name(OperandKind::FunctionControl, 0x5), "Inline|Pure");
+        assert_eq!(
+            enum_name(OperandKind::MemoryAccess, 0x3 | 0x4000_0000),
+            "Volatile|Aligned|0x40000000"
+        );
+        assert_eq!(enum_name(OperandKind::StorageClass, 12), "StorageBuffer");
+        assert_eq!(enum_name(OperandKind::Dim, 1), "2D");
+    }
+
+    #[test]
+    fn formats_special_floats() {
+        let module = test_module(
+            (1, 0),
+            5,
+            vec![
+                Instruction::new(Op::TypeFloat, vec![1, 32]),
+                Instruction::new(Op::Constant, vec![1, 2, f32::INFINITY.to_bits()]),
+                Instruction::new(Op::Constant, vec![1, 3, (-1.5f32).to_bits()]),
+                Instruction::new(Op::Constant, vec![1, 4, f32::NAN.to_bits()]),
+            ],
+        );
+        let text = disassemble(&module).unwrap();
+        assert!(text.contains("OpConstant %float 0x1p+128\n"));
+        assert!(text.contains("%float_n1_5 = OpConstant %float -1.5\n"));
+        assert!(text.contains("OpConstant %float 0x1.8p+128\n"));
+    }
+}
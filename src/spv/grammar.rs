@@ -0,0 +1,941 @@
+//! Operand layout of SPIR-V instructions and enumerants.
+//!
+//! Generated by `tools/gen-grammar` from the SPIR-V core grammar 1.6 revision 1,
+//! with `tools/gen-grammar/additions.json` applied; rerun it instead of
+//! editing this file. Capability and extension requirements live in
+//! [`crate::requirements`].
+
+/// Kind of a logical operand, as named by the SPIR-V grammar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[allow(dead_code)] // Some kinds only appear inside other kinds
+pub enum OperandKind {
+    ImageOperands,
+    FPFastMathMode,
+    SelectionControl,
+    LoopControl,
+    FunctionControl,
+    MemorySemantics,
+    MemoryAccess,
+    KernelProfilingInfo,
+    RayFlags,
+    FragmentShadingRate,
+    SourceLanguage,
+    ExecutionModel,
+    AddressingModel,
+    MemoryModel,
+    ExecutionMode,
+    StorageClass,
+    Dim,
+    SamplerAddressingMode,
+    SamplerFilterMode,
+    ImageFormat,
+    ImageChannelOrder,
+    ImageChannelDataType,
+    FPRoundingMode,
+    FPDenormMode,
+    QuantizationModes,
+    FPOperationMode,
+    OverflowModes,
+    LinkageType,
+    AccessQualifier,
+    FunctionParameterAttribute,
+    Decoration,
+    BuiltIn,
+    Scope,
+    GroupOperation,
+    KernelEnqueueFlags,
+    Capability,
+    RayQueryIntersection,
+    RayQueryCommittedIntersectionType,
+    RayQueryCandidateIntersectionType,
+    PackedVectorFormat,
+    CooperativeMatrixOperands,
+    IdResultType,
+    IdResult,
+    IdMemorySemantics,
+    IdScope,
+    IdRef,
+    LiteralInteger,
+    LiteralString,
+    LiteralContextDependentNumber,
+    LiteralExtInstInteger,
+    LiteralSpecConstantOpInteger,
+    PairLiteralIntegerIdRef,
+    PairIdRefLiteralInteger,
+    PairIdRefIdRef,
+}
+
+/// How many times a logical operand may appear.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantifier {
+    One,
+    ZeroOrOne,
+    ZeroOrMore,
+}
+
+/// Operand layout of a single opcode.
+#[derive(Debug)]
+pub struct InstructionGrammar {
+    pub opcode: u16,
+    /// Name without the `Op` prefix.
+    pub name: &'static str,
+    pub operands: &'static [(OperandKind, Quantifier)],
+}
+
+impl InstructionGrammar {
+    /// Whether the instruction produces a result id.
+    pub fn has_result(&self) -> bool {
+        self.operands
+            .iter()
+            .take(2)
+            .any(|(kind, _)| *kind == OperandKind::IdResult)
+    }
+
+    /// Whether the instruction has a result type id.
+    pub fn has_result_type(&self) -> bool {
+        self.operands
+            .first()
+            .is_some_and(|(kind, _)| *kind == OperandKind::IdResultType)
+    }
+}
+
+/// Operands that follow an enumerant of a kind, or a bit of a mask kind.
+type EnumerantParameters = (OperandKind, u32, &'static [(OperandKind, Quantifier)]);
+
+/// Looks up the operand layout of `opcode`.
+pub fn lookup(opcode: u16) -> Option<&'static InstructionGrammar> {
+    INSTRUCTIONS
+        .binary_search_by_key(&opcode, |inst| inst.opcode)
+        .ok()
+        .map(|index| &INSTRUCTIONS[index])
+}
+
+/// Operands that follow an enumerant, or a single bit of a mask.
+pub fn parameters(kind: OperandKind, value: u32) -> &'static [(OperandKind, Quantifier)] {
+    PARAMETERS
+        .binary_search_by_key(&(kind, value), |&(kind, value, _)| (kind, value))
+        .map_or(&[], |index| PARAMETERS[index].2)
+}
+
+/// Whether operands of the kind are masks, with a parameter list per set bit.
+pub fn is_mask(kind: OperandKind) -> bool {
+    mask_bits(kind).is_some()
+}
+
+macro_rules! op {
+    ($opcode:literal, $name:ident, [$( ($kind:ident, $quant:ident) ),*]) => {
+        InstructionGrammar {
+            opcode: $opcode,
+            name: stringify!($name),
+            operands: &[$( (OperandKind::$kind, Quantifier::$quant) ),*],
+        }
+    };
+}
+
+macro_rules! params {
+    ($kind:ident, $value:literal, [$( ($param:ident, $quant:ident) ),*]) => {
+        (
+            OperandKind::$kind,
+            $value,
+            &[$( (OperandKind::$param, Quantifier::$quant) ),*],
+        )
+    };
+}
+
+/// Names of the bits of each mask kind, without aliases.
+#[rustfmt::skip]
+pub fn mask_bits(kind: OperandKind) -> Option<&'static [(u32, &'static str)]> {
+    Some(match kind {
+        OperandKind::CooperativeMatrixOperands => &[(0x1, "MatrixASignedComponentsKHR"), (0x2, "MatrixBSignedComponentsKHR"), (0x4, "MatrixCSignedComponentsKHR"), (0x8, "MatrixResultSignedComponentsKHR"), (0x10, "SaturatingAccumulationKHR")],
+        OperandKind::FPFastMathMode => &[(0x1, "NotNaN"), (0x2, "NotInf"), (0x4, "NSZ"), (0x8, "AllowRecip"), (0x10, "Fast"), (0x10000, "AllowContract"), (0x20000, "AllowReassoc"), (0x40000, "AllowTransform")],
+        OperandKind::FragmentShadingRate => &[(0x1, "Vertical2Pixels"), (0x2, "Vertical4Pixels"), (0x4, "Horizontal2Pixels"), (0x8, "Horizontal4Pixels")],
+        OperandKind::FunctionControl => &[(0x1, "Inline"), (0x2, "DontInline"), (0x4, "Pure"), (0x8, "Const"), (0x10000, "OptNoneINTEL")],
+        OperandKind::ImageOperands => &[(0x1, "Bias"), (0x2, "Lod"), (0x4, "Grad"), (0x8, "ConstOffset"), (0x10, "Offset"), (0x20, "ConstOffsets"), (0x40, "Sample"), (0x80, "MinLod"), (0x100, "MakeTexelAvailable"), (0x200, "MakeTexelVisible"), (0x400, "NonPrivateTexel"), (0x800, "VolatileTexel"), (0x1000, "SignExtend"), (0x2000, "ZeroExtend"), (0x4000, "Nontemporal"), (0x10000, "Offsets")],
+        OperandKind::KernelProfilingInfo => &[(0x1, "CmdExecTime")],
+        OperandKind::LoopControl => &[(0x1, "Unroll"), (0x2, "DontUnroll"), (0x4, "DependencyInfinite"), (0x8, "DependencyLength"), (0x10, "MinIterations"), (0x20, "MaxIterations"), (0x40, "IterationMultiple"), (0x80, "PeelCount"), (0x100, "PartialCount"), (0x10000, "InitiationIntervalINTEL"), (0x20000, "MaxConcurrencyINTEL"), (0x40000, "DependencyArrayINTEL"), (0x80000, "PipelineEnableINTEL"), (0x100000, "LoopCoalesceINTEL"), (0x200000, "MaxInterleavingINTEL"), (0x400000, "SpeculatedIterationsINTEL"), (0x800000, "NoFusionINTEL")],
+        OperandKind::MemoryAccess => &[(0x1, "Volatile"), (0x2, "Aligned"), (0x4, "Nontemporal"), (0x8, "MakePointerAvailable"), (0x10, "MakePointerVisible"), (0x20, "NonPrivatePointer"), (0x10000, "AliasScopeINTELMask"), (0x20000, "NoAliasINTELMask")],
+        OperandKind::MemorySemantics => &[(0x2, "Acquire"), (0x4, "Release"), (0x8, "AcquireRelease"), (0x10, "SequentiallyConsistent"), (0x40, "UniformMemory"), (0x80, "SubgroupMemory"), (0x100, "WorkgroupMemory"), (0x200, "CrossWorkgroupMemory"), (0x400, "AtomicCounterMemory"), (0x800, "ImageMemory"), (0x1000, "OutputMemory"), (0x2000, "MakeAvailable"), (0x4000, "MakeVisible"), (0x8000, "Volatile")],
+        OperandKind::RayFlags => &[(0x1, "OpaqueKHR"), (0x2, "NoOpaqueKHR"), (0x4, "TerminateOnFirstHitKHR"), (0x8, "SkipClosestHitShaderKHR"), (0x10, "CullBackFacingTrianglesKHR"), (0x20, "CullFrontFacingTrianglesKHR"), (0x40, "CullOpaqueKHR"), (0x80, "CullNoOpaqueKHR"), (0x100, "SkipTrianglesKHR"), (0x200, "SkipAABBsKHR"), (0x400, "ForceOpacityMicromap2StateEXT")],
+        OperandKind::SelectionControl => &[(0x1, "Flatten"), (0x2, "DontFlatten")],
+        _ => return None,
+    })
+}
+
+// Sorted by opcode.
+#[rustfmt::skip]
+static INSTRUCTIONS: &[InstructionGrammar] = &[
+    op!(0, Nop, []),
+    op!(1, Undef, [(IdResultType, One), (IdResult, One)]),
+    op!(2, SourceContinued, [(LiteralString, One)]),
+    op!(3, Source, [(SourceLanguage, One), (LiteralInteger, One), (IdRef, ZeroOrOne), (LiteralString, ZeroOrOne)]),
+    op!(4, SourceExtension, [(LiteralString, One)]),
+    op!(5, Name, [(IdRef, One), (LiteralString, One)]),
+    op!(6, MemberName, [(IdRef, One), (LiteralInteger, One), (LiteralString, One)]),
+    op!(7, String, [(IdResult, One), (LiteralString, One)]),
+    op!(8, Line, [(IdRef, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(10, Extension, [(LiteralString, One)]),
+    op!(11, ExtInstImport, [(IdResult, One), (LiteralString, One)]),
+    op!(12, ExtInst, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralExtInstInteger, One), (IdRef, ZeroOrMore)]),
+    op!(14, MemoryModel, [(AddressingModel, One), (MemoryModel, One)]),
+    op!(15, EntryPoint, [(ExecutionModel, One), (IdRef, One), (LiteralString, One), (IdRef, ZeroOrMore)]),
+    op!(16, ExecutionMode, [(IdRef, One), (ExecutionMode, One)]),
+    op!(17, Capability, [(Capability, One)]),
+    op!(19, TypeVoid, [(IdResult, One)]),
+    op!(20, TypeBool, [(IdResult, One)]),
+    op!(21, TypeInt, [(IdResult, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(22, TypeFloat, [(IdResult, One), (LiteralInteger, One)]),
+    op!(23, TypeVector, [(IdResult, One), (IdRef, One), (LiteralInteger, One)]),
+    op!(24, TypeMatrix, [(IdResult, One), (IdRef, One), (LiteralInteger, One)]),
+    op!(25, TypeImage, [(IdResult, One), (IdRef, One), (Dim, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (ImageFormat, One), (AccessQualifier, ZeroOrOne)]),
+    op!(26, TypeSampler, [(IdResult, One)]),
+    op!(27, TypeSampledImage, [(IdResult, One), (IdRef, One)]),
+    op!(28, TypeArray, [(IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(29, TypeRuntimeArray, [(IdResult, One), (IdRef, One)]),
+    op!(30, TypeStruct, [(IdResult, One), (IdRef, ZeroOrMore)]),
+    op!(31, TypeOpaque, [(IdResult, One), (LiteralString, One)]),
+    op!(32, TypePointer, [(IdResult, One), (StorageClass, One), (IdRef, One)]),
+    op!(33, TypeFunction, [(IdResult, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(34, TypeEvent, [(IdResult, One)]),
+    op!(35, TypeDeviceEvent, [(IdResult, One)]),
+    op!(36, TypeReserveId, [(IdResult, One)]),
+    op!(37, TypeQueue, [(IdResult, One)]),
+    op!(38, TypePipe, [(IdResult, One), (AccessQualifier, One)]),
+    op!(39, TypeForwardPointer, [(IdRef, One), (StorageClass, One)]),
+    op!(41, ConstantTrue, [(IdResultType, One), (IdResult, One)]),
+    op!(42, ConstantFalse, [(IdResultType, One), (IdResult, One)]),
+    op!(43, Constant, [(IdResultType, One), (IdResult, One), (LiteralContextDependentNumber, One)]),
+    op!(44, ConstantComposite, [(IdResultType, One), (IdResult, One), (IdRef, ZeroOrMore)]),
+    op!(45, ConstantSampler, [(IdResultType, One), (IdResult, One), (SamplerAddressingMode, One), (LiteralInteger, One), (SamplerFilterMode, One)]),
+    op!(46, ConstantNull, [(IdResultType, One), (IdResult, One)]),
+    op!(48, SpecConstantTrue, [(IdResultType, One), (IdResult, One)]),
+    op!(49, SpecConstantFalse, [(IdResultType, One), (IdResult, One)]),
+    op!(50, SpecConstant, [(IdResultType, One), (IdResult, One), (LiteralContextDependentNumber, One)]),
+    op!(51, SpecConstantComposite, [(IdResultType, One), (IdResult, One), (IdRef, ZeroOrMore)]),
+    op!(52, SpecConstantOp, [(IdResultType, One), (IdResult, One), (LiteralSpecConstantOpInteger, One)]),
+    op!(54, Function, [(IdResultType, One), (IdResult, One), (FunctionControl, One), (IdRef, One)]),
+    op!(55, FunctionParameter, [(IdResultType, One), (IdResult, One)]),
+    op!(56, FunctionEnd, []),
+    op!(57, FunctionCall, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(59, Variable, [(IdResultType, One), (IdResult, One), (StorageClass, One), (IdRef, ZeroOrOne)]),
+    op!(60, ImageTexelPointer, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(61, Load, [(IdResultType, One), (IdResult, One), (IdRef, One), (MemoryAccess, ZeroOrOne)]),
+    op!(62, Store, [(IdRef, One), (IdRef, One), (MemoryAccess, ZeroOrOne)]),
+    op!(63, CopyMemory, [(IdRef, One), (IdRef, One), (MemoryAccess, ZeroOrOne), (MemoryAccess, ZeroOrOne)]),
+    op!(64, CopyMemorySized, [(IdRef, One), (IdRef, One), (IdRef, One), (MemoryAccess, ZeroOrOne), (MemoryAccess, ZeroOrOne)]),
+    op!(65, AccessChain, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(66, InBoundsAccessChain, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(67, PtrAccessChain, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(68, ArrayLength, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One)]),
+    op!(69, GenericPtrMemSemantics, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(70, InBoundsPtrAccessChain, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(71, Decorate, [(IdRef, One), (Decoration, One)]),
+    op!(72, MemberDecorate, [(IdRef, One), (LiteralInteger, One), (Decoration, One)]),
+    op!(73, DecorationGroup, [(IdResult, One)]),
+    op!(74, GroupDecorate, [(IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(75, GroupMemberDecorate, [(IdRef, One), (PairIdRefLiteralInteger, ZeroOrMore)]),
+    op!(77, VectorExtractDynamic, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(78, VectorInsertDynamic, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(79, VectorShuffle, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, ZeroOrMore)]),
+    op!(80, CompositeConstruct, [(IdResultType, One), (IdResult, One), (IdRef, ZeroOrMore)]),
+    op!(81, CompositeExtract, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, ZeroOrMore)]),
+    op!(82, CompositeInsert, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, ZeroOrMore)]),
+    op!(83, CopyObject, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(84, Transpose, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(86, SampledImage, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(87, ImageSampleImplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(88, ImageSampleExplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, One)]),
+    op!(89, ImageSampleDrefImplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(90, ImageSampleDrefExplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, One)]),
+    op!(91, ImageSampleProjImplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(92, ImageSampleProjExplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, One)]),
+    op!(93, ImageSampleProjDrefImplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(94, ImageSampleProjDrefExplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, One)]),
+    op!(95, ImageFetch, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(96, ImageGather, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(97, ImageDrefGather, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(98, ImageRead, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(99, ImageWrite, [(IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(100, Image, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(101, ImageQueryFormat, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(102, ImageQueryOrder, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(103, ImageQuerySizeLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(104, ImageQuerySize, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(105, ImageQueryLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(106, ImageQueryLevels, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(107, ImageQuerySamples, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(109, ConvertFToU, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(110, ConvertFToS, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(111, ConvertSToF, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(112, ConvertUToF, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(113, UConvert, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(114, SConvert, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(115, FConvert, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(116, QuantizeToF16, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(117, ConvertPtrToU, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(118, SatConvertSToU, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(119, SatConvertUToS, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(120, ConvertUToPtr, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(121, PtrCastToGeneric, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(122, GenericCastToPtr, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(123, GenericCastToPtrExplicit, [(IdResultType, One), (IdResult, One), (IdRef, One), (StorageClass, One)]),
+    op!(124, Bitcast, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(126, SNegate, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(127, FNegate, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(128, IAdd, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(129, FAdd, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(130, ISub, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(131, FSub, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(132, IMul, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(133, FMul, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(134, UDiv, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(135, SDiv, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(136, FDiv, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(137, UMod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(138, SRem, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(139, SMod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(140, FRem, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(141, FMod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(142, VectorTimesScalar, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(143, MatrixTimesScalar, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(144, VectorTimesMatrix, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(145, MatrixTimesVector, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(146, MatrixTimesMatrix, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(147, OuterProduct, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(148, Dot, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(149, IAddCarry, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(150, ISubBorrow, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(151, UMulExtended, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(152, SMulExtended, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(154, Any, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(155, All, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(156, IsNan, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(157, IsInf, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(158, IsFinite, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(159, IsNormal, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(160, SignBitSet, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(161, LessOrGreater, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(162, Ordered, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(163, Unordered, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(164, LogicalEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(165, LogicalNotEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(166, LogicalOr, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(167, LogicalAnd, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(168, LogicalNot, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(169, Select, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(170, IEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(171, INotEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(172, UGreaterThan, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(173, SGreaterThan, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(174, UGreaterThanEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(175, SGreaterThanEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(176, ULessThan, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(177, SLessThan, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(178, ULessThanEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(179, SLessThanEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(180, FOrdEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(181, FUnordEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(182, FOrdNotEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(183, FUnordNotEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(184, FOrdLessThan, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(185, FUnordLessThan, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(186, FOrdGreaterThan, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(187, FUnordGreaterThan, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(188, FOrdLessThanEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(189, FUnordLessThanEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(190, FOrdGreaterThanEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(191, FUnordGreaterThanEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(194, ShiftRightLogical, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(195, ShiftRightArithmetic, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(196, ShiftLeftLogical, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(197, BitwiseOr, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(198, BitwiseXor, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(199, BitwiseAnd, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(200, Not, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(201, BitFieldInsert, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(202, BitFieldSExtract, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(203, BitFieldUExtract, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(204, BitReverse, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(205, BitCount, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(207, DPdx, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(208, DPdy, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(209, Fwidth, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(210, DPdxFine, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(211, DPdyFine, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(212, FwidthFine, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(213, DPdxCoarse, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(214, DPdyCoarse, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(215, FwidthCoarse, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(218, EmitVertex, []),
+    op!(219, EndPrimitive, []),
+    op!(220, EmitStreamVertex, [(IdRef, One)]),
+    op!(221, EndStreamPrimitive, [(IdRef, One)]),
+    op!(224, ControlBarrier, [(IdScope, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(225, MemoryBarrier, [(IdScope, One), (IdMemorySemantics, One)]),
+    op!(227, AtomicLoad, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(228, AtomicStore, [(IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(229, AtomicExchange, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(230, AtomicCompareExchange, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdMemorySemantics, One), (IdRef, One), (IdRef, One)]),
+    op!(231, AtomicCompareExchangeWeak, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdMemorySemantics, One), (IdRef, One), (IdRef, One)]),
+    op!(232, AtomicIIncrement, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(233, AtomicIDecrement, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(234, AtomicIAdd, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(235, AtomicISub, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(236, AtomicSMin, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(237, AtomicUMin, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(238, AtomicSMax, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(239, AtomicUMax, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(240, AtomicAnd, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(241, AtomicOr, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(242, AtomicXor, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(245, Phi, [(IdResultType, One), (IdResult, One), (PairIdRefIdRef, ZeroOrMore)]),
+    op!(246, LoopMerge, [(IdRef, One), (IdRef, One), (LoopControl, One)]),
+    op!(247, SelectionMerge, [(IdRef, One), (SelectionControl, One)]),
+    op!(248, Label, [(IdResult, One)]),
+    op!(249, Branch, [(IdRef, One)]),
+    op!(250, BranchConditional, [(IdRef, One), (IdRef, One), (IdRef, One), (LiteralInteger, ZeroOrMore)]),
+    op!(251, Switch, [(IdRef, One), (IdRef, One), (PairLiteralIntegerIdRef, ZeroOrMore)]),
+    op!(252, Kill, []),
+    op!(253, Return, []),
+    op!(254, ReturnValue, [(IdRef, One)]),
+    op!(255, Unreachable, []),
+    op!(256, LifetimeStart, [(IdRef, One), (LiteralInteger, One)]),
+    op!(257, LifetimeStop, [(IdRef, One), (LiteralInteger, One)]),
+    op!(259, GroupAsyncCopy, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(260, GroupWaitEvents, [(IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(261, GroupAll, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(262, GroupAny, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(263, GroupBroadcast, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(264, GroupIAdd, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(265, GroupFAdd, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(266, GroupFMin, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(267, GroupUMin, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(268, GroupSMin, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(269, GroupFMax, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(270, GroupUMax, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(271, GroupSMax, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(274, ReadPipe, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(275, WritePipe, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(276, ReservedReadPipe, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(277, ReservedWritePipe, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(278, ReserveReadPipePackets, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(279, ReserveWritePipePackets, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(280, CommitReadPipe, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(281, CommitWritePipe, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(282, IsValidReserveId, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(283, GetNumPipePackets, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(284, GetMaxPipePackets, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(285, GroupReserveReadPipePackets, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(286, GroupReserveWritePipePackets, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(287, GroupCommitReadPipe, [(IdScope, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(288, GroupCommitWritePipe, [(IdScope, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(291, EnqueueMarker, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(292, EnqueueKernel, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(293, GetKernelNDrangeSubGroupCount, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(294, GetKernelNDrangeMaxSubGroupSize, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(295, GetKernelWorkGroupSize, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(296, GetKernelPreferredWorkGroupSizeMultiple, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(297, RetainEvent, [(IdRef, One)]),
+    op!(298, ReleaseEvent, [(IdRef, One)]),
+    op!(299, CreateUserEvent, [(IdResultType, One), (IdResult, One)]),
+    op!(300, IsValidEvent, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(301, SetUserEventStatus, [(IdRef, One), (IdRef, One)]),
+    op!(302, CaptureEventProfilingInfo, [(IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(303, GetDefaultQueue, [(IdResultType, One), (IdResult, One)]),
+    op!(304, BuildNDRange, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(305, ImageSparseSampleImplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(306, ImageSparseSampleExplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, One)]),
+    op!(307, ImageSparseSampleDrefImplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(308, ImageSparseSampleDrefExplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, One)]),
+    op!(309, ImageSparseSampleProjImplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(310, ImageSparseSampleProjExplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, One)]),
+    op!(311, ImageSparseSampleProjDrefImplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(312, ImageSparseSampleProjDrefExplicitLod, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, One)]),
+    op!(313, ImageSparseFetch, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(314, ImageSparseGather, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(315, ImageSparseDrefGather, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(316, ImageSparseTexelsResident, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(317, NoLine, []),
+    op!(318, AtomicFlagTestAndSet, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(319, AtomicFlagClear, [(IdRef, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(320, ImageSparseRead, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(321, SizeOf, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(322, TypePipeStorage, [(IdResult, One)]),
+    op!(323, ConstantPipeStorage, [(IdResultType, One), (IdResult, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(324, CreatePipeFromPipeStorage, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(325, GetKernelLocalSizeForSubgroupCount, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(326, GetKernelMaxNumSubgroups, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(327, TypeNamedBarrier, [(IdResult, One)]),
+    op!(328, NamedBarrierInitialize, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(329, MemoryNamedBarrier, [(IdRef, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(330, ModuleProcessed, [(LiteralString, One)]),
+    op!(331, ExecutionModeId, [(IdRef, One), (ExecutionMode, One)]),
+    op!(332, DecorateId, [(IdRef, One), (Decoration, One)]),
+    op!(333, GroupNonUniformElect, [(IdResultType, One), (IdResult, One), (IdScope, One)]),
+    op!(334, GroupNonUniformAll, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(335, GroupNonUniformAny, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(336, GroupNonUniformAllEqual, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(337, GroupNonUniformBroadcast, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(338, GroupNonUniformBroadcastFirst, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(339, GroupNonUniformBallot, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(340, GroupNonUniformInverseBallot, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(341, GroupNonUniformBallotBitExtract, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(342, GroupNonUniformBallotBitCount, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(343, GroupNonUniformBallotFindLSB, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(344, GroupNonUniformBallotFindMSB, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One)]),
+    op!(345, GroupNonUniformShuffle, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(346, GroupNonUniformShuffleXor, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(347, GroupNonUniformShuffleUp, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(348, GroupNonUniformShuffleDown, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(349, GroupNonUniformIAdd, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(350, GroupNonUniformFAdd, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(351, GroupNonUniformIMul, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(352, GroupNonUniformFMul, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(353, GroupNonUniformSMin, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(354, GroupNonUniformUMin, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(355, GroupNonUniformFMin, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(356, GroupNonUniformSMax, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(357, GroupNonUniformUMax, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(358, GroupNonUniformFMax, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(359, GroupNonUniformBitwiseAnd, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(360, GroupNonUniformBitwiseOr, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(361, GroupNonUniformBitwiseXor, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(362, GroupNonUniformLogicalAnd, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(363, GroupNonUniformLogicalOr, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(364, GroupNonUniformLogicalXor, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(365, GroupNonUniformQuadBroadcast, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(366, GroupNonUniformQuadSwap, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(400, CopyLogical, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(401, PtrEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(402, PtrNotEqual, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(403, PtrDiff, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(4416, TerminateInvocation, []),
+    op!(4421, SubgroupBallotKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(4422, SubgroupFirstInvocationKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(4428, SubgroupAllKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(4429, SubgroupAnyKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(4430, SubgroupAllEqualKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(4431, GroupNonUniformRotateKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(4432, SubgroupReadInvocationKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(4445, TraceRayKHR, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(4446, ExecuteCallableKHR, [(IdRef, One), (IdRef, One)]),
+    op!(4447, ConvertUToAccelerationStructureKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(4448, IgnoreIntersectionKHR, []),
+    op!(4449, TerminateRayKHR, []),
+    op!(4450, SDot, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (PackedVectorFormat, ZeroOrOne)]),
+    op!(4451, UDot, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (PackedVectorFormat, ZeroOrOne)]),
+    op!(4452, SUDot, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (PackedVectorFormat, ZeroOrOne)]),
+    op!(4453, SDotAccSat, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (PackedVectorFormat, ZeroOrOne)]),
+    op!(4454, UDotAccSat, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (PackedVectorFormat, ZeroOrOne)]),
+    op!(4455, SUDotAccSat, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (PackedVectorFormat, ZeroOrOne)]),
+    op!(4456, TypeCooperativeMatrixKHR, [(IdResult, One), (IdRef, One), (IdScope, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(4457, CooperativeMatrixLoadKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne), (MemoryAccess, ZeroOrOne)]),
+    op!(4458, CooperativeMatrixStoreKHR, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne), (MemoryAccess, ZeroOrOne)]),
+    op!(4459, CooperativeMatrixMulAddKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (CooperativeMatrixOperands, ZeroOrOne)]),
+    op!(4460, CooperativeMatrixLengthKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(4472, TypeRayQueryKHR, [(IdResult, One)]),
+    op!(4473, RayQueryInitializeKHR, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(4474, RayQueryTerminateKHR, [(IdRef, One)]),
+    op!(4475, RayQueryGenerateIntersectionKHR, [(IdRef, One), (IdRef, One)]),
+    op!(4476, RayQueryConfirmIntersectionKHR, [(IdRef, One)]),
+    op!(4477, RayQueryProceedKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(4479, RayQueryGetIntersectionTypeKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5000, GroupIAddNonUniformAMD, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(5001, GroupFAddNonUniformAMD, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(5002, GroupFMinNonUniformAMD, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(5003, GroupUMinNonUniformAMD, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(5004, GroupSMinNonUniformAMD, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(5005, GroupFMaxNonUniformAMD, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(5006, GroupUMaxNonUniformAMD, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(5007, GroupSMaxNonUniformAMD, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(5011, FragmentMaskFetchAMD, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5012, FragmentFetchAMD, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5056, ReadClockKHR, [(IdResultType, One), (IdResult, One), (IdScope, One)]),
+    op!(5283, ImageSampleFootprintNV, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (ImageOperands, ZeroOrOne)]),
+    op!(5294, EmitMeshTasksEXT, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(5295, SetMeshOutputsEXT, [(IdRef, One), (IdRef, One)]),
+    op!(5296, GroupNonUniformPartitionNV, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5299, WritePackedPrimitiveIndices4x8NV, [(IdRef, One), (IdRef, One)]),
+    op!(5334, ReportIntersectionKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5335, IgnoreIntersectionNV, []),
+    op!(5336, TerminateRayNV, []),
+    op!(5337, TraceNV, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5338, TraceMotionNV, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5339, TraceRayMotionNV, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5340, RayQueryGetIntersectionTriangleVertexPositionsKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5341, TypeAccelerationStructureKHR, [(IdResult, One)]),
+    op!(5344, ExecuteCallableNV, [(IdRef, One), (IdRef, One)]),
+    op!(5358, TypeCooperativeMatrixNV, [(IdResult, One), (IdRef, One), (IdScope, One), (IdRef, One), (IdRef, One)]),
+    op!(5359, CooperativeMatrixLoadNV, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (MemoryAccess, ZeroOrOne)]),
+    op!(5360, CooperativeMatrixStoreNV, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (MemoryAccess, ZeroOrOne)]),
+    op!(5361, CooperativeMatrixMulAddNV, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5362, CooperativeMatrixLengthNV, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5364, BeginInvocationInterlockEXT, []),
+    op!(5365, EndInvocationInterlockEXT, []),
+    op!(5380, DemoteToHelperInvocation, []),
+    op!(5381, IsHelperInvocationEXT, [(IdResultType, One), (IdResult, One)]),
+    op!(5391, ConvertUToImageNV, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5392, ConvertUToSamplerNV, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5393, ConvertImageToUNV, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5394, ConvertSamplerToUNV, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5395, ConvertUToSampledImageNV, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5396, ConvertSampledImageToUNV, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5397, SamplerImageAddressingModeNV, [(LiteralInteger, One)]),
+    op!(5571, SubgroupShuffleINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5572, SubgroupShuffleDownINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5573, SubgroupShuffleUpINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5574, SubgroupShuffleXorINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5575, SubgroupBlockReadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5576, SubgroupBlockWriteINTEL, [(IdRef, One), (IdRef, One)]),
+    op!(5577, SubgroupImageBlockReadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5578, SubgroupImageBlockWriteINTEL, [(IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5580, SubgroupImageMediaBlockReadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5581, SubgroupImageMediaBlockWriteINTEL, [(IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5585, UCountLeadingZerosINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5586, UCountTrailingZerosINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5587, AbsISubINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5588, AbsUSubINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5589, IAddSatINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5590, UAddSatINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5591, IAverageINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5592, UAverageINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5593, IAverageRoundedINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5594, UAverageRoundedINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5595, ISubSatINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5596, USubSatINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5597, IMul32x16INTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5598, UMul32x16INTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5600, ConstantFunctionPointerINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5601, FunctionPointerCallINTEL, [(IdResultType, One), (IdResult, One), (IdRef, ZeroOrMore)]),
+    op!(5609, AsmTargetINTEL, [(IdResultType, One), (IdResult, One), (LiteralString, One)]),
+    op!(5610, AsmINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralString, One), (LiteralString, One)]),
+    op!(5611, AsmCallINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, ZeroOrMore)]),
+    op!(5614, AtomicFMinEXT, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(5615, AtomicFMaxEXT, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(5630, AssumeTrueKHR, [(IdRef, One)]),
+    op!(5631, ExpectKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5632, DecorateString, [(IdRef, One), (Decoration, One)]),
+    op!(5633, MemberDecorateString, [(IdRef, One), (LiteralInteger, One), (Decoration, One)]),
+    op!(5699, VmeImageINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5700, TypeVmeImageINTEL, [(IdResult, One), (IdRef, One)]),
+    op!(5701, TypeAvcImePayloadINTEL, [(IdResult, One)]),
+    op!(5702, TypeAvcRefPayloadINTEL, [(IdResult, One)]),
+    op!(5703, TypeAvcSicPayloadINTEL, [(IdResult, One)]),
+    op!(5704, TypeAvcMcePayloadINTEL, [(IdResult, One)]),
+    op!(5705, TypeAvcMceResultINTEL, [(IdResult, One)]),
+    op!(5706, TypeAvcImeResultINTEL, [(IdResult, One)]),
+    op!(5707, TypeAvcImeResultSingleReferenceStreamoutINTEL, [(IdResult, One)]),
+    op!(5708, TypeAvcImeResultDualReferenceStreamoutINTEL, [(IdResult, One)]),
+    op!(5709, TypeAvcImeSingleReferenceStreaminINTEL, [(IdResult, One)]),
+    op!(5710, TypeAvcImeDualReferenceStreaminINTEL, [(IdResult, One)]),
+    op!(5711, TypeAvcRefResultINTEL, [(IdResult, One)]),
+    op!(5712, TypeAvcSicResultINTEL, [(IdResult, One)]),
+    op!(5713, SubgroupAvcMceGetDefaultInterBaseMultiReferencePenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5714, SubgroupAvcMceSetInterBaseMultiReferencePenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5715, SubgroupAvcMceGetDefaultInterShapePenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5716, SubgroupAvcMceSetInterShapePenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5717, SubgroupAvcMceGetDefaultInterDirectionPenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5718, SubgroupAvcMceSetInterDirectionPenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5719, SubgroupAvcMceGetDefaultIntraLumaShapePenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5720, SubgroupAvcMceGetDefaultInterMotionVectorCostTableINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5721, SubgroupAvcMceGetDefaultHighPenaltyCostTableINTEL, [(IdResultType, One), (IdResult, One)]),
+    op!(5722, SubgroupAvcMceGetDefaultMediumPenaltyCostTableINTEL, [(IdResultType, One), (IdResult, One)]),
+    op!(5723, SubgroupAvcMceGetDefaultLowPenaltyCostTableINTEL, [(IdResultType, One), (IdResult, One)]),
+    op!(5724, SubgroupAvcMceSetMotionVectorCostFunctionINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5725, SubgroupAvcMceGetDefaultIntraLumaModePenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5726, SubgroupAvcMceGetDefaultNonDcLumaIntraPenaltyINTEL, [(IdResultType, One), (IdResult, One)]),
+    op!(5727, SubgroupAvcMceGetDefaultIntraChromaModeBasePenaltyINTEL, [(IdResultType, One), (IdResult, One)]),
+    op!(5728, SubgroupAvcMceSetAcOnlyHaarINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5729, SubgroupAvcMceSetSourceInterlacedFieldPolarityINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5730, SubgroupAvcMceSetSingleReferenceInterlacedFieldPolarityINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5731, SubgroupAvcMceSetDualReferenceInterlacedFieldPolaritiesINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5732, SubgroupAvcMceConvertToImePayloadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5733, SubgroupAvcMceConvertToImeResultINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5734, SubgroupAvcMceConvertToRefPayloadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5735, SubgroupAvcMceConvertToRefResultINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5736, SubgroupAvcMceConvertToSicPayloadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5737, SubgroupAvcMceConvertToSicResultINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5738, SubgroupAvcMceGetMotionVectorsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5739, SubgroupAvcMceGetInterDistortionsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5740, SubgroupAvcMceGetBestInterDistortionsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5741, SubgroupAvcMceGetInterMajorShapeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5742, SubgroupAvcMceGetInterMinorShapeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5743, SubgroupAvcMceGetInterDirectionsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5744, SubgroupAvcMceGetInterMotionVectorCountINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5745, SubgroupAvcMceGetInterReferenceIdsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5746, SubgroupAvcMceGetInterReferenceInterlacedFieldPolaritiesINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5747, SubgroupAvcImeInitializeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5748, SubgroupAvcImeSetSingleReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5749, SubgroupAvcImeSetDualReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5750, SubgroupAvcImeRefWindowSizeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5751, SubgroupAvcImeAdjustRefOffsetINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5752, SubgroupAvcImeConvertToMcePayloadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5753, SubgroupAvcImeSetMaxMotionVectorCountINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5754, SubgroupAvcImeSetUnidirectionalMixDisableINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5755, SubgroupAvcImeSetEarlySearchTerminationThresholdINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5756, SubgroupAvcImeSetWeightedSadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5757, SubgroupAvcImeEvaluateWithSingleReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5758, SubgroupAvcImeEvaluateWithDualReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5759, SubgroupAvcImeEvaluateWithSingleReferenceStreaminINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5760, SubgroupAvcImeEvaluateWithDualReferenceStreaminINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5761, SubgroupAvcImeEvaluateWithSingleReferenceStreamoutINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5762, SubgroupAvcImeEvaluateWithDualReferenceStreamoutINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5763, SubgroupAvcImeEvaluateWithSingleReferenceStreaminoutINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5764, SubgroupAvcImeEvaluateWithDualReferenceStreaminoutINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5765, SubgroupAvcImeConvertToMceResultINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5766, SubgroupAvcImeGetSingleReferenceStreaminINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5767, SubgroupAvcImeGetDualReferenceStreaminINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5768, SubgroupAvcImeStripSingleReferenceStreamoutINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5769, SubgroupAvcImeStripDualReferenceStreamoutINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5770, SubgroupAvcImeGetStreamoutSingleReferenceMajorShapeMotionVectorsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5771, SubgroupAvcImeGetStreamoutSingleReferenceMajorShapeDistortionsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5772, SubgroupAvcImeGetStreamoutSingleReferenceMajorShapeReferenceIdsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5773, SubgroupAvcImeGetStreamoutDualReferenceMajorShapeMotionVectorsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5774, SubgroupAvcImeGetStreamoutDualReferenceMajorShapeDistortionsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5775, SubgroupAvcImeGetStreamoutDualReferenceMajorShapeReferenceIdsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5776, SubgroupAvcImeGetBorderReachedINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5777, SubgroupAvcImeGetTruncatedSearchIndicationINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5778, SubgroupAvcImeGetUnidirectionalEarlySearchTerminationINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5779, SubgroupAvcImeGetWeightingPatternMinimumMotionVectorINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5780, SubgroupAvcImeGetWeightingPatternMinimumDistortionINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5781, SubgroupAvcFmeInitializeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5782, SubgroupAvcBmeInitializeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5783, SubgroupAvcRefConvertToMcePayloadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5784, SubgroupAvcRefSetBidirectionalMixDisableINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5785, SubgroupAvcRefSetBilinearFilterEnableINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5786, SubgroupAvcRefEvaluateWithSingleReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5787, SubgroupAvcRefEvaluateWithDualReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5788, SubgroupAvcRefEvaluateWithMultiReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5789, SubgroupAvcRefEvaluateWithMultiReferenceInterlacedINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5790, SubgroupAvcRefConvertToMceResultINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5791, SubgroupAvcSicInitializeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5792, SubgroupAvcSicConfigureSkcINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5793, SubgroupAvcSicConfigureIpeLumaINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5794, SubgroupAvcSicConfigureIpeLumaChromaINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5795, SubgroupAvcSicGetMotionVectorMaskINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5796, SubgroupAvcSicConvertToMcePayloadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5797, SubgroupAvcSicSetIntraLumaShapePenaltyINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5798, SubgroupAvcSicSetIntraLumaModeCostFunctionINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5799, SubgroupAvcSicSetIntraChromaModeCostFunctionINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5800, SubgroupAvcSicSetBilinearFilterEnableINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5801, SubgroupAvcSicSetSkcForwardTransformEnableINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5802, SubgroupAvcSicSetBlockBasedRawSkipSadINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5803, SubgroupAvcSicEvaluateIpeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5804, SubgroupAvcSicEvaluateWithSingleReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5805, SubgroupAvcSicEvaluateWithDualReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5806, SubgroupAvcSicEvaluateWithMultiReferenceINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5807, SubgroupAvcSicEvaluateWithMultiReferenceInterlacedINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (IdRef, One), (IdRef, One)]),
+    op!(5808, SubgroupAvcSicConvertToMceResultINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5809, SubgroupAvcSicGetIpeLumaShapeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5810, SubgroupAvcSicGetBestIpeLumaDistortionINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5811, SubgroupAvcSicGetBestIpeChromaDistortionINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5812, SubgroupAvcSicGetPackedIpeLumaModesINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5813, SubgroupAvcSicGetIpeChromaModeINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5814, SubgroupAvcSicGetPackedSkcLumaCountThresholdINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5815, SubgroupAvcSicGetPackedSkcLumaSumThresholdINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5816, SubgroupAvcSicGetInterRawSadsINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5818, VariableLengthArrayINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5819, SaveMemoryINTEL, [(IdResultType, One), (IdResult, One)]),
+    op!(5820, RestoreMemoryINTEL, [(IdRef, One)]),
+    op!(5840, ArbitraryFloatSinCosPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5841, ArbitraryFloatCastINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5842, ArbitraryFloatCastFromIntINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5843, ArbitraryFloatCastToIntINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5846, ArbitraryFloatAddINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5847, ArbitraryFloatSubINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5848, ArbitraryFloatMulINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5849, ArbitraryFloatDivINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5850, ArbitraryFloatGTINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One)]),
+    op!(5851, ArbitraryFloatGEINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One)]),
+    op!(5852, ArbitraryFloatLTINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One)]),
+    op!(5853, ArbitraryFloatLEINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One)]),
+    op!(5854, ArbitraryFloatEQINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One)]),
+    op!(5855, ArbitraryFloatRecipINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5856, ArbitraryFloatRSqrtINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5857, ArbitraryFloatCbrtINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5858, ArbitraryFloatHypotINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5859, ArbitraryFloatSqrtINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5860, ArbitraryFloatLogINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5861, ArbitraryFloatLog2INTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5862, ArbitraryFloatLog10INTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5863, ArbitraryFloatLog1pINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5864, ArbitraryFloatExpINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5865, ArbitraryFloatExp2INTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5866, ArbitraryFloatExp10INTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5867, ArbitraryFloatExpm1INTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5868, ArbitraryFloatSinINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5869, ArbitraryFloatCosINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5870, ArbitraryFloatSinCosINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5871, ArbitraryFloatSinPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5872, ArbitraryFloatCosPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5873, ArbitraryFloatASinINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5874, ArbitraryFloatASinPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5875, ArbitraryFloatACosINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5876, ArbitraryFloatACosPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5877, ArbitraryFloatATanINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5878, ArbitraryFloatATanPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5879, ArbitraryFloatATan2INTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5880, ArbitraryFloatPowINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5881, ArbitraryFloatPowRINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5882, ArbitraryFloatPowNINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (LiteralInteger, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5887, LoopControlINTEL, [(LiteralInteger, ZeroOrMore)]),
+    op!(5911, AliasDomainDeclINTEL, [(IdResult, One), (IdRef, ZeroOrOne)]),
+    op!(5912, AliasScopeDeclINTEL, [(IdResult, One), (IdRef, One), (IdRef, ZeroOrOne)]),
+    op!(5913, AliasScopeListDeclINTEL, [(IdResult, One), (IdRef, ZeroOrMore)]),
+    op!(5923, FixedSqrtINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5924, FixedRecipINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5925, FixedRsqrtINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5926, FixedSinINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5927, FixedCosINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5928, FixedSinCosINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5929, FixedSinPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5930, FixedCosPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5931, FixedSinCosPiINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5932, FixedLogINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5933, FixedExpINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    op!(5934, PtrCastToCrossWorkgroupINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5938, CrossWorkgroupCastToPtrINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(5946, ReadPipeBlockingINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5947, WritePipeBlockingINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(5949, FPGARegINTEL, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6016, RayQueryGetRayTMinKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(6017, RayQueryGetRayFlagsKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(6018, RayQueryGetIntersectionTKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6019, RayQueryGetIntersectionInstanceCustomIndexKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6020, RayQueryGetIntersectionInstanceIdKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6021, RayQueryGetIntersectionInstanceShaderBindingTableRecordOffsetKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6022, RayQueryGetIntersectionGeometryIndexKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6023, RayQueryGetIntersectionPrimitiveIndexKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6024, RayQueryGetIntersectionBarycentricsKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6025, RayQueryGetIntersectionFrontFaceKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6026, RayQueryGetIntersectionCandidateAABBOpaqueKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(6027, RayQueryGetIntersectionObjectRayDirectionKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6028, RayQueryGetIntersectionObjectRayOriginKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6029, RayQueryGetWorldRayDirectionKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(6030, RayQueryGetWorldRayOriginKHR, [(IdResultType, One), (IdResult, One), (IdRef, One)]),
+    op!(6031, RayQueryGetIntersectionObjectToWorldKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6032, RayQueryGetIntersectionWorldToObjectKHR, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdRef, One)]),
+    op!(6035, AtomicFAddEXT, [(IdResultType, One), (IdResult, One), (IdRef, One), (IdScope, One), (IdMemorySemantics, One), (IdRef, One)]),
+    op!(6086, TypeBufferSurfaceINTEL, [(IdResult, One), (AccessQualifier, One)]),
+    op!(6090, TypeStructContinuedINTEL, [(IdRef, ZeroOrMore)]),
+    op!(6091, ConstantCompositeContinuedINTEL, [(IdRef, ZeroOrMore)]),
+    op!(6092, SpecConstantCompositeContinuedINTEL, [(IdRef, ZeroOrMore)]),
+    op!(6142, ControlBarrierArriveINTEL, [(IdScope, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(6143, ControlBarrierWaitINTEL, [(IdScope, One), (IdScope, One), (IdMemorySemantics, One)]),
+    op!(6401, GroupIMulKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(6402, GroupFMulKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(6403, GroupBitwiseAndKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(6404, GroupBitwiseOrKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(6405, GroupBitwiseXorKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(6406, GroupLogicalAndKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(6407, GroupLogicalOrKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+    op!(6408, GroupLogicalXorKHR, [(IdResultType, One), (IdResult, One), (IdScope, One), (GroupOperation, One), (IdRef, One)]),
+];
+
+// Sorted by kind, then value.
+#[rustfmt::skip]
+static PARAMETERS: &[EnumerantParameters] = &[
+    params!(ImageOperands, 0x1, [(IdRef, One)]),
+    params!(ImageOperands, 0x2, [(IdRef, One)]),
+    params!(ImageOperands, 0x4, [(IdRef, One), (IdRef, One)]),
+    params!(ImageOperands, 0x8, [(IdRef, One)]),
+    params!(ImageOperands, 0x10, [(IdRef, One)]),
+    params!(ImageOperands, 0x20, [(IdRef, One)]),
+    params!(ImageOperands, 0x40, [(IdRef, One)]),
+    params!(ImageOperands, 0x80, [(IdRef, One)]),
+    params!(ImageOperands, 0x100, [(IdScope, One)]),
+    params!(ImageOperands, 0x200, [(IdScope, One)]),
+    params!(ImageOperands, 0x10000, [(IdRef, One)]),
+    params!(LoopControl, 0x8, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x10, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x20, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x40, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x80, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x100, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x10000, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x20000, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x40000, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x80000, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x100000, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x200000, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x400000, [(LiteralInteger, One)]),
+    params!(LoopControl, 0x800000, [(LiteralInteger, One)]),
+    params!(MemoryAccess, 0x2, [(LiteralInteger, One)]),
+    params!(MemoryAccess, 0x8, [(IdScope, One)]),
+    params!(MemoryAccess, 0x10, [(IdScope, One)]),
+    params!(MemoryAccess, 0x10000, [(IdRef, One)]),
+    params!(MemoryAccess, 0x20000, [(IdRef, One)]),
+    params!(ExecutionMode, 0x0, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x11, [(LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    params!(ExecutionMode, 0x12, [(LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    params!(ExecutionMode, 0x1a, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x1e, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x23, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x24, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x25, [(IdRef, One)]),
+    params!(ExecutionMode, 0x26, [(IdRef, One), (IdRef, One), (IdRef, One)]),
+    params!(ExecutionMode, 0x27, [(IdRef, One), (IdRef, One), (IdRef, One)]),
+    params!(ExecutionMode, 0x116b, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x116c, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x116d, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x116e, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x116f, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x1496, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x15f2, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x15f4, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x15f5, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x15f6, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x15f7, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x1705, [(LiteralInteger, One), (LiteralInteger, One), (LiteralInteger, One)]),
+    params!(ExecutionMode, 0x1706, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x1708, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x170f, [(LiteralInteger, One)]),
+    params!(ExecutionMode, 0x1911, [(LiteralInteger, One)]),
+    params!(Decoration, 0x1, [(LiteralInteger, One)]),
+    params!(Decoration, 0x6, [(LiteralInteger, One)]),
+    params!(Decoration, 0x7, [(LiteralInteger, One)]),
+    params!(Decoration, 0xb, [(BuiltIn, One)]),
+    params!(Decoration, 0x1b, [(IdScope, One)]),
+    params!(Decoration, 0x1d, [(LiteralInteger, One)]),
+    params!(Decoration, 0x1e, [(LiteralInteger, One)]),
+    params!(Decoration, 0x1f, [(LiteralInteger, One)]),
+    params!(Decoration, 0x20, [(LiteralInteger, One)]),
+    params!(Decoration, 0x21, [(LiteralInteger, One)]),
+    params!(Decoration, 0x22, [(LiteralInteger, One)]),
+    params!(Decoration, 0x23, [(LiteralInteger, One)]),
+    params!(Decoration, 0x24, [(LiteralInteger, One)]),
+    params!(Decoration, 0x25, [(LiteralInteger, One)]),
+    params!(Decoration, 0x26, [(FunctionParameterAttribute, One)]),
+    params!(Decoration, 0x27, [(FPRoundingMode, One)]),
+    params!(Decoration, 0x28, [(FPFastMathMode, One)]),
+    params!(Decoration, 0x29, [(LiteralString, One), (LinkageType, One)]),
+    params!(Decoration, 0x2b, [(LiteralInteger, One)]),
+    params!(Decoration, 0x2c, [(LiteralInteger, One)]),
+    params!(Decoration, 0x2d, [(LiteralInteger, One)]),
+    params!(Decoration, 0x2e, [(IdRef, One)]),
+    params!(Decoration, 0x2f, [(IdRef, One)]),
+    params!(Decoration, 0x1488, [(LiteralInteger, One)]),
+    params!(Decoration, 0x15df, [(LiteralInteger, One)]),
+    params!(Decoration, 0x15e7, [(LiteralString, One)]),
+    params!(Decoration, 0x15f9, [(LiteralInteger, One)]),
+    params!(Decoration, 0x15fc, [(LiteralInteger, One)]),
+    params!(Decoration, 0x1602, [(IdRef, One)]),
+    params!(Decoration, 0x1603, [(LiteralString, One)]),
+    params!(Decoration, 0x1604, [(LiteralString, One)]),
+    params!(Decoration, 0x16be, [(LiteralInteger, One), (FPRoundingMode, One)]),
+    params!(Decoration, 0x16bf, [(LiteralInteger, One), (FPDenormMode, One)]),
+    params!(Decoration, 0x16c2, [(LiteralString, One)]),
+    params!(Decoration, 0x16c3, [(LiteralInteger, One)]),
+    params!(Decoration, 0x16c4, [(LiteralInteger, One)]),
+    params!(Decoration, 0x16c5, [(LiteralInteger, One)]),
+    params!(Decoration, 0x16c8, [(LiteralInteger, One)]),
+    params!(Decoration, 0x16ca, [(LiteralString, One), (LiteralString, One)]),
+    params!(Decoration, 0x16cb, [(LiteralInteger, ZeroOrMore)]),
+    params!(Decoration, 0x16cc, [(LiteralInteger, One)]),
+    params!(Decoration, 0x170c, [(LiteralInteger, One)]),
+    params!(Decoration, 0x170e, [(LiteralInteger, One)]),
+    params!(Decoration, 0x171a, [(IdRef, One)]),
+    params!(Decoration, 0x171b, [(IdRef, One)]),
+    params!(Decoration, 0x1721, [(LiteralInteger, One)]),
+    params!(Decoration, 0x1738, [(LiteralInteger, One)]),
+    params!(Decoration, 0x17c0, [(LiteralInteger, One), (FPOperationMode, One)]),
+];
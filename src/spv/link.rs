@@ -0,0 +1,477 @@
+//! Merging several modules into one module with all their entry points.
+
+use super::{Header, Instruction, Module, Span, SpanKind, SpirvError, grammar, opcode_name};
+use spirv::Op;
+use std::collections::{HashMap, HashSet};
+
+/// Where an instruction goes in the logical layout of the linked module.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Section {
+    Capability,
+    Extension,
+    ExtInstImport,
+    MemoryModel,
+    EntryPoint,
+    ExecutionMode,
+    DebugSource,
+    DebugName,
+    ModuleProcessed,
+    Annotation,
+    Global,
+    FunctionDeclaration,
+    FunctionDefinition,
+}
+
+/// Links `modules` into a single module containing every entry point.
+///
+/// Ids are renumbered. Capabilities, extensions, extended instruction set
+/// imports, and types and constants that are identical including their
+/// decorations are emitted once. Fails if the modules use different memory
+/// models, two entry points share a name and execution model, or some modules
+/// are SPIR-V 1.4 or later and others are not: from 1.4 on entry point
+/// interfaces list every global variable they use instead of only inputs and
+/// outputs, so the lists cannot be carried over to the other version.
+pub fn link_modules(modules: &[Module]) -> Result<Module, SpirvError> {
+    let first = modules
+        .first()
+        .ok_or_else(|| SpirvError::Validation("no modules to link".to_string()))?;
+
+    let lists_all_globals = |module: &Module| module.header.version() >= (1, 4);
+    if let Some(other) = modules
+        .iter()
+        .find(|module| lists_all_globals(module) != lists_all_globals(first))
+    {
+        let (first, other) = (first.header.version(), other.header.version());
+        return Err(SpirvError::Validation(format!(
+            "cannot link SPIR-V {}.{} and {}.{} modules, whose entry point interfaces differ",
+            first.0, first.1, other.0, other.1
+        )));
+    }
+
+    let mut seen = HashSet::new();
+    for entry_point in modules.iter().flat_map(Module::entry_points) {
+        if !seen.insert((entry_point.execution_model, entry_point.name.clone())) {
+            return Err(SpirvError::Validation(format!(
+                "{:?} entry point \"{}\" is defined by more than one module",
+                entry_point.execution_model, entry_point.name
+            )));
+        }
+    }
+
+    let mut linker = Linker::default();
+    for module in modules {
+        linker.add(module)?;
+    }
+
+    let version = modules
+        .iter()
+        .map(|module| module.header.version)
+        .max()
+        .unwrap_or(first.header.version);
+    let mut sections = linker.sections;
+    sections.sort_by_key(|(section, _)| *section);
+    Ok(Module {
+        header: Header {
+            version,
+            generator: first.header.generator,
+            bound: linker.next_id,
+            schema: 0,
+        },
+        instructions: sections.into_iter().map(|(_, inst)| inst).collect(),
+    })
+}
+
+struct Linker {
+    next_id: u32,
+    /// Instructions of the linked module tagged with their section. The sort by
+    /// section is stable, so each section keeps the order it was added in.
+    sections: Vec<(Section, Instruction)>,
+    /// Capabilities, extensions and source information already emitted.
+    emitted: HashSet<Instruction>,
+    ext_inst_imports: HashMap<String, u32>,
+    /// Remapped types and constants, with their decorations, to their linked id.
+    globals: HashMap<(Instruction, Vec<Instruction>), u32>,
+    memory_model: Option<Instruction>,
+}
+
+impl Default for Linker {
+    fn default() -> Self {
+        Self {
+            next_id: 1,
+            sections: Vec::new(),
+            emitted: HashSet::new(),
+            ext_inst_imports: HashMap::new(),
+            globals: HashMap::new(),
+            memory_model: None,
+        }
+    }
+}
+
+impl Linker {
+    fn fresh_id(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn add(&mut self, module: &Module) -> Result<(), SpirvError> {
+        let layout = module.layout()?;
+        let ids = self.assign_ids(module, &layout);
+
+        let mut function_section = None;
+        for (index, inst) in module.instructions.iter().enumerate() {
+            if inst.is(Op::Function) {
+                let is_definition = module.instructions[index..]
+                    .iter()
+                    .take_while(|inst| !inst.is(Op::FunctionEnd))
+                    .any(|inst| inst.is(Op::Label));
+                function_section = Some(if is_definition {
+                    Section::FunctionDefinition
+                } else {
+                    Section::FunctionDeclaration
+                });
+            }
+            let section = match function_section {
+                Some(section) => section,
+                None => global_section(inst),
+            };
+            if inst.is(Op::FunctionEnd) {
+                function_section = None;
+            }
+
+            if ids.duplicates.contains(&index) {
+                continue;
+            }
+            if matches!(section, Section::DebugName | Section::Annotation)
+                && inst
+                    .operands
+                    .first()
+                    .is_some_and(|target| ids.deduplicated.contains(target))
+            {
+                // Already emitted with the first definition of this type or constant.
+                continue;
+            }
+
+            let remapped = remap(inst, &layout[index], &ids.map)?;
+            match section {
+                Section::Capability
+                | Section::Extension
+                | Section::DebugSource
+                | Section::ModuleProcessed
+                    if !self.emitted.insert(remapped.clone()) =>
+                {
+                    continue;
+                }
+                Section::MemoryModel => match &self.memory_model {
+                    Some(memory_model) if *memory_model != remapped => {
+                        return Err(SpirvError::Validation(
+                            "modules use different addressing or memory models".to_string(),
+                        ));
+                    }
+                    Some(_) => continue,
+                    None => self.memory_model = Some(remapped.clone()),
+                },
+                _ => {}
+            }
+            self.sections.push((section, remapped));
+        }
+        Ok(())
+    }
+
+    /// Maps every id of `module` to its id in the linked module.
+    fn assign_ids(&mut self, module: &Module, layout: &[Vec<Span>]) -> IdMap {
+        // Decorations by target. Types reached through decoration groups or
+        // forward pointers are never merged.
+        let mut decorations: HashMap<u32, Vec<usize>> = HashMap::new();
+        let mut unmergeable: HashSet<u32> = HashSet::new();
+        for (index, inst) in module.instructions.iter().enumerate() {
+            match global_section(inst) {
+                Section::Annotation if inst.is(Op::GroupDecorate) => {
+                    unmergeable.extend(inst.operands.iter().skip(1).copied());
+                }
+                Section::Annotation if inst.is(Op::GroupMemberDecorate) => {
+                    unmergeable.extend(inst.operands.iter().skip(1).step_by(2).copied());
+                }
+                Section::Annotation if !inst.is(Op::DecorationGroup) => {
+                    if let Some(&target) = inst.operands.first() {
+                        decorations.entry(target).or_default().push(index);
+                    }
+                }
+                _ if inst.is(Op::TypeForwardPointer) => {
+                    unmergeable.extend(inst.operands.first().copied());
+                }
+                _ => {}
+            }
+        }
+
+        let mut ids = IdMap::default();
+        let mut in_function = false;
+        for (index, inst) in module.instructions.iter().enumerate() {
+            if inst.is(Op::Function) {
+                in_function = true;
+            } else if inst.is(Op::FunctionEnd) {
+                in_function = false;
+            }
+            let Some(result) = inst.result_id() else {
+                continue;
+            };
+
+            let merged = if in_function || unmergeable.contains(&result) {
+                None
+            } else if inst.is(Op::ExtInstImport) {
+                let name = inst.string_operand(1).unwrap_or_default();
+                Some(match self.ext_inst_imports.get(&name) {
+                    Some(&id) => (id, true),
+                    None => {
+                        let id = self.fresh_id();
+                        self.ext_inst_imports.insert(name, id);
+                        (id, false)
+                    }
+                })
+            } else if is_mergeable(inst) {
+                let decorations = decorations.get(&result).map_or(&[][..], Vec::as_slice);
+                merge_key(module, layout, index, decorations, &ids.map).map(|key| {
+                    match self.globals.get(&key) {
+                        Some(&id) => (id, true),
+                        None => {
+                            let id = self.fresh_id();
+                            self.globals.insert(key, id);
+                            (id, false)
+                        }
+                    }
+                })
+            } else {
+                None
+            };
+
+            match merged {
+                Some((id, true)) => {
+                    ids.map.insert(result, id);
+                    ids.duplicates.insert(index);
+                    ids.deduplicated.insert(result);
+                }
+                Some((id, false)) => {
+                    ids.map.insert(result, id);
+                }
+                None => {
+                    let id = self.fresh_id();
+                    ids.map.insert(result, id);
+                }
+            }
+        }
+        ids
+    }
+}
+
+/// Id assignments for one input module.
+#[derive(Default)]
+struct IdMap {
+    map: HashMap<u32, u32>,
+    /// Instruction indices that duplicate something already in the linked module.
+    duplicates: HashSet<usize>,
+    /// Original ids of those duplicates.
+    deduplicated: HashSet<u32>,
+}
+
+/// Builds the key two types or constants must share to be merged: the remapped
+/// instruction without its result id, and its remapped decorations.
+///
+/// Returns `None` if something it refers to has no linked id yet.
+fn merge_key(
+    module: &Module,
+    layout: &[Vec<Span>],
+    index: usize,
+    decorations: &[usize],
+    map: &HashMap<u32, u32>,
+) -> Option<(Instruction, Vec<Instruction>)> {
+    let inst = &module.instructions[index];
+    let mut definition = inst.clone();
+    for span in &layout[index] {
+        match span.kind {
+            SpanKind::Result => definition.operands[span.start] = 0,
+            _ if span.is_id() => {
+                definition.operands[span.start] = *map.get(&inst.operands[span.start])?
+            }
+            _ => {}
+        }
+    }
+
+    let mut annotations = Vec::new();
+    for &decoration in decorations {
+        let mut annotation = module.instructions[decoration].clone();
+        annotation.operands[0] = 0;
+        for span in layout[decoration]
+            .iter()
+            .skip(1)
+            .filter(|span| span.is_id())
+        {
+            annotation.operands[span.start] = *map.get(&annotation.operands[span.start])?;
+        }
+        annotations.push(annotation);
+    }
+    annotations.sort_by(|a, b| (a.opcode, &a.operands).cmp(&(b.opcode, &b.operands)));
+    Some((definition, annotations))
+}
+
+/// Rewrites every id operand of `inst` through `map`.
+fn remap(
+    inst: &Instruction,
+    spans: &[Span],
+    map: &HashMap<u32, u32>,
+) -> Result<Instruction, SpirvError> {
+    let mut remapped = inst.clone();
+    for span in spans.iter().filter(|span| span.is_id()) {
+        let id = inst.operands[span.start];
+        remapped.operands[span.start] = *map.get(&id).ok_or_else(|| {
+            SpirvError::Validation(format!(
+                "{} references %{id}, which is never defined",
+                opcode_name(inst.opcode)
+            ))
+        })?;
+    }
+    Ok(remapped)
+}
+
+/// Types and constants, which are merged when identical.
+fn is_mergeable(inst: &Instruction) -> bool {
+    grammar::lookup(inst.opcode).is_some_and(|grammar| {
+        (grammar.name.starts_with("Type") && !inst.is(Op::TypeForwardPointer))
+            || grammar.name.starts_with("Constant")
+            || grammar.name.starts_with("SpecConstant")
+    })
+}
+
+/// Section of an instruction outside any function.
+fn global_section(inst: &Instruction) -> Section {
+    match Op::from_u32(u32::from(inst.opcode)) {
+        Some(Op::Capability) => Section::Capability,
+        Some(Op::Extension) => Section::Extension,
+        Some(Op::ExtInstImport) => Section::ExtInstImport,
+        Some(Op::MemoryModel) => Section::MemoryModel,
+        Some(Op::EntryPoint) => Section::EntryPoint,
+        Some(Op::ExecutionMode | Op::ExecutionModeId) => Section::ExecutionMode,
+        Some(Op::String | Op::Source | Op::SourceContinued | Op::SourceExtension) => {
+            Section::DebugSource
+        }
+        Some(Op::Name | Op::MemberName) => Section::DebugName,
+        Some(Op::ModuleProcessed) => Section::ModuleProcessed,
+        Some(
+            Op::Decorate
+            | Op::MemberDecorate
+            | Op::DecorationGroup
+            | Op::GroupDecorate
+            | Op::GroupMemberDecorate
+            | Op::DecorateId
+            | Op::DecorateString
+            | Op::MemberDecorateString,
+        ) => Section::Annotation,
+        _ => Section::Global,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spv::{encode_string, test_module};
+    use spirv::{
+        AddressingModel, Capability, ExecutionMode, ExecutionModel, FunctionControl, MemoryModel,
+    };
+
+    /// A fragment shader with an empty `main` named `name`.
+    fn fragment(version: (u8, u8), name: &str) -> Module {
+        let mut entry_point = vec![ExecutionModel::Fragment as u32, 3];
+        entry_point.extend(encode_string(name));
+        test_module(
+            version,
+            5,
+            vec![
+                Instruction::new(Op::Capability, vec![Capability::Shader as u32]),
+                Instruction::new(
+                    Op::MemoryModel,
+                    vec![AddressingModel::Logical as u32, MemoryModel::GLSL450 as u32],
+                ),
+                Instruction::new(Op::EntryPoint, entry_point),
+                Instruction::new(
+                    Op::ExecutionMode,
+                    vec![3, ExecutionMode::OriginUpperLeft as u32],
+                ),
+                Instruction::new(Op::TypeVoid, vec![1]),
+                Instruction::new(Op::TypeFunction, vec![2, 1]),
+                Instruction::new(Op::Function, vec![1, 3, FunctionControl::NONE.bits(), 2]),
+                Instruction::new(Op::Label, vec![4]),
+                Instruction::new(Op::Return, vec![]),
+                Instruction::new(Op::FunctionEnd, vec![]),
+            ],
+        )
+    }
+
+    fn count(module: &Module, op: Op) -> usize {
+        module
+            .instructions
+            .iter()
+            .filter(|inst| inst.is(op))
+            .count()
+    }
+
+    #[test]
+    fn links_entry_points_and_merges_types() {
+        let linked = link_modules(&[fragment((1, 3), "a"), fragment((1, 3), "b")]).unwrap();
+        linked.validate().unwrap();
+
+        let names: Vec<_> = linked
+            .entry_points()
+            .into_iter()
+            .map(|entry_point| entry_point.name)
+            .collect();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(count(&linked, Op::Capability), 1);
+        assert_eq!(count(&linked, Op::MemoryModel), 1);
+        assert_eq!(count(&linked, Op::TypeVoid), 1);
+        assert_eq!(count(&linked, Op::TypeFunction), 1);
+        assert_eq!(count(&linked, Op::Function), 2);
+        // void, its function type, and a function and label per module
+        assert_eq!(linked.header.bound, 7);
+
+        // Entry points still name their own function
+        let functions: Vec<_> = linked
+            .instructions
+            .iter()
+            .filter(|inst| inst.is(Op::Function))
+            .map(|inst| inst.operands[1])
+            .collect();
+        let targets: Vec<_> = linked
+            .entry_points()
+            .into_iter()
+            .map(|entry_point| entry_point.function)
+            .collect();
+        assert_eq!(functions, targets);
+    }
+
+    #[test]
+    fn keeps_the_newest_version_within_interface_rules() {
+        let linked = link_modules(&[fragment((1, 4), "a"), fragment((1, 6), "b")]).unwrap();
+        assert_eq!(linked.header.version(), (1, 6));
+        let linked = link_modules(&[fragment((1, 0), "a"), fragment((1, 3), "b")]).unwrap();
+        assert_eq!(linked.header.version(), (1, 3));
+    }
+
+    #[test]
+    fn rejects_mixing_interface_rules() {
+        let result = link_modules(&[fragment((1, 3), "a"), fragment((1, 4), "b")]);
+        assert!(matches!(result, Err(SpirvError::Validation(_))));
+    }
+
+    #[test]
+    fn rejects_duplicate_entry_points() {
+        let result = link_modules(&[fragment((1, 3), "a"), fragment((1, 3), "a")]);
+        assert!(matches!(result, Err(SpirvError::Validation(_))));
+    }
+
+    #[test]
+    fn rejects_different_memory_models() {
+        let mut vulkan = fragment((1, 3), "b");
+        vulkan.instructions[1].operands[1] = MemoryModel::Vulkan as u32;
+        let result = link_modules(&[fragment((1, 3), "a"), vulkan]);
+        assert!(matches!(result, Err(SpirvError::Validation(_))));
+    }
+}
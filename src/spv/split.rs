@@ -0,0 +1,468 @@
+//! Splitting a module into one module per entry point.
+
+use super::{EntryPoint, Header, Instruction, Module, SpanKind, SpirvError};
+use spirv::Op;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// Where an id is defined.
+#[derive(Clone, Copy)]
+enum Site {
+    Global(usize),
+    Function(usize),
+}
+
+/// Splits `module` into one module per entry point.
+///
+/// Each module keeps only the functions, globals, types, constants, names and
+/// decorations reachable from its entry point. Ids are left unchanged.
+pub fn split_entry_points(module: &Module) -> Result<Vec<(EntryPoint, Module)>, SpirvError> {
+    module
+        .entry_points()
+        .into_iter()
+        .map(|entry_point| {
+            let split = extract_entry_point(module, &entry_point)?;
+            Ok((entry_point, split))
+        })
+        .collect()
+}
+
+/// Builds a module containing only `entry_point` and what it can reach.
+pub fn extract_entry_point(
+    module: &Module,
+    entry_point: &EntryPoint,
+) -> Result<Module, SpirvError> {
+    let layout = module.layout()?;
+    let refs = |index: usize| -> Vec<u32> {
+        let inst = &module.instructions[index];
+        layout[index]
+            .iter()
+            .filter(|span| span.is_id() && span.kind != SpanKind::Result)
+            .map(|span| inst.operands[span.start])
+            .collect()
+    };
+
+    let functions = function_ranges(module)?;
+    let mut function_of = vec![None; module.instructions.len()];
+    for (function, range) in functions.iter().enumerate() {
+        for index in range.clone() {
+            function_of[index] = Some(function);
+        }
+    }
+
+    let mut sites = HashMap::new();
+    for (index, inst) in module.instructions.iter().enumerate() {
+        if let Some(id) = inst.result_id() {
+            let site = match function_of[index] {
+                Some(function) => Site::Function(function),
+                None => Site::Global(index),
+            };
+            sites.insert(id, site);
+        }
+    }
+
+    let mut worklist = vec![entry_point.function];
+    worklist.extend(&entry_point.interface);
+    for (index, inst) in module.instructions.iter().enumerate() {
+        if function_of[index].is_some() {
+            continue;
+        }
+        if is_unconditional(inst)
+            || (is_execution_mode(inst) && inst.operands.first() == Some(&entry_point.function))
+        {
+            worklist.extend(refs(index));
+        }
+    }
+
+    let mut live_ids = HashSet::new();
+    let mut live_globals = HashSet::new();
+    let mut live_functions = HashSet::new();
+    loop {
+        while let Some(id) = worklist.pop() {
+            if !live_ids.insert(id) {
+                continue;
+            }
+            match sites.get(&id) {
+                Some(&Site::Global(index)) if live_globals.insert(index) => {
+                    worklist.extend(refs(index));
+                }
+                Some(&Site::Function(function)) if live_functions.insert(function) => {
+                    for index in functions[function].clone() {
+                        worklist.extend(refs(index));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        // Decorations of live ids can reference further ids, e.g. `CounterBuffer`
+        // or a decoration group applied to a live target.
+        for (index, inst) in module.instructions.iter().enumerate() {
+            if function_of[index].is_some() {
+                continue;
+            }
+            let targets_live = if inst.is(Op::GroupDecorate) {
+                inst.operands[1..].iter().any(|id| live_ids.contains(id))
+            } else if inst.is(Op::GroupMemberDecorate) {
+                inst.operands[1..]
+                    .iter()
+                    .step_by(2)
+                    .any(|id| live_ids.contains(id))
+            } else if is_annotation(inst) || inst.is(Op::TypeForwardPointer) {
+                inst.operands
+                    .first()
+                    .is_some_and(|id| live_ids.contains(id))
+            } else {
+                false
+            };
+            if !targets_live {
+                continue;
+            }
+            if inst.is(Op::GroupDecorate) || inst.is(Op::GroupMemberDecorate) {
+                // Only the group; the other targets stay dead unless reached
+                if !live_ids.contains(&inst.operands[0]) {
+                    worklist.push(inst.operands[0]);
+                }
+            } else {
+                worklist.extend(refs(index).into_iter().filter(|id| !live_ids.contains(id)));
+            }
+        }
+        if worklist.is_empty() {
+            break;
+        }
+    }
+
+    let mut instructions = Vec::new();
+    for (index, inst) in module.instructions.iter().enumerate() {
+        let keep = if let Some(function) = function_of[index] {
+            live_functions.contains(&function)
+        } else if inst.is(Op::EntryPoint) {
+            is_same_entry_point(inst, entry_point)
+        } else if is_execution_mode(inst) {
+            inst.operands.first() == Some(&entry_point.function)
+        } else if inst.is(Op::GroupDecorate) || inst.is(Op::GroupMemberDecorate) {
+            let stride = if inst.is(Op::GroupDecorate) { 1 } else { 2 };
+            let mut operands = vec![inst.operands[0]];
+            for target in inst.operands[1..].chunks(stride) {
+                if live_ids.contains(&target[0]) {
+                    operands.extend_from_slice(target);
+                }
+            }
+            if operands.len() > 1 {
+                instructions.push(Instruction {
+                    opcode: inst.opcode,
+                    operands,
+                });
+            }
+            continue;
+        } else if is_annotation(inst) || is_debug_name(inst) || inst.is(Op::TypeForwardPointer) {
+            inst.operands
+                .first()
+                .is_some_and(|id| live_ids.contains(id))
+        } else if inst.result_id().is_some() {
+            live_globals.contains(&index)
+        } else {
+            true
+        };
+        if keep {
+            instructions.push(inst.clone());
+        }
+    }
+
+    let bound = instructions
+        .iter()
+        .filter_map(Instruction::result_id)
+        .max()
+        .map_or(1, |id| id + 1);
+    Ok(Module {
+        header: Header {
+            bound,
+            ..module.header
+        },
+        instructions,
+    })
+}
+
+/// Instruction index ranges of every function, from `OpFunction` to `OpFunctionEnd`.
+fn function_ranges(module: &Module) -> Result<Vec<Range<usize>>, SpirvError> {
+    let mut ranges = Vec::new();
+    let mut start = None;
+    for (index, inst) in module.instructions.iter().enumerate() {
+        if inst.is(Op::Function) {
+            start = Some(index);
+        } else if inst.is(Op::FunctionEnd) {
+            let begin = start.take().ok_or_else(|| {
+                SpirvError::Validation("OpFunctionEnd without a matching OpFunction".to_string())
+            })?;
+            ranges.push(begin..index + 1);
+        }
+    }
+    if start.is_some() {
+        return Err(SpirvError::Validation(
+            "OpFunction without a matching OpFunctionEnd".to_string(),
+        ));
+    }
+    Ok(ranges)
+}
+
+fn is_same_entry_point(inst: &Instruction, entry_point: &EntryPoint) -> bool {
+    inst.operands.first() == Some(&(entry_point.execution_model as u32))
+        && inst.operands.get(1) == Some(&entry_point.function)
+        && inst.string_operand(2).as_deref() == Some(entry_point.name.as_str())
+}
+
+fn is_execution_mode(inst: &Instruction) -> bool {
+    inst.is(Op::ExecutionMode) || inst.is(Op::ExecutionModeId)
+}
+
+fn is_annotation(inst: &Instruction) -> bool {
+    [
+        Op::Decorate,
+        Op::MemberDecorate,
+        Op::DecorateId,
+        Op::DecorateString,
+        Op::MemberDecorateString,
+    ]
+    .into_iter()
+    .any(|op| inst.is(op))
+}
+
+fn is_debug_name(inst: &Instruction) -> bool {
+    inst.is(Op::Name) || inst.is(Op::MemberName)
+}
+
+/// Global instructions without a result that are kept in every split module,
+/// such as capabilities, the memory model and source information.
+fn is_unconditional(inst: &Instruction) -> bool {
+    inst.result_id().is_none()
+        && !inst.is(Op::EntryPoint)
+        && !is_execution_mode(inst)
+        && !is_annotation(inst)
+        && !is_debug_name(inst)
+        && !inst.is(Op::GroupDecorate)
+        && !inst.is(Op::GroupMemberDecorate)
+        && !inst.is(Op::TypeForwardPointer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spv::{encode_string, test_module};
+    use spirv::{
+        AddressingModel, Capability, Decoration, ExecutionMode, ExecutionModel, FunctionControl,
+        MemoryModel, StorageClass,
+    };
+
+    fn entry_point(
+        model: ExecutionModel,
+        function: u32,
+        name: &str,
+        interface: &[u32],
+    ) -> Instruction {
+        let mut operands = vec![model as u32, function];
+        operands.extend(encode_string(name));
+        operands.extend_from_slice(interface);
+        Instruction::new(Op::EntryPoint, operands)
+    }
+
+    fn name(target: u32, name: &str) -> Instruction {
+        let mut operands = vec![target];
+        operands.extend(encode_string(name));
+        Instruction::new(Op::Name, operands)
+    }
+
+    /// A void function `id` whose body is `body`, labelled `id + 1`.
+    fn function(id: u32, body: Vec<Instruction>) -> Vec<Instruction> {
+        let mut instructions = vec![
+            Instruction::new(Op::Function, vec![1, id, FunctionControl::NONE.bits(), 2]),
+            Instruction::new(Op::Label, vec![id + 1]),
+        ];
+        instructions.extend(body);
+        instructions.push(Instruction::new(Op::Return, vec![]));
+        instructions.push(Instruction::new(Op::FunctionEnd, vec![]));
+        instructions
+    }
+
+    /// A vertex entry point `vs` (10) writing `out_vs` (40) and a fragment entry
+    /// point `fs` (20) writing `out_fs` (41). Both call `shared` (30), only `fs`
+    /// calls `fs_only` (25) which uses constant 7, and nothing calls 60. Both
+    /// outputs are `RelaxedPrecision` through decoration group 6.
+    fn two_entry_points() -> Module {
+        let mut instructions = vec![
+            Instruction::new(Op::Capability, vec![Capability::Shader as u32]),
+            Instruction::new(
+                Op::MemoryModel,
+                vec![AddressingModel::Logical as u32, MemoryModel::GLSL450 as u32],
+            ),
+            entry_point(ExecutionModel::Vertex, 10, "vs", &[40]),
+            entry_point(ExecutionModel::Fragment, 20, "fs", &[41]),
+            Instruction::new(
+                Op::ExecutionMode,
+                vec![20, ExecutionMode::OriginUpperLeft as u32],
+            ),
+            name(10, "vs"),
+            name(20, "fs"),
+            name(25, "fs_only"),
+            name(30, "shared"),
+            name(60, "unused"),
+            Instruction::new(Op::Decorate, vec![40, Decoration::Location as u32, 0]),
+            Instruction::new(Op::Decorate, vec![41, Decoration::Location as u32, 0]),
+            Instruction::new(Op::Decorate, vec![6, Decoration::RelaxedPrecision as u32]),
+            Instruction::new(Op::DecorationGroup, vec![6]),
+            Instruction::new(Op::GroupDecorate, vec![6, 40, 41]),
+            Instruction::new(Op::TypeVoid, vec![1]),
+            Instruction::new(Op::TypeFunction, vec![2, 1]),
+            Instruction::new(Op::TypeFloat, vec![3, 32]),
+            Instruction::new(Op::TypePointer, vec![4, StorageClass::Output as u32, 3]),
+            Instruction::new(Op::Constant, vec![3, 5, 1.0f32.to_bits()]),
+            Instruction::new(Op::Constant, vec![3, 7, 2.0f32.to_bits()]),
+            Instruction::new(Op::Variable, vec![4, 40, StorageClass::Output as u32]),
+            Instruction::new(Op::Variable, vec![4, 41, StorageClass::Output as u32]),
+        ];
+        instructions.extend(function(30, vec![]));
+        instructions.extend(function(25, vec![Instruction::new(Op::Store, vec![41, 7])]));
+        instructions.extend(function(
+            10,
+            vec![
+                Instruction::new(Op::FunctionCall, vec![1, 12, 30]),
+                Instruction::new(Op::Store, vec![40, 5]),
+            ],
+        ));
+        instructions.extend(function(
+            20,
+            vec![
+                Instruction::new(Op::FunctionCall, vec![1, 22, 30]),
+                Instruction::new(Op::FunctionCall, vec![1, 23, 25]),
+                Instruction::new(Op::Store, vec![41, 5]),
+            ],
+        ));
+        instructions.extend(function(60, vec![]));
+        test_module((1, 3), 62, instructions)
+    }
+
+    fn split(module: &Module, name: &str) -> Module {
+        let split = split_entry_points(module).unwrap();
+        let (_, module) = split
+            .into_iter()
+            .find(|(entry_point, _)| entry_point.name == name)
+            .unwrap();
+        module.validate().unwrap();
+        module
+    }
+
+    fn defined(module: &Module) -> HashSet<u32> {
+        module
+            .instructions
+            .iter()
+            .filter_map(Instruction::result_id)
+            .collect()
+    }
+
+    /// Ids named by `OpFunction`.
+    fn functions(module: &Module) -> Vec<u32> {
+        module
+            .instructions
+            .iter()
+            .filter(|inst| inst.is(Op::Function))
+            .map(|inst| inst.operands[1])
+            .collect()
+    }
+
+    /// Targets of `OpName`, `OpDecorate` and `OpGroupDecorate`.
+    fn targets(module: &Module, op: Op) -> Vec<u32> {
+        module
+            .instructions
+            .iter()
+            .filter(|inst| inst.is(op))
+            .flat_map(|inst| match op {
+                Op::GroupDecorate => inst.operands[1..].to_vec(),
+                _ => vec![inst.operands[0]],
+            })
+            .collect()
+    }
+
+    /// Checks that every id the module references is defined in it.
+    fn assert_closed(module: &Module) {
+        let defined = defined(module);
+        let layout = module.layout().unwrap();
+        for (inst, spans) in module.instructions.iter().zip(&layout) {
+            for span in spans.iter().filter(|span| span.is_id()) {
+                let id = inst.operands[span.start];
+                assert!(defined.contains(&id), "{id} is used but not defined");
+            }
+        }
+    }
+
+    #[test]
+    fn keeps_only_what_each_entry_point_reaches() {
+        let module = two_entry_points();
+        module.validate().unwrap();
+
+        let vs = split(&module, "vs");
+        assert_closed(&vs);
+        assert_eq!(functions(&vs), [30, 10]);
+        assert_eq!(
+            defined(&vs),
+            HashSet::from([1, 2, 3, 4, 5, 6, 10, 11, 12, 30, 31, 40])
+        );
+        assert_eq!(targets(&vs, Op::Name), [10, 30]);
+        assert_eq!(targets(&vs, Op::Decorate), [40, 6]);
+        assert_eq!(targets(&vs, Op::GroupDecorate), [40]);
+        assert!(
+            !vs.instructions
+                .iter()
+                .any(|inst| inst.is(Op::ExecutionMode))
+        );
+        assert_eq!(vs.header.bound, 41);
+
+        let fs = split(&module, "fs");
+        assert_closed(&fs);
+        assert_eq!(functions(&fs), [30, 25, 20]);
+        assert_eq!(
+            defined(&fs),
+            HashSet::from([1, 2, 3, 4, 5, 6, 7, 20, 21, 22, 23, 25, 26, 30, 31, 41])
+        );
+        assert_eq!(targets(&fs, Op::Name), [20, 25, 30]);
+        assert_eq!(targets(&fs, Op::Decorate), [41, 6]);
+        assert_eq!(targets(&fs, Op::GroupDecorate), [41]);
+        assert_eq!(targets(&fs, Op::ExecutionMode), [20]);
+        assert_eq!(fs.header.bound, 42);
+    }
+
+    #[test]
+    fn keeps_interface_variables_per_entry_point() {
+        let module = two_entry_points();
+        for (entry_point, split) in split_entry_points(&module).unwrap() {
+            assert_eq!(split.entry_points(), std::slice::from_ref(&entry_point));
+            let variables: Vec<u32> = split
+                .instructions
+                .iter()
+                .filter(|inst| inst.is(Op::Variable))
+                .map(|inst| inst.operands[1])
+                .collect();
+            assert_eq!(variables, entry_point.interface);
+        }
+    }
+
+    #[test]
+    fn drops_decoration_groups_without_live_targets() {
+        let mut module = two_entry_points();
+        // Only `out_fs` is in the group now
+        let group = module
+            .instructions
+            .iter_mut()
+            .find(|inst| inst.is(Op::GroupDecorate))
+            .unwrap();
+        group.operands = vec![6, 41];
+
+        let vs = split(&module, "vs");
+        assert_closed(&vs);
+        assert!(!defined(&vs).contains(&6));
+        assert_eq!(targets(&vs, Op::Decorate), [40]);
+        assert!(targets(&vs, Op::GroupDecorate).is_empty());
+
+        let fs = split(&module, "fs");
+        assert!(defined(&fs).contains(&6));
+        assert_eq!(targets(&fs, Op::GroupDecorate), [41]);
+    }
+}
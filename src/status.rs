@@ -0,0 +1,65 @@
+//! Snapshot of what the hot reloader is doing.
+
+use crate::diagnostics::Suggestion;
+use std::path::PathBuf;
+
+/// State of the most recent shader compile.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum CompileState {
+    /// No compile is running and the last one succeeded.
+    #[default]
+    Idle,
+    /// spirv-builder is compiling the shader crate.
+    Compiling,
+    /// The compile is waiting for a cargo lock held by another process.
+    WaitingForLock {
+        /// Lock file being waited on.
+        path: PathBuf,
+        /// Process holding the lock, where the platform can tell.
+        holder: Option<LockHolder>,
+    },
+    /// The last compile failed with the given error.
+    Failed(String),
+}
+
+/// Process holding a cargo lock.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockHolder {
+    pub pid: u32,
+    /// Executable name of the process.
+    pub name: String,
+}
+
+impl LockHolder {
+    /// Returns true if the holder is cargo, which releases its locks when done.
+    pub fn is_cargo(&self) -> bool {
+        self.name == "cargo" || self.name.starts_with("cargo-")
+    }
+}
+
+/// Status reported by [`ShaderHotReloader::status`](crate::ShaderHotReloader::status).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReloadStatus {
+    pub state: CompileState,
+    /// Generation shaders are currently loaded from.
+    pub current_generation: Option<u64>,
+    /// Target directory spirv-builder builds in.
+    pub target_dir: PathBuf,
+    /// Shader crate features the default-feature builds use.
+    pub features: Vec<String>,
+    /// Declarations the last failed compile was missing, recognised from its diagnostics.
+    pub suggestions: Vec<Suggestion>,
+    /// Declarations added automatically for this session that the builder
+    /// configuration lacks. See
+    /// [`ShaderHotReloaderBuilder::auto_capabilities`](crate::ShaderHotReloaderBuilder::auto_capabilities).
+    pub session_requirements: Vec<Suggestion>,
+}
+
+/// A shader crate that was recompiled, as returned by
+/// [`ShaderHotReloader::take_reloads`](crate::ShaderHotReloader::take_reloads).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReloadEvent {
+    pub crate_name: String,
+    /// Shader crate features the reloaded shaders were built with.
+    pub features: Vec<String>,
+}
@@ -0,0 +1,220 @@
+//! SPIR-V targets rust-gpu can compile for, and checks of a configuration against them.
+
+#[cfg(feature = "compiler")]
+use {crate::compile::CompileConfig, crate::requirements};
+
+/// Every target rust-gpu accepts, with the SPIR-V version it emits.
+pub(crate) const KNOWN_TARGETS: &[(&str, (u8, u8))] = &[
+    ("spirv-unknown-spv1.0", (1, 0)),
+    ("spirv-unknown-spv1.1", (1, 1)),
+    ("spirv-unknown-spv1.2", (1, 2)),
+    ("spirv-unknown-spv1.3", (1, 3)),
+    ("spirv-unknown-spv1.4", (1, 4)),
+    ("spirv-unknown-spv1.5", (1, 5)),
+    ("spirv-unknown-spv1.6", (1, 6)),
+    ("spirv-unknown-vulkan1.0", (1, 0)),
+    ("spirv-unknown-vulkan1.1", (1, 3)),
+    ("spirv-unknown-vulkan1.1spv1.4", (1, 4)),
+    ("spirv-unknown-vulkan1.2", (1, 5)),
+    ("spirv-unknown-vulkan1.3", (1, 6)),
+    ("spirv-unknown-vulkan1.4", (1, 6)),
+    ("spirv-unknown-opengl4.0", (1, 0)),
+    ("spirv-unknown-opengl4.1", (1, 0)),
+    ("spirv-unknown-opengl4.2", (1, 0)),
+    ("spirv-unknown-opengl4.3", (1, 0)),
+    ("spirv-unknown-opengl4.5", (1, 0)),
+    ("spirv-unknown-webgpu0", (1, 0)),
+];
+
+/// SPIR-V version a rust-gpu target emits, e.g. 1.3 for `spirv-unknown-vulkan1.1`.
+pub(crate) fn target_spirv_version(target: &str) -> Option<(u8, u8)> {
+    KNOWN_TARGETS
+        .iter()
+        .find(|(name, _)| *name == target)
+        .map(|&(_, version)| version)
+}
+
+/// Checks every target of a crate, and that each declared capability can be
+/// used with its declared extensions on each target.
+///
+/// # Errors
+///
+/// Returns an error listing every problem found, with the change that fixes it.
+#[cfg(feature = "compiler")]
+pub(crate) fn validate(config: &CompileConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let mut problems = Vec::new();
+
+    for target in std::iter::once(&config.target).chain(&config.extra_targets) {
+        let Some(version) = target_spirv_version(target) else {
+            let mut problem = format!("unknown SPIR-V target \"{}\"", target);
+            if let Some(suggestion) = closest_target(target) {
+                problem.push_str(&format!("; did you mean \"{}\"?", suggestion));
+            }
+            problems.push(problem);
+            continue;
+        };
+
+        for &capability in &config.capabilities {
+            let Some(requirement) = requirements::requirement(capability) else {
+                continue;
+            };
+            if version < requirement.min_version {
+                problems.push(format!(
+                    "capability {:?} needs SPIR-V {}.{}, but {} emits SPIR-V {}.{}",
+                    capability,
+                    requirement.min_version.0,
+                    requirement.min_version.1,
+                    target,
+                    version.0,
+                    version.1
+                ));
+            } else if !requirement.is_satisfied(version, config.extensions.as_slice()) {
+                let mut problem = format!(
+                    "capability {:?} on {} needs extension {}",
+                    capability,
+                    target,
+                    requirement
+                        .extensions
+                        .iter()
+                        .map(|ext| format!(".extension(\"{}\")", ext))
+                        .collect::<Vec<_>>()
+                        .join(" or ")
+                );
+                if let Some((major, minor)) = requirement.core_since {
+                    problem.push_str(&format!(
+                        ", or a target emitting SPIR-V {}.{} or later",
+                        major, minor
+                    ));
+                }
+                problems.push(problem);
+            }
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid configuration for shader crate {}:\n  {}",
+            config.crate_name(),
+            problems.join("\n  ")
+        )
+        .into())
+    }
+}
+
+/// Known target closest to a misspelt one, if any is close enough to be a typo.
+#[cfg(feature = "compiler")]
+fn closest_target(target: &str) -> Option<&'static str> {
+    KNOWN_TARGETS
+        .iter()
+        .map(|&(name, _)| (edit_distance(target, name), name))
+        .min()
+        .filter(|&(distance, _)| distance <= 3)
+        .map(|(_, name)| name)
+}
+
+/// Levenshtein distance between two strings, by characters.
+#[cfg(feature = "compiler")]
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(all(test, feature = "compiler"))]
+mod tests {
+    use super::*;
+    use spirv::Capability;
+    use std::path::Path;
+
+    fn config(target: &str) -> CompileConfig {
+        let mut config = CompileConfig::new(Path::new("shaders"));
+        config.target = target.to_string();
+        config
+    }
+
+    fn problems(config: &CompileConfig) -> Vec<String> {
+        let error = validate(config).unwrap_err().to_string();
+        let mut lines = error.lines();
+        assert_eq!(
+            lines.next(),
+            Some("invalid configuration for shader crate shaders:")
+        );
+        lines.map(|line| line.trim().to_string()).collect()
+    }
+
+    #[test]
+    fn measures_edit_distance() {
+        assert_eq!(edit_distance("", "spv"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("vulkan1.3", "vulkan1.3"), 0);
+    }
+
+    #[test]
+    fn suggests_the_closest_target() {
+        assert_eq!(
+            closest_target("spirv-unknown-vulkan13"),
+            Some("spirv-unknown-vulkan1.3")
+        );
+        assert_eq!(closest_target("x86_64-unknown-linux-gnu"), None);
+        assert_eq!(
+            problems(&config("spirv-unknown-vulkan13")),
+            ["unknown SPIR-V target \"spirv-unknown-vulkan13\"; \
+                 did you mean \"spirv-unknown-vulkan1.3\"?"]
+        );
+    }
+
+    #[test]
+    fn requires_extensions_of_capabilities() {
+        let mut config = config("spirv-unknown-vulkan1.2");
+        config.capabilities.push(Capability::RayTracingKHR);
+        assert_eq!(
+            problems(&config),
+            [
+                "capability RayTracingKHR on spirv-unknown-vulkan1.2 needs extension \
+                 .extension(\"SPV_KHR_ray_tracing\")"
+            ]
+        );
+
+        config.extensions.push("SPV_KHR_ray_tracing".to_string());
+        validate(&config).unwrap();
+
+        // Core since 1.5, so only older targets need the extension
+        let mut config = self::config("spirv-unknown-vulkan1.1");
+        config.capabilities.push(Capability::VulkanMemoryModel);
+        assert_eq!(
+            problems(&config),
+            [
+                "capability VulkanMemoryModel on spirv-unknown-vulkan1.1 needs extension \
+                 .extension(\"SPV_KHR_vulkan_memory_model\"), \
+                 or a target emitting SPIR-V 1.5 or later"
+            ]
+        );
+    }
+
+    #[test]
+    fn requires_the_minimum_version_of_capabilities() {
+        let mut config = config("spirv-unknown-vulkan1.2");
+        config
+            .extra_targets
+            .push("spirv-unknown-vulkan1.1".to_string());
+        config.capabilities.push(Capability::RayTracingKHR);
+        config.extensions.push("SPV_KHR_ray_tracing".to_string());
+        // The extension does not help below the minimum version
+        assert_eq!(
+            problems(&config),
+            ["capability RayTracingKHR needs SPIR-V 1.4, \
+                 but spirv-unknown-vulkan1.1 emits SPIR-V 1.3"]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::ShaderOutputDir;
+use crate::{DeviceProfile, ShaderOutputDir};
 use bevy::prelude::*;
 use parking_lot::Mutex;
 use std::sync::Arc;
@@ -52,6 +52,10 @@ use vulkano::device::Device;
 ///         );
 ///         Ok(())
 ///     }
+///
+///     fn shader_dependencies(&self) -> Vec<&'static str> {
+///         vec![Self::VERTEX_SHADER, Self::FRAGMENT_SHADER]
+///     }
 /// }
 /// ```
 pub trait HotReloadable {
@@ -64,6 +68,15 @@ pub trait HotReloadable {
         device: Arc<Device>,
         shader_paths: &ShaderOutputDir,
     ) -> Result<(), Box<dyn std::error::Error>>;
+
+    /// Shader files the pipelines load.
+    ///
+    /// [`HotReloadableTask::reload`] checks them against the device before
+    /// calling [`Self::recreate_pipeline`]. The default empty list checks every
+    /// module of the generation.
+    fn shader_dependencies(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
 }
 
 /// Resource wrapper for a hot-reloadable task.
@@ -83,6 +96,52 @@ impl<T> HotReloadableTask<T> {
     }
 }
 
+impl<T: HotReloadable> HotReloadableTask<T> {
+    /// Rebuilds the task's pipelines with newly compiled shaders.
+    ///
+    /// The shaders the task depends on, or every module if it names none, are
+    /// checked against the device first, so an incompatible reload keeps every
+    /// current pipeline. Checking and rebuilding use one snapshot, so a compile
+    /// published in between is not loaded unchecked. Afterwards, cached shader
+    /// modules no pipeline uses any more are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - Vulkan device the pipelines are created on
+    /// * `shader_paths` - Output directory of the reloaded shaders
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a shader declares more than the device has enabled,
+    /// or [`HotReloadable::recreate_pipeline`] fails.
+    pub fn reload(
+        &self,
+        device: Arc<Device>,
+        shader_paths: &ShaderOutputDir,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let snap = shader_paths.snapshot();
+        let mut task = self.task.lock();
+        let mut dependencies: Vec<String> = task
+            .shader_dependencies()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        if dependencies.is_empty() {
+            dependencies = snap
+                .files()?
+                .into_iter()
+                .filter(|name| name.ends_with(".spv"))
+                .collect();
+        }
+        let dependencies: Vec<&str> = dependencies.iter().map(String::as_str).collect();
+        snap.check_compatible(&DeviceProfile::from_device(&device), &dependencies)?;
+        task.recreate_pipeline(device, &snap)?;
+        // The replaced pipelines held the only other references to old modules
+        snap.evict_unused_shaders();
+        Ok(())
+    }
+}
+
 impl<T> Clone for HotReloadableTask<T> {
     fn clone(&self) -> Self {
         Self {
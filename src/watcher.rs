@@ -1,21 +1,386 @@
-use crate::ShaderHotReloaderBuilder;
-use crate::compile::compile_shaders;
+use crate::compile::{CompileConfig, stage_shaders};
+use crate::diagnostics::{MissingRequirements, Suggestion};
+use crate::pack::{Compression, PackHeader};
+use crate::publish::{self, Publisher, Staging};
+use crate::status::{CompileState, ReloadEvent, ReloadStatus};
+use crate::{GenerationInfo, ShaderHotReloaderBuilder, ShaderOutputDir};
 use bevy::prelude::Resource;
 use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
-use spirv_builder::Capability;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{Receiver, Sender, channel};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+/// Times a build is retried with newly reported capabilities and extensions.
+const MAX_AUTO_RETRIES: usize = 3;
+
 /// Resource for managing shader hot reloading.
 ///
-/// Watches the shader crate directory for changes and automatically recompiles
-/// SPIR-V shaders when source files are modified.
+/// Watches one or more shader crate directories for changes and automatically
+/// recompiles the crate that owns a modified source file.
+///
+/// Methods without a crate name act on the first crate passed to the builder.
+/// Generations are numbered by a crate's main target; moving between them
+/// moves every target and permutation to the output of the same compile.
 #[derive(Resource)]
 pub struct ShaderHotReloader {
     _watcher: RecommendedWatcher,
-    reload_receiver: Arc<Mutex<Receiver<()>>>,
+    reload_sender: Arc<Mutex<Sender<ReloadEvent>>>,
+    reload_receiver: Arc<Mutex<Receiver<ReloadEvent>>>,
+    crates: Vec<Arc<ShaderCrate>>,
+}
+
+/// A shader crate managed by the reloader.
+struct ShaderCrate {
+    name: String,
+    /// Canonical crate directory, matched against changed paths.
+    root: PathBuf,
+    /// One build per target and permutation, the first being the crate's main
+    /// target with its default features.
+    builds: Vec<VariantBuild>,
+    state: parking_lot::Mutex<CompileState>,
+    /// Features the default-feature builds use, changed at runtime by `set_features`.
+    features: parking_lot::Mutex<Vec<String>>,
+    /// Declarations `auto_capabilities` added on top of the builder's.
+    session_requirements: parking_lot::Mutex<Vec<Suggestion>>,
+    /// Declarations the last failed compile was missing.
+    suggestions: parking_lot::Mutex<Vec<Suggestion>>,
+    /// Held while compiling, as concurrent compiles would contend for the same
+    /// target directory and publish out of order.
+    compile_lock: parking_lot::Mutex<()>,
+    output_dir: ShaderOutputDir,
+    last_compile_time: Mutex<Option<Instant>>,
+}
+
+/// The shader crate built for one SPIR-V target and feature permutation.
+struct VariantBuild {
+    config: CompileConfig,
+    publisher: Publisher,
+}
+
+/// Outcome of building one variant.
+struct VariantOutcome {
+    /// Staging directory holding the variant's modules, not yet published.
+    result: Result<Staging, String>,
+    /// Shader crate features the build used.
+    features: Vec<String>,
+    /// Declarations added by `auto_capabilities` for the build to succeed.
+    added: Vec<Suggestion>,
+    /// Declarations the failed build was missing, if the diagnostics named any.
+    missing: Vec<Suggestion>,
+}
+
+impl VariantBuild {
+    /// Builds the variant with `config`, which carries the crate's runtime
+    /// settings, into a staging directory of its publisher.
+    ///
+    /// With `auto_capabilities`, a build failing for lack of capabilities or
+    /// extensions is retried with them added.
+    fn compile(
+        &self,
+        mut config: CompileConfig,
+        state: &parking_lot::Mutex<CompileState>,
+    ) -> VariantOutcome {
+        let mut added = Vec::new();
+        let mut retries = 0;
+        loop {
+            let error = match stage_shaders(&config, &self.publisher, state) {
+                Ok(staging) => {
+                    return VariantOutcome {
+                        result: Ok(staging),
+                        features: config.features,
+                        added,
+                        missing: Vec::new(),
+                    };
+                }
+                Err(e) => e,
+            };
+            let missing = error
+                .downcast_ref::<MissingRequirements>()
+                .map(|missing| missing.suggestions.clone())
+                .unwrap_or_default();
+            if !config.auto_capabilities || missing.is_empty() || retries == MAX_AUTO_RETRIES {
+                return VariantOutcome {
+                    result: Err(format!("{}: {}", self.name(), error)),
+                    features: config.features,
+                    added,
+                    missing,
+                };
+            }
+
+            let calls: Vec<String> = missing.iter().map(|s| s.to_string()).collect();
+            println!("Retrying {} with {}", self.name(), calls.join(" and "));
+            for suggestion in missing {
+                suggestion.apply(&mut config);
+                added.push(suggestion);
+            }
+            retries += 1;
+        }
+    }
+
+    /// Target, followed by the permutation name if there is one.
+    fn name(&self) -> String {
+        match &self.config.permutation {
+            Some(permutation) => format!("{} ({})", self.config.target, permutation),
+            None => self.config.target.clone(),
+        }
+    }
+}
+
+impl ShaderCrate {
+    fn new(config: CompileConfig) -> Self {
+        let root = config
+            .shader_crate_path
+            .canonicalize()
+            .unwrap_or_else(|_| config.shader_crate_path.clone());
+        let builds: Vec<VariantBuild> = config
+            .variants()
+            .into_iter()
+            .map(|config| VariantBuild {
+                publisher: Publisher::new(config.published_dir(), config.kept_generations),
+                config,
+            })
+            .collect();
+        Self {
+            name: config.crate_name().to_string(),
+            root,
+            state: parking_lot::Mutex::new(CompileState::Idle),
+            features: parking_lot::Mutex::new(config.features.clone()),
+            session_requirements: parking_lot::Mutex::new(Vec::new()),
+            suggestions: parking_lot::Mutex::new(Vec::new()),
+            compile_lock: parking_lot::Mutex::new(()),
+            output_dir: ShaderOutputDir::with_variants(
+                builds
+                    .iter()
+                    .map(|build| build.config.output_dir())
+                    .collect(),
+            ),
+            last_compile_time: Mutex::new(None),
+            builds,
+        }
+    }
+
+    /// Publisher of the crate's main target.
+    fn publisher(&self) -> &Publisher {
+        &self.builds[0].publisher
+    }
+
+    /// Compiles every target and permutation, recording the outcome in the crate's state.
+    ///
+    /// Variants are built in parallel, and published only once all of them
+    /// have built, so a failing variant leaves every variant on its previous
+    /// generation; see [`Self::publish`] for a variant failing to publish.
+    /// Returns the generation published for the main target.
+    fn compile(&self) -> Result<GenerationInfo, Box<dyn std::error::Error>> {
+        let _guard = self.compile_lock.lock();
+        *self.state.lock() = CompileState::Compiling;
+        let features = self.features.lock().clone();
+        let session_requirements = self.session_requirements.lock().clone();
+        let outcomes: Vec<VariantOutcome> = std::thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .builds
+                .iter()
+                .map(|build| {
+                    let mut config = build.config.clone();
+                    if config.permutation.is_none() {
+                        config.features = features.clone();
+                    }
+                    for requirement in &session_requirements {
+                        requirement.apply(&mut config);
+                    }
+                    scope.spawn(move || build.compile(config, &self.state))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle.join().unwrap_or_else(|_| VariantOutcome {
+                        result: Err("compile thread panicked".to_string()),
+                        features: Vec::new(),
+                        added: Vec::new(),
+                        missing: Vec::new(),
+                    })
+                })
+                .collect()
+        });
+
+        let mut added = Vec::new();
+        let mut missing = Vec::new();
+        for outcome in &outcomes {
+            for suggestion in &outcome.added {
+                if !added.contains(suggestion) && !session_requirements.contains(suggestion) {
+                    added.push(suggestion.clone());
+                }
+            }
+            for suggestion in &outcome.missing {
+                if !missing.contains(suggestion) {
+                    missing.push(suggestion.clone());
+                }
+            }
+        }
+        if !added.is_empty() {
+            let calls: Vec<String> = added.iter().map(|s| s.to_string()).collect();
+            eprintln!(
+                "Warning: the builder configuration of {} is out of date. Added {} for this \
+                 session; add them to the ShaderHotReloaderBuilder to keep them.",
+                self.name,
+                calls.join(" and ")
+            );
+            self.session_requirements.lock().extend(added);
+        }
+        *self.suggestions.lock() = missing;
+
+        let errors: Vec<&str> = outcomes
+            .iter()
+            .filter_map(|outcome| outcome.result.as_ref().err().map(String::as_str))
+            .collect();
+        let result = if errors.is_empty() {
+            self.publish(outcomes)
+        } else {
+            Err(errors.join("\n").into())
+        };
+        *self.state.lock() = match &result {
+            Ok(_) => CompileState::Idle,
+            Err(e) => CompileState::Failed(e.to_string()),
+        };
+        result
+    }
+
+    /// Publishes the staged output of every variant under one compile id.
+    ///
+    /// If a variant fails to publish, the variants published before it are
+    /// checked out at their previous generation again, so no variant is left
+    /// ahead of the others. A variant with no previous generation keeps its first.
+    fn publish(
+        &self,
+        outcomes: Vec<VariantOutcome>,
+    ) -> Result<GenerationInfo, Box<dyn std::error::Error>> {
+        let compile_id = publish::compile_id();
+        let mut published = Vec::new();
+        for (build, outcome) in self.builds.iter().zip(outcomes) {
+            let previous = build.publisher.current();
+            let result = outcome.result.and_then(|staging| {
+                build
+                    .publisher
+                    .publish(staging, &build.config.target, &outcome.features, compile_id)
+                    .map_err(|e| format!("{}: {}", build.name(), e))
+            });
+            match result {
+                Ok(info) => published.push((info, previous)),
+                Err(error) => {
+                    let mut errors = vec![error];
+                    for (build, (_, previous)) in self.builds.iter().zip(&published) {
+                        if let Some(previous) = *previous
+                            && let Err(e) = build.publisher.checkout(previous)
+                        {
+                            errors.push(format!(
+                                "{}: could not return to generation {}: {}",
+                                build.name(),
+                                previous,
+                                e
+                            ));
+                        }
+                    }
+                    return Err(errors.join("\n").into());
+                }
+            }
+        }
+        Ok(published.swap_remove(0).0)
+    }
+
+    /// Generations of the main target every other target and permutation has a
+    /// generation from the same compile for, oldest first.
+    fn generations(&self) -> Result<Vec<GenerationInfo>, Box<dyn std::error::Error>> {
+        let mut complete = Vec::new();
+        for info in self.publisher().generations()? {
+            if self.variant_generations(&info)?.is_some() {
+                complete.push(info);
+            }
+        }
+        Ok(complete)
+    }
+
+    /// Generation of every variant published by the compile that published
+    /// `info` for the main target, in build order.
+    ///
+    /// Returns `None` if a variant no longer has one, or the generation predates
+    /// compile ids and cannot be matched.
+    fn variant_generations(
+        &self,
+        info: &GenerationInfo,
+    ) -> Result<Option<Vec<u64>>, Box<dyn std::error::Error>> {
+        if self.builds.len() == 1 {
+            return Ok(Some(vec![info.generation]));
+        }
+        if info.compile_id == 0 {
+            return Ok(None);
+        }
+        let mut generations = vec![info.generation];
+        for build in &self.builds[1..] {
+            match build.publisher.find_compile(info.compile_id)? {
+                Some(generation) => generations.push(generation),
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(generations))
+    }
+
+    /// Makes a kept generation of the main target current, along with the
+    /// generations the same compile published for every other variant.
+    ///
+    /// Fails without moving any variant if one of them lacks that compile.
+    /// Returns the metadata of the main target's generation.
+    fn checkout(&self, generation: u64) -> Result<GenerationInfo, Box<dyn std::error::Error>> {
+        let info = self
+            .publisher()
+            .generations()?
+            .into_iter()
+            .find(|info| info.generation == generation)
+            .ok_or_else(|| format!("shader generation {} is not on disk", generation))?;
+        let generations = self.variant_generations(&info)?.ok_or_else(|| {
+            format!(
+                "shader generation {} was not kept for every target and permutation",
+                generation
+            )
+        })?;
+        for (build, generation) in self.builds.iter().zip(generations) {
+            build.publisher.checkout(generation)?;
+        }
+        Ok(info)
+    }
+
+    /// Returns false if the crate was compiled within the debounce interval.
+    fn debounce(&self, debounce_ms: u64) -> bool {
+        if let Ok(mut last_time) = self.last_compile_time.lock() {
+            if let Some(last) = *last_time
+                && last.elapsed() < Duration::from_millis(debounce_ms)
+            {
+                return false;
+            }
+            *last_time = Some(Instant::now());
+        }
+        true
+    }
+
+    fn status(&self) -> ReloadStatus {
+        ReloadStatus {
+            state: self.state.lock().clone(),
+            current_generation: self.publisher().current(),
+            target_dir: self.builds[0].config.spirv_target_dir(),
+            features: self.features.lock().clone(),
+            suggestions: self.suggestions.lock().clone(),
+            session_requirements: self.session_requirements.lock().clone(),
+        }
+    }
+
+    /// Event announcing `info`, a generation of the main target, with the
+    /// features it was built with rather than the ones set now.
+    fn reload_event(&self, info: &GenerationInfo) -> ReloadEvent {
+        ReloadEvent {
+            crate_name: self.name.clone(),
+            features: info.features.clone(),
+        }
+    }
 }
 
 impl ShaderHotReloader {
@@ -25,36 +390,36 @@ impl ShaderHotReloader {
     }
 
     pub(crate) fn new_with_config(
-        shader_crate_path: &Path,
-        target: &str,
-        capabilities: Vec<Capability>,
-        extensions: Vec<String>,
-        multimodule: bool,
+        configs: Vec<CompileConfig>,
         debounce_ms: u64,
-        strip_capabilities: Vec<Capability>,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        let (reload_tx, reload_rx): (Sender<()>, Receiver<()>) = channel();
+        let (reload_tx, reload_rx): (Sender<ReloadEvent>, Receiver<ReloadEvent>) = channel();
         let reload_tx = Arc::new(Mutex::new(reload_tx));
-        let last_compile_time = Arc::new(Mutex::new(None::<Instant>));
-        let last_compile_time_clone = last_compile_time.clone();
-
-        let shader_crate_path_buf = shader_crate_path.to_path_buf();
-
-        println!("Performing initial shader compilation...");
-        compile_shaders(
-            &shader_crate_path_buf,
-            target,
-            &capabilities,
-            &extensions,
-            multimodule,
-            &strip_capabilities,
-        )?;
-        println!("Initial shader compilation complete");
+        let reload_sender = reload_tx.clone();
+
+        let crates: Vec<Arc<ShaderCrate>> = configs
+            .into_iter()
+            .map(|config| Arc::new(ShaderCrate::new(config)))
+            .collect();
+        let watched_crates = crates.clone();
 
-        let shader_crate_path_for_closure = shader_crate_path_buf.clone();
-        let target_owned = target.to_string();
-        let capabilities_owned = capabilities.clone();
-        let extensions_owned = extensions.clone();
+        for shader_crate in &crates {
+            println!(
+                "Performing initial shader compilation of {}...",
+                shader_crate.name
+            );
+            match shader_crate.compile() {
+                Ok(_) => println!("Initial shader compilation complete"),
+                // Keep serving the last good shaders rather than refusing to start
+                Err(e) => match shader_crate.publisher().current() {
+                    Some(generation) => eprintln!(
+                        "Initial shader compilation failed: {}\nUsing last good generation {}",
+                        e, generation
+                    ),
+                    None => return Err(format!("{}: {}", shader_crate.name, e).into()),
+                },
+            }
+        }
 
         let mut watcher = RecommendedWatcher::new(
             move |res: Result<Event, notify::Error>| {
@@ -63,37 +428,41 @@ impl ShaderHotReloader {
                         event.kind,
                         notify::EventKind::Modify(_) | notify::EventKind::Create(_)
                     ) {
-                        let is_rust_file = event
-                            .paths
-                            .iter()
-                            .any(|p| p.extension().and_then(|e| e.to_str()) == Some("rs"));
-
-                        if is_rust_file {
-                            if let Ok(mut last_time) = last_compile_time_clone.lock() {
-                                if let Some(last) = *last_time {
-                                    if last.elapsed() < Duration::from_millis(debounce_ms) {
-                                        return;
-                                    }
-                                }
-                                *last_time = Some(Instant::now());
+                        // Map every changed Rust file to the crate that owns it
+                        let mut changed: Vec<&Arc<ShaderCrate>> = Vec::new();
+                        for path in &event.paths {
+                            if path.extension().and_then(|e| e.to_str()) != Some("rs") {
+                                continue;
                             }
+                            let owner = watched_crates
+                                .iter()
+                                .filter(|c| path.starts_with(&c.root))
+                                .max_by_key(|c| c.root.components().count());
+                            if let Some(owner) = owner
+                                && !changed.iter().any(|c| Arc::ptr_eq(c, owner))
+                            {
+                                changed.push(owner);
+                            }
+                        }
+
+                        for shader_crate in changed {
+                            if !shader_crate.debounce(debounce_ms) {
+                                continue;
+                            }
+
+                            println!(
+                                "Shader source of {} changed, recompiling...",
+                                shader_crate.name
+                            );
 
-                            println!("Shader source changed, recompiling...");
-
-                            if let Err(e) = compile_shaders(
-                                &shader_crate_path_for_closure,
-                                &target_owned,
-                                &capabilities_owned,
-                                &extensions_owned,
-                                multimodule,
-                                &strip_capabilities,
-                            ) {
-                                eprintln!("Shader compilation failed: {}", e);
-                            } else {
-                                println!("Shaders recompiled successfully");
-                                if let Ok(tx) = reload_tx.lock() {
-                                    let _ = tx.send(());
+                            match shader_crate.compile() {
+                                Ok(info) => {
+                                    println!("Shaders recompiled successfully");
+                                    if let Ok(tx) = reload_tx.lock() {
+                                        let _ = tx.send(shader_crate.reload_event(&info));
+                                    }
                                 }
+                                Err(e) => eprintln!("Shader compilation failed: {}", e),
                             }
                         }
                     }
@@ -102,31 +471,396 @@ impl ShaderHotReloader {
             Config::default().with_poll_interval(Duration::from_millis(debounce_ms)),
         )?;
 
-        watcher.watch(&shader_crate_path_buf, RecursiveMode::Recursive)?;
         println!("Shader hot reloading enabled");
-        println!("Watching: {}", shader_crate_path_buf.display());
+        for shader_crate in &crates {
+            watcher.watch(&shader_crate.root, RecursiveMode::Recursive)?;
+            println!("Watching: {}", shader_crate.root.display());
+        }
 
         Ok(Self {
             _watcher: watcher,
+            reload_sender,
             reload_receiver: Arc::new(Mutex::new(reload_rx)),
+            crates,
         })
     }
 
+    /// Returns the names of the watched shader crates, in builder order.
+    pub fn crate_names(&self) -> impl Iterator<Item = &str> {
+        self.crates.iter().map(|c| c.name.as_str())
+    }
+
+    fn primary(&self) -> &ShaderCrate {
+        &self.crates[0]
+    }
+
+    fn find(&self, crate_name: &str) -> Result<&ShaderCrate, Box<dyn std::error::Error>> {
+        self.crates
+            .iter()
+            .find(|c| c.name == crate_name)
+            .map(|c| c.as_ref())
+            .ok_or_else(|| format!("no shader crate named {}", crate_name).into())
+    }
+
+    /// Returns an output directory that loads the shaders this reloader publishes.
+    ///
+    /// Follows the configured target directory, so it stays in sync when the
+    /// reloader builds outside the default `target/spirv-builder`.
+    pub fn output_dir(&self) -> ShaderOutputDir {
+        self.primary().output_dir.clone()
+    }
+
+    /// Returns the output directory of a watched shader crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no watched crate has that name.
+    pub fn crate_output_dir(
+        &self,
+        crate_name: &str,
+    ) -> Result<ShaderOutputDir, Box<dyn std::error::Error>> {
+        Ok(self.find(crate_name)?.output_dir.clone())
+    }
+
+    /// Returns what the reloader is doing, including any cargo lock it is waiting on.
+    pub fn status(&self) -> ReloadStatus {
+        self.primary().status()
+    }
+
+    /// Returns the status of a watched shader crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no watched crate has that name.
+    pub fn crate_status(
+        &self,
+        crate_name: &str,
+    ) -> Result<ReloadStatus, Box<dyn std::error::Error>> {
+        Ok(self.find(crate_name)?.status())
+    }
+
+    /// Returns the shader generations kept on disk, oldest first.
+    ///
+    /// Only generations whose compile is still on disk for every target and
+    /// permutation are listed, as only those can be checked out.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a generation's metadata cannot be read.
+    pub fn generations(&self) -> Result<Vec<GenerationInfo>, Box<dyn std::error::Error>> {
+        self.primary().generations()
+    }
+
+    /// Returns the shader generations of a watched shader crate kept on disk, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no watched crate has that name, or a generation's
+    /// metadata cannot be read.
+    pub fn crate_generations(
+        &self,
+        crate_name: &str,
+    ) -> Result<Vec<GenerationInfo>, Box<dyn std::error::Error>> {
+        self.find(crate_name)?.generations()
+    }
+
+    /// Returns the generation shaders are currently loaded from.
+    pub fn current_generation(&self) -> Option<u64> {
+        self.primary().publisher().current()
+    }
+
+    /// Returns the generation a watched shader crate's shaders are currently loaded from.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no watched crate has that name.
+    pub fn crate_current_generation(
+        &self,
+        crate_name: &str,
+    ) -> Result<Option<u64>, Box<dyn std::error::Error>> {
+        Ok(self.find(crate_name)?.publisher().current())
+    }
+
+    /// Switches back to the generation before the current one and signals a reload.
+    ///
+    /// Returns the generation that is now current.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no earlier generation is kept on disk.
+    pub fn rollback(&self) -> Result<u64, Box<dyn std::error::Error>> {
+        self.rollback_crate(self.primary())
+    }
+
+    /// Switches a watched shader crate back to the generation before its
+    /// current one and signals a reload of that crate.
+    ///
+    /// Returns the generation that is now current.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no watched crate has that name, or no earlier
+    /// generation is kept on disk.
+    pub fn crate_rollback(&self, crate_name: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        self.rollback_crate(self.find(crate_name)?)
+    }
+
+    fn rollback_crate(
+        &self,
+        shader_crate: &ShaderCrate,
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        let current = shader_crate
+            .publisher()
+            .current()
+            .ok_or("no shader generation published")?;
+        let previous = shader_crate
+            .generations()?
+            .into_iter()
+            .map(|info| info.generation)
+            .filter(|&generation| generation < current)
+            .max()
+            .ok_or("no earlier shader generation to roll back to")?;
+        self.checkout_crate(shader_crate, previous)?;
+        Ok(previous)
+    }
+
+    /// Writes the current generation of the primary crate's main target to a
+    /// single-file shader pack.
+    ///
+    /// See [`ShaderOutputDir::export_pack`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to write the pack to
+    /// * `compression` - How to store file data in the pack
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no generation has been published, or the pack
+    /// cannot be written.
+    pub fn export_pack(
+        &self,
+        path: impl AsRef<Path>,
+        compression: Compression,
+    ) -> Result<PackHeader, Box<dyn std::error::Error>> {
+        self.export_crate_pack(self.primary(), path.as_ref(), compression)
+    }
+
+    /// Writes the current generation of a watched shader crate's main target
+    /// to a single-file shader pack.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    /// * `path` - Path to write the pack to
+    /// * `compression` - How to store file data in the pack
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no watched crate has that name, no generation has
+    /// been published, or the pack cannot be written.
+    pub fn crate_export_pack(
+        &self,
+        crate_name: &str,
+        path: impl AsRef<Path>,
+        compression: Compression,
+    ) -> Result<PackHeader, Box<dyn std::error::Error>> {
+        self.export_crate_pack(self.find(crate_name)?, path.as_ref(), compression)
+    }
+
+    fn export_crate_pack(
+        &self,
+        shader_crate: &ShaderCrate,
+        path: &Path,
+        compression: Compression,
+    ) -> Result<PackHeader, Box<dyn std::error::Error>> {
+        let generation = shader_crate
+            .publisher()
+            .current()
+            .ok_or("no shader generation published")?;
+        let header = shader_crate
+            .output_dir
+            .snapshot()
+            .export_pack(path, compression)?;
+        println!(
+            "Exported shader generation {} of {} to {}",
+            generation,
+            shader_crate.name,
+            path.display()
+        );
+        Ok(header)
+    }
+
+    /// Makes a kept generation current and signals a reload.
+    ///
+    /// Every target and permutation switches to the output of the compile that
+    /// published the generation. The next successful compile publishes a new
+    /// generation on top of it.
+    ///
+    /// # Arguments
+    ///
+    /// * `generation` - Generation number, as listed by [`Self::generations`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the generation, or the output of its compile for
+    /// another target or permutation, is no longer on disk.
+    pub fn checkout(&self, generation: u64) -> Result<(), Box<dyn std::error::Error>> {
+        self.checkout_crate(self.primary(), generation)
+    }
+
+    /// Makes a kept generation of a watched shader crate current and signals a
+    /// reload of that crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    /// * `generation` - Generation number, as listed by [`Self::crate_generations`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no watched crate has that name, or the generation,
+    /// or the output of its compile for another target or permutation, is no
+    /// longer on disk.
+    pub fn crate_checkout(
+        &self,
+        crate_name: &str,
+        generation: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.checkout_crate(self.find(crate_name)?, generation)
+    }
+
+    fn checkout_crate(
+        &self,
+        shader_crate: &ShaderCrate,
+        generation: u64,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let info = shader_crate.checkout(generation)?;
+        println!(
+            "Switched {} to shader generation {}",
+            shader_crate.name, generation
+        );
+        if let Ok(tx) = self.reload_sender.lock() {
+            let _ = tx.send(shader_crate.reload_event(&info));
+        }
+        Ok(())
+    }
+
+    /// Sets the shader crate features to build with and recompiles in the background.
+    ///
+    /// Replaces the features the default-feature builds use; named permutations
+    /// keep their own. The reload event of the rebuild carries the new feature
+    /// set, and [`Self::status`] reports it from now on.
+    ///
+    /// # Arguments
+    ///
+    /// * `features` - Shader crate features to enable (e.g., `["debug_normals"]`)
+    pub fn set_features<I, S>(&self, features: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rebuild_with_features(self.crates[0].clone(), features);
+    }
+
+    /// Sets the features of a watched shader crate and recompiles it in the background.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - Name of the shader crate, as listed by [`Self::crate_names`]
+    /// * `features` - Shader crate features to enable
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no watched crate has that name.
+    pub fn set_crate_features<I, S>(
+        &self,
+        crate_name: &str,
+        features: I,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let shader_crate = self
+            .crates
+            .iter()
+            .find(|c| c.name == crate_name)
+            .ok_or_else(|| format!("no shader crate named {}", crate_name))?;
+        self.rebuild_with_features(shader_crate.clone(), features);
+        Ok(())
+    }
+
+    fn rebuild_with_features<I, S>(&self, shader_crate: Arc<ShaderCrate>, features: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let features: Vec<String> = features.into_iter().map(Into::into).collect();
+        *shader_crate.features.lock() = features.clone();
+        let reload_tx = self.reload_sender.clone();
+
+        std::thread::spawn(move || {
+            println!(
+                "Features of {} set to [{}], recompiling...",
+                shader_crate.name,
+                features.join(", ")
+            );
+            match shader_crate.compile() {
+                Ok(info) => {
+                    println!("Shaders recompiled successfully");
+                    if let Ok(tx) = reload_tx.lock() {
+                        let _ = tx.send(shader_crate.reload_event(&info));
+                    }
+                }
+                Err(e) => eprintln!("Shader compilation failed: {}", e),
+            }
+        });
+    }
+
     /// Checks if shaders have been recompiled since the last check.
     ///
     /// Returns true if a reload is available, false otherwise.
     /// This method is non-blocking and can be called frequently.
     #[inline]
     pub fn check_for_reload(&self) -> bool {
+        !self.take_reloads().is_empty()
+    }
+
+    /// Returns the crates recompiled since the last check.
+    ///
+    /// Each crate is listed once with the features of its latest rebuild,
+    /// however often it was recompiled. Like [`Self::check_for_reload`], this
+    /// consumes the pending reload signals.
+    pub fn take_reloads(&self) -> Vec<ReloadEvent> {
+        let mut reloaded: Vec<ReloadEvent> = Vec::new();
         if let Ok(receiver) = self.reload_receiver.lock() {
-            // Drain all pending reload signals and return true if any were present
-            let mut has_reload = false;
-            while receiver.try_recv().is_ok() {
-                has_reload = true;
+            // Drain all pending reload signals
+            while let Ok(event) = receiver.try_recv() {
+                reloaded.retain(|other| other.crate_name != event.crate_name);
+                reloaded.push(event);
             }
-            has_reload
-        } else {
-            false
         }
+        reloaded
     }
 }
@@ -0,0 +1,340 @@
+//! Generates `src/spv/grammar.rs` from the SPIR-V core grammar.
+//!
+//! ```text
+//! cargo run --manifest-path tools/gen-grammar/Cargo.toml -- \
+//!     path/to/SPIRV-Headers/include/spirv/unified1/spirv.core.grammar.json
+//! ```
+//!
+//! `additions.json` holds instructions, enumerants and operand kinds rust-gpu
+//! emits that the grammar predates, in the grammar's own layout. An addition
+//! replaces the grammar's instruction with the same opcode, or enumerant with
+//! the same value. An operand kind with a `category` is new; one without adds
+//! enumerants to the grammar's kind.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Definitions that do not depend on the grammar.
+const PRELUDE: &str = r#"/// How many times a logical operand may appear.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Quantifier {
+    One,
+    ZeroOrOne,
+    ZeroOrMore,
+}
+
+/// Operand layout of a single opcode.
+#[derive(Debug)]
+pub struct InstructionGrammar {
+    pub opcode: u16,
+    /// Name without the `Op` prefix.
+    pub name: &'static str,
+    pub operands: &'static [(OperandKind, Quantifier)],
+}
+
+impl InstructionGrammar {
+    /// Whether the instruction produces a result id.
+    pub fn has_result(&self) -> bool {
+        self.operands
+            .iter()
+            .take(2)
+            .any(|(kind, _)| *kind == OperandKind::IdResult)
+    }
+
+    /// Whether the instruction has a result type id.
+    pub fn has_result_type(&self) -> bool {
+        self.operands
+            .first()
+            .is_some_and(|(kind, _)| *kind == OperandKind::IdResultType)
+    }
+}
+
+/// Operands that follow an enumerant of a kind, or a bit of a mask kind.
+type EnumerantParameters = (OperandKind, u32, &'static [(OperandKind, Quantifier)]);
+
+/// Looks up the operand layout of `opcode`.
+pub fn lookup(opcode: u16) -> Option<&'static InstructionGrammar> {
+    INSTRUCTIONS
+        .binary_search_by_key(&opcode, |inst| inst.opcode)
+        .ok()
+        .map(|index| &INSTRUCTIONS[index])
+}
+
+/// Operands that follow an enumerant, or a single bit of a mask.
+pub fn parameters(kind: OperandKind, value: u32) -> &'static [(OperandKind, Quantifier)] {
+    PARAMETERS
+        .binary_search_by_key(&(kind, value), |&(kind, value, _)| (kind, value))
+        .map_or(&[], |index| PARAMETERS[index].2)
+}
+
+/// Whether operands of the kind are masks, with a parameter list per set bit.
+pub fn is_mask(kind: OperandKind) -> bool {
+    mask_bits(kind).is_some()
+}
+
+macro_rules! op {
+    ($opcode:literal, $name:ident, [$( ($kind:ident, $quant:ident) ),*]) => {
+        InstructionGrammar {
+            opcode: $opcode,
+            name: stringify!($name),
+            operands: &[$( (OperandKind::$kind, Quantifier::$quant) ),*],
+        }
+    };
+}
+
+macro_rules! params {
+    ($kind:ident, $value:literal, [$( ($param:ident, $quant:ident) ),*]) => {
+        (
+            OperandKind::$kind,
+            $value,
+            &[$( (OperandKind::$param, Quantifier::$quant) ),*],
+        )
+    };
+}
+"#;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args_os().skip(1);
+    let Some(grammar_path) = args.next().map(PathBuf::from) else {
+        return Err("usage: gen-grammar <spirv.core.grammar.json> [output]".into());
+    };
+    let tool_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let output = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| tool_dir.join("../../src/spv/grammar.rs"));
+
+    let grammar: Value = serde_json::from_slice(&std::fs::read(&grammar_path)?)?;
+    let additions: Value =
+        serde_json::from_slice(&std::fs::read(tool_dir.join("additions.json"))?)?;
+    let generated = generate(&grammar, &additions)?;
+    std::fs::write(&output, generated)?;
+    println!("wrote {}", output.display());
+    Ok(())
+}
+
+/// Operand kinds and quantifiers, as named in the generated code.
+type Operands = Vec<(String, &'static str)>;
+
+/// An operand kind's enumerants by value, keeping the first name of aliases.
+struct Enumerants {
+    is_mask: bool,
+    by_value: BTreeMap<u32, (String, Operands)>,
+}
+
+fn generate(grammar: &Value, additions: &Value) -> Result<String, Box<dyn std::error::Error>> {
+    let version = format!(
+        "{}.{} revision {}",
+        grammar["major_version"], grammar["minor_version"], grammar["revision"]
+    );
+
+    // Instructions by opcode, additions last so they replace the grammar's
+    // Aliases share an opcode; keep the grammar's first name, or an addition's
+    let mut instructions = BTreeMap::new();
+    let tagged = |value, replace| array(value, "instructions").map(move |inst| (inst, replace));
+    for (inst, replace) in tagged(grammar, false).chain(tagged(additions, true)) {
+        let opcode = inst["opcode"]
+            .as_u64()
+            .ok_or("instruction without opcode")?;
+        let name = inst["opname"]
+            .as_str()
+            .ok_or("instruction without opname")?;
+        let name = name.strip_prefix("Op").ok_or("opname without Op prefix")?;
+        if replace || !instructions.contains_key(&opcode) {
+            instructions.insert(opcode, (name.to_string(), operands(&inst["operands"])?));
+        }
+    }
+
+    // An addition with a category declares an enumerated kind the grammar
+    // lacks; it goes after the grammar's enumerated kinds
+    let mut kinds = Vec::new();
+    let mut enums: BTreeMap<String, Enumerants> = BTreeMap::new();
+    let mut enumerated = 0;
+    for kind in array(grammar, "operand_kinds").chain(array(additions, "operand_kinds")) {
+        let name = kind["kind"].as_str().ok_or("operand kind without name")?;
+        let category = kind["category"].as_str().unwrap_or_default();
+        if category.is_empty() || kinds.iter().any(|kind| kind == name) {
+            continue;
+        }
+        if category == "ValueEnum" || category == "BitEnum" {
+            kinds.insert(enumerated, name.to_string());
+            enumerated += 1;
+            enums.insert(
+                name.to_string(),
+                Enumerants {
+                    is_mask: category == "BitEnum",
+                    by_value: BTreeMap::new(),
+                },
+            );
+        } else {
+            kinds.push(name.to_string());
+        }
+    }
+    // Aliases share a value; keep the grammar's first name, or an addition's
+    for kind in array(grammar, "operand_kinds") {
+        add_enumerants(&mut enums, kind, false)?;
+    }
+    for kind in array(additions, "operand_kinds") {
+        add_enumerants(&mut enums, kind, true)?;
+    }
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "//! Operand layout of SPIR-V instructions and enumerants."
+    )?;
+    writeln!(out, "//!")?;
+    writeln!(
+        out,
+        "//! Generated by `tools/gen-grammar` from the SPIR-V core grammar {version},"
+    )?;
+    writeln!(
+        out,
+        "//! with `tools/gen-grammar/additions.json` applied; rerun it instead of"
+    )?;
+    writeln!(
+        out,
+        "//! editing this file. Capability and extension requirements live in"
+    )?;
+    writeln!(out, "//! [`crate::requirements`].")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/// Kind of a logical operand, as named by the SPIR-V grammar."
+    )?;
+    writeln!(
+        out,
+        "#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]"
+    )?;
+    writeln!(
+        out,
+        "#[allow(dead_code)] // Some kinds only appear inside other kinds"
+    )?;
+    writeln!(out, "pub enum OperandKind {{")?;
+    for kind in &kinds {
+        writeln!(out, "    {kind},")?;
+    }
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+    out.push_str(PRELUDE);
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/// Names of the bits of each mask kind, without aliases."
+    )?;
+    writeln!(out, "#[rustfmt::skip]")?;
+    writeln!(
+        out,
+        "pub fn mask_bits(kind: OperandKind) -> Option<&'static [(u32, &'static str)]> {{"
+    )?;
+    writeln!(out, "    Some(match kind {{")?;
+    for (kind, enumerants) in enums.iter().filter(|(_, e)| e.is_mask) {
+        let bits: Vec<String> = enumerants
+            .by_value
+            .iter()
+            .filter(|(value, _)| **value != 0)
+            .map(|(value, (name, _))| format!("({value:#x}, {name:?})"))
+            .collect();
+        writeln!(
+            out,
+            "        OperandKind::{kind} => &[{}],",
+            bits.join(", ")
+        )?;
+    }
+    writeln!(out, "        _ => return None,")?;
+    writeln!(out, "    }})")?;
+    writeln!(out, "}}")?;
+
+    writeln!(out)?;
+    writeln!(out, "// Sorted by opcode.")?;
+    writeln!(out, "#[rustfmt::skip]")?;
+    writeln!(out, "static INSTRUCTIONS: &[InstructionGrammar] = &[")?;
+    for (opcode, (name, operands)) in &instructions {
+        writeln!(out, "    op!({opcode}, {name}, [{}]),", layout(operands))?;
+    }
+    writeln!(out, "];")?;
+
+    // Sorted like `OperandKind`, then by value, for `parameters`
+    writeln!(out)?;
+    writeln!(out, "// Sorted by kind, then value.")?;
+    writeln!(out, "#[rustfmt::skip]")?;
+    writeln!(out, "static PARAMETERS: &[EnumerantParameters] = &[")?;
+    for kind in &kinds {
+        let Some(enumerants) = enums.get(kind) else {
+            continue;
+        };
+        for (value, (_, params)) in &enumerants.by_value {
+            if !params.is_empty() {
+                writeln!(
+                    out,
+                    "    params!({kind}, {value:#x}, [{}]),",
+                    layout(params)
+                )?;
+            }
+        }
+    }
+    writeln!(out, "];")?;
+    Ok(out)
+}
+
+fn add_enumerants(
+    enums: &mut BTreeMap<String, Enumerants>,
+    kind: &Value,
+    replace: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let name = kind["kind"].as_str().ok_or("operand kind without name")?;
+    let Some(enumerants) = enums.get_mut(name) else {
+        if replace {
+            return Err(format!("{name} is not an enumerated operand kind").into());
+        }
+        return Ok(());
+    };
+    for enumerant in array(kind, "enumerants") {
+        let value = match &enumerant["value"] {
+            Value::Number(value) => value.as_u64().ok_or("bad enumerant value")? as u32,
+            Value::String(value) => u32::from_str_radix(value.trim_start_matches("0x"), 16)?,
+            _ => return Err("enumerant without value".into()),
+        };
+        let name = enumerant["enumerant"]
+            .as_str()
+            .ok_or("enumerant without name")?;
+        if replace || !enumerants.by_value.contains_key(&value) {
+            enumerants.by_value.insert(
+                value,
+                (name.to_string(), operands(&enumerant["parameters"])?),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value[key].as_array().into_iter().flatten()
+}
+
+/// Operand kinds and quantifiers of an instruction or enumerant.
+fn operands(value: &Value) -> Result<Operands, Box<dyn std::error::Error>> {
+    let mut operands = Vec::new();
+    for operand in value.as_array().into_iter().flatten() {
+        let kind = operand["kind"].as_str().ok_or("operand without kind")?;
+        let quantifier = match operand["quantifier"].as_str() {
+            None => "One",
+            Some("?") => "ZeroOrOne",
+            Some("*") => "ZeroOrMore",
+            Some(other) => return Err(format!("unknown quantifier {other}").into()),
+        };
+        operands.push((kind.to_string(), quantifier));
+    }
+    Ok(operands)
+}
+
+fn layout(operands: &[(String, &str)]) -> String {
+    operands
+        .iter()
+        .map(|(kind, quantifier)| format!("({kind}, {quantifier})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}